@@ -3,9 +3,9 @@ This implements the "Counter" task from [7GUIs](https://eugenkiss.github.io/7gui
 */
 use druid_shell::kurbo;
 use guiver::widget_manager::WidgetManager;
-use guiver::{WidgetEventType, WidgetId};
+use guiver::{WidgetCommand, WidgetEventType, WidgetId, WidgetValue};
 use guiver_piet::{run, Command, Piet, PietWidgetManager, Region, WidgetType};
-use guiver_piet::{Clipboard, Event, PietApplication};
+use guiver_piet::{Clipboard, Event, PietApplication, WindowId};
 
 ///
 #[derive(Clone)]
@@ -30,6 +30,10 @@ impl App {
         let counter_text = widget_manager.widget_id_provider().next_widget_id();
         let counter_button = widget_manager.widget_id_provider().next_widget_id();
 
+        let counter_button_clicked = widget_manager
+            .widget_id_provider()
+            .next_event_observation_handle();
+
         widget_manager
             .handle_commands(vec![
                 // Create the widgets.
@@ -60,6 +64,7 @@ impl App {
                 // =================================================================================
                 Command::AddEventObservation(
                     counter_button,
+                    counter_button_clicked,
                     WidgetEventType::Clicked,
                     CustomEvent::Count,
                 ),
@@ -75,7 +80,7 @@ impl App {
 }
 
 impl PietApplication for App {
-    fn handle_event(&mut self, event: &Event) {
+    fn handle_event(&mut self, _window_id: WindowId, event: &Event) {
         // Handle the given event, possibly creating widget events.
         let widget_events = self.widget_manager.handle_event(event, None).unwrap();
 
@@ -88,9 +93,11 @@ impl PietApplication for App {
 
                     // Update the counter text.
                     self.widget_manager
-                        .handle_command(Command::SetValue(
+                        .handle_command(Command::ToWidget(
                             self.counter_text,
-                            Box::new(format!("{}", self.counter)),
+                            vec![WidgetCommand::SetValue(WidgetValue::Number(
+                                self.counter.into(),
+                            ))],
                         ))
                         .unwrap();
                 }
@@ -98,11 +105,11 @@ impl PietApplication for App {
         }
     }
 
-    fn paint(&mut self, piet: &mut Piet, region: &Region) {
+    fn paint(&mut self, _window_id: WindowId, piet: &mut Piet, region: &Region) {
         self.widget_manager.paint(piet, region).unwrap();
     }
 
-    fn resize(&mut self, size: kurbo::Size) {
+    fn resize(&mut self, _window_id: WindowId, size: kurbo::Size) {
         self.widget_manager.resize(size);
     }
     fn set_clipboard(&mut self, _clipboard: Clipboard) {}
@@ -6,6 +6,7 @@ use guiver::widget_manager::WidgetManager;
 use guiver::Size;
 use guiver_piet::{
     run, Clipboard, Command, Event, Piet, PietApplication, PietWidgetManager, Region, WidgetType,
+    WindowId,
 };
 
 pub(crate) struct App {
@@ -87,17 +88,17 @@ impl App {
 }
 
 impl PietApplication for App {
-    fn handle_event(&mut self, event: &Event) {
+    fn handle_event(&mut self, _window_id: WindowId, event: &Event) {
         let _widget_events = self.widget_manager.handle_event(event, None).unwrap();
 
         // TODO
     }
 
-    fn paint(&mut self, piet: &mut Piet, region: &Region) {
+    fn paint(&mut self, _window_id: WindowId, piet: &mut Piet, region: &Region) {
         self.widget_manager.paint(piet, region).unwrap();
     }
 
-    fn resize(&mut self, size: kurbo::Size) {
+    fn resize(&mut self, _window_id: WindowId, size: kurbo::Size) {
         self.widget_manager.resize(size);
     }
 
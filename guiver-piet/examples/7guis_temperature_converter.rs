@@ -3,16 +3,20 @@ This implements the "Counter" task from [7GUIs](https://eugenkiss.github.io/7gui
  */
 use druid_shell::kurbo;
 use guiver::widget_manager::WidgetManager;
-use guiver::{HorizontalAlignment, WidgetEventType, WidgetId};
+use guiver::{
+    HorizontalAlignment, WidgetCommand, WidgetEventData, WidgetEventType, WidgetId, WidgetValue,
+};
 use guiver_piet::{
     run, Clipboard, Command, Event, Piet, PietApplication, PietWidgetManager, Region, WidgetType,
+    WindowId,
 };
+use std::rc::Rc;
 
 ///
 #[derive(Clone)]
 enum CustomEvent {
-    ConvertFromCtoF,
-    ConvertFromFtoC,
+    ConvertFromCtoF(String),
+    ConvertFromFtoC(String),
 }
 
 ///
@@ -35,6 +39,13 @@ impl App {
         let input_fahrenheit = widget_manager.widget_id_provider().next_widget_id();
         let text2 = widget_manager.widget_id_provider().next_widget_id();
 
+        let input_celsius_changed = widget_manager
+            .widget_id_provider()
+            .next_event_observation_handle();
+        let input_fahrenheit_changed = widget_manager
+            .widget_id_provider()
+            .next_event_observation_handle();
+
         widget_manager
             .handle_commands(vec![
                 // Create the widgets.
@@ -88,19 +99,35 @@ impl App {
                 // Configure the widgets.
                 // =================================================================================
                 Command::SetHasFocus(input_celsius, true),
-                Command::SetHorizontalAlignment(input_celsius, HorizontalAlignment::Right),
-                Command::SetHorizontalAlignment(input_fahrenheit, HorizontalAlignment::Left),
+                Command::ToWidget(
+                    input_celsius,
+                    vec![WidgetCommand::SetHorizontalAlignment(
+                        HorizontalAlignment::Right,
+                    )],
+                ),
+                Command::ToWidget(
+                    input_fahrenheit,
+                    vec![WidgetCommand::SetHorizontalAlignment(
+                        HorizontalAlignment::Left,
+                    )],
+                ),
                 // Add event observations.
                 // =================================================================================
-                Command::AddEventObservation(
+                Command::AddMappedEventObservation(
                     input_celsius,
+                    input_celsius_changed,
                     WidgetEventType::ValueChanged,
-                    CustomEvent::ConvertFromCtoF,
+                    Rc::new(|widget_event_data| {
+                        CustomEvent::ConvertFromCtoF(text(widget_event_data))
+                    }),
                 ),
-                Command::AddEventObservation(
+                Command::AddMappedEventObservation(
                     input_fahrenheit,
+                    input_fahrenheit_changed,
                     WidgetEventType::ValueChanged,
-                    CustomEvent::ConvertFromFtoC,
+                    Rc::new(|widget_event_data| {
+                        CustomEvent::ConvertFromFtoC(text(widget_event_data))
+                    }),
                 ),
             ])
             .unwrap();
@@ -114,6 +141,15 @@ impl App {
     }
 }
 
+/// Returns the text of the given widget event's value.
+fn text(widget_event_data: &WidgetEventData) -> String {
+    widget_event_data
+        .value
+        .as_ref()
+        .and_then(|value| value.to_text())
+        .unwrap_or_default()
+}
+
 fn celsius_from_fahrenheit(fahrenheit: f32) -> f32 {
     (fahrenheit - 32.0) * (5.0 / 9.0)
 }
@@ -123,7 +159,7 @@ fn fahrenheit_from_celsius(celsius: f32) -> f32 {
 }
 
 impl PietApplication for App {
-    fn handle_event(&mut self, event: &Event) {
+    fn handle_event(&mut self, _window_id: WindowId, event: &Event) {
         // Handle the given event, possibly creating widget events.
         let widget_events = self
             .widget_manager
@@ -133,75 +169,61 @@ impl PietApplication for App {
         // Iterate over the generated widget events.
         for widget_event in widget_events {
             match widget_event {
-                CustomEvent::ConvertFromCtoF => {
-                    // Try to get the widget's value.
-                    let value = self
-                        .widget_manager
-                        .value(self.text_input_celsius)
-                        .unwrap()
-                        .unwrap();
-
-                    // The given value is a string.
-                    if let Some(string) = value.downcast_ref::<String>() {
-                        // The string is empty.
-                        if string.trim().is_empty() {
-                            self.widget_manager
-                                .handle_command(Command::SetValue(
-                                    self.text_input_fahrenheit,
-                                    Box::new("".to_string()),
-                                ))
-                                .unwrap();
-                        }
-                        // The string could be parsed as a float
-                        else if let Ok(celsius) = string.parse::<f32>() {
-                            self.widget_manager
-                                .handle_command(Command::SetValue(
-                                    self.text_input_fahrenheit,
-                                    Box::new(format!("{:.0}", fahrenheit_from_celsius(celsius))),
-                                ))
-                                .unwrap();
-                        }
+                CustomEvent::ConvertFromCtoF(string) => {
+                    // The string is empty.
+                    if string.trim().is_empty() {
+                        self.widget_manager
+                            .handle_command(Command::ToWidget(
+                                self.text_input_fahrenheit,
+                                vec![WidgetCommand::SetValue(WidgetValue::Text("".to_string()))],
+                            ))
+                            .unwrap();
+                    }
+                    // The string could be parsed as a float
+                    else if let Ok(celsius) = string.parse::<f32>() {
+                        self.widget_manager
+                            .handle_command(Command::ToWidget(
+                                self.text_input_fahrenheit,
+                                vec![WidgetCommand::SetValue(WidgetValue::Text(format!(
+                                    "{:.0}",
+                                    fahrenheit_from_celsius(celsius)
+                                )))],
+                            ))
+                            .unwrap();
                     }
                 }
-                CustomEvent::ConvertFromFtoC => {
-                    // Try to get the widget's value.
-                    let value = self
-                        .widget_manager
-                        .value(self.text_input_fahrenheit)
-                        .unwrap()
-                        .unwrap();
-
-                    // The given value is a string.
-                    if let Some(string) = value.downcast_ref::<String>() {
-                        // The string is empty.
-                        if string.trim().is_empty() {
-                            self.widget_manager
-                                .handle_command(Command::SetValue(
-                                    self.text_input_celsius,
-                                    Box::new("".to_string()),
-                                ))
-                                .unwrap();
-                        }
-                        // The string could be parsed as a float
-                        else if let Ok(fahrenheit) = string.parse::<f32>() {
-                            self.widget_manager
-                                .handle_command(Command::SetValue(
-                                    self.text_input_celsius,
-                                    Box::new(format!("{:.0}", celsius_from_fahrenheit(fahrenheit))),
-                                ))
-                                .unwrap();
-                        }
+                CustomEvent::ConvertFromFtoC(string) => {
+                    // The string is empty.
+                    if string.trim().is_empty() {
+                        self.widget_manager
+                            .handle_command(Command::ToWidget(
+                                self.text_input_celsius,
+                                vec![WidgetCommand::SetValue(WidgetValue::Text("".to_string()))],
+                            ))
+                            .unwrap();
+                    }
+                    // The string could be parsed as a float
+                    else if let Ok(fahrenheit) = string.parse::<f32>() {
+                        self.widget_manager
+                            .handle_command(Command::ToWidget(
+                                self.text_input_celsius,
+                                vec![WidgetCommand::SetValue(WidgetValue::Text(format!(
+                                    "{:.0}",
+                                    celsius_from_fahrenheit(fahrenheit)
+                                )))],
+                            ))
+                            .unwrap();
                     }
                 }
             }
         }
     }
 
-    fn paint(&mut self, piet: &mut Piet, region: &Region) {
+    fn paint(&mut self, _window_id: WindowId, piet: &mut Piet, region: &Region) {
         self.widget_manager.paint(piet, region).unwrap();
     }
 
-    fn resize(&mut self, size: kurbo::Size) {
+    fn resize(&mut self, _window_id: WindowId, size: kurbo::Size) {
         self.widget_manager.resize(size);
     }
 
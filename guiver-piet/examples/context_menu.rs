@@ -0,0 +1,124 @@
+use druid_shell::kurbo;
+use guiver::widget_manager::WidgetManager;
+use guiver::{MenuItem, WidgetCommand, WidgetId, WidgetValue};
+use guiver_piet::{
+    run, Clipboard, Command, Event, Piet, PietApplication, PietWidgetManager, Region, WidgetType,
+    WindowId,
+};
+
+///
+#[derive(Clone)]
+enum CustomEvent {
+    MakeLowercase,
+    MakeUppercase,
+    Reset,
+}
+
+pub(crate) struct App {
+    text: WidgetId,
+    widget_manager: PietWidgetManager<CustomEvent>,
+}
+
+impl App {
+    pub(crate) fn new() -> Self {
+        let mut widget_manager = PietWidgetManager::new();
+
+        let layout_center = widget_manager.widget_id_provider().next_widget_id();
+        let text = widget_manager.widget_id_provider().next_widget_id();
+
+        widget_manager
+            .handle_commands(vec![
+                // Create the widgets.
+                // =================================================================================
+                Command::CreateWidget(layout_center, WidgetType::LayoutCenter),
+                Command::CreateWidget(text, WidgetType::Text("Right-click me".to_string())),
+                // Compose the widgets.
+                // =================================================================================
+                Command::SetMainWidget(layout_center),
+                Command::AddChild {
+                    parent_widget_id: layout_center,
+                    widget_placement: None,
+                    child_widget_id: text,
+                },
+                // Add the context menu.
+                // =================================================================================
+                Command::SetContextMenu(
+                    text,
+                    vec![
+                        MenuItem::Submenu {
+                            is_enabled: true,
+                            label: "Case".to_string(),
+                            menu_items: vec![
+                                MenuItem::Action {
+                                    app_event: CustomEvent::MakeLowercase,
+                                    is_checked: false,
+                                    is_enabled: true,
+                                    label: "Lowercase".to_string(),
+                                    shortcut: None,
+                                },
+                                MenuItem::Action {
+                                    app_event: CustomEvent::MakeUppercase,
+                                    is_checked: false,
+                                    is_enabled: true,
+                                    label: "Uppercase".to_string(),
+                                    shortcut: None,
+                                },
+                            ],
+                        },
+                        MenuItem::Separator,
+                        MenuItem::Action {
+                            app_event: CustomEvent::Reset,
+                            is_checked: false,
+                            is_enabled: true,
+                            label: "Reset".to_string(),
+                            shortcut: None,
+                        },
+                    ],
+                ),
+            ])
+            .unwrap();
+
+        App {
+            text,
+            widget_manager,
+        }
+    }
+}
+
+impl PietApplication for App {
+    fn handle_event(&mut self, _window_id: WindowId, event: &Event) {
+        // Handle the given event, possibly creating widget events.
+        let widget_events = self.widget_manager.handle_event(event, None).unwrap();
+
+        // Iterate over the generated widget events.
+        for widget_event in widget_events {
+            let text = match widget_event {
+                CustomEvent::MakeLowercase => "right-click me",
+                CustomEvent::MakeUppercase => "RIGHT-CLICK ME",
+                CustomEvent::Reset => "Right-click me",
+            };
+
+            // Update the text.
+            self.widget_manager
+                .handle_command(Command::ToWidget(
+                    self.text,
+                    vec![WidgetCommand::SetValue(WidgetValue::Text(text.to_string()))],
+                ))
+                .unwrap();
+        }
+    }
+
+    fn paint(&mut self, _window_id: WindowId, piet: &mut Piet, region: &Region) {
+        self.widget_manager.paint(piet, region).unwrap();
+    }
+
+    fn resize(&mut self, _window_id: WindowId, size: kurbo::Size) {
+        self.widget_manager.resize(size);
+    }
+
+    fn set_clipboard(&mut self, _clipboard: Clipboard) {}
+}
+
+pub fn main() {
+    run(Box::new(App::new()), "context menu", (400.0, 200.0).into());
+}
@@ -3,6 +3,7 @@ use guiver::widget_manager::WidgetManager;
 use guiver::Size;
 use guiver_piet::{
     run, Clipboard, Command, Event, Piet, PietApplication, PietWidgetManager, Region, WidgetType,
+    WindowId,
 };
 
 pub(crate) struct App {
@@ -43,13 +44,13 @@ impl App {
 }
 
 impl PietApplication for App {
-    fn handle_event(&mut self, _system_event_event: &Event) {}
+    fn handle_event(&mut self, _window_id: WindowId, _system_event_event: &Event) {}
 
-    fn paint(&mut self, piet: &mut Piet, region: &Region) {
+    fn paint(&mut self, _window_id: WindowId, piet: &mut Piet, region: &Region) {
         self.widget_manager.paint(piet, region).unwrap();
     }
 
-    fn resize(&mut self, size: kurbo::Size) {
+    fn resize(&mut self, _window_id: WindowId, size: kurbo::Size) {
         self.widget_manager.resize(size);
     }
 
@@ -0,0 +1,130 @@
+use druid_shell::kurbo;
+use guiver::widget_manager::WidgetManager;
+use guiver::{MenuItem, Shortcut, WidgetCommand, WidgetId, WidgetValue};
+use guiver_piet::{
+    run, Clipboard, Command, Event, Piet, PietApplication, PietWidgetManager, Region, WidgetType,
+    WindowId, WindowRequest,
+};
+
+///
+#[derive(Clone)]
+enum CustomEvent {
+    Reset,
+    ToggleUppercase,
+}
+
+pub(crate) struct App {
+    is_uppercase: bool,
+    text: WidgetId,
+    widget_manager: PietWidgetManager<CustomEvent>,
+}
+
+impl App {
+    pub(crate) fn new() -> Self {
+        let mut widget_manager = PietWidgetManager::new();
+
+        let layout_center = widget_manager.widget_id_provider().next_widget_id();
+        let text = widget_manager.widget_id_provider().next_widget_id();
+
+        widget_manager
+            .handle_commands(vec![
+                // Create the widgets.
+                // =================================================================================
+                Command::CreateWidget(layout_center, WidgetType::LayoutCenter),
+                Command::CreateWidget(text, WidgetType::Text("Use the menu bar".to_string())),
+                // Compose the widgets.
+                // =================================================================================
+                Command::SetMainWidget(layout_center),
+                Command::AddChild {
+                    parent_widget_id: layout_center,
+                    widget_placement: None,
+                    child_widget_id: text,
+                },
+                // Set the menu bar.
+                // =================================================================================
+                Command::SetMenuBar(App::menu_bar(false)),
+            ])
+            .unwrap();
+
+        App {
+            is_uppercase: false,
+            text,
+            widget_manager,
+        }
+    }
+
+    /// Returns the menu bar, reflecting the given state.
+    fn menu_bar(is_uppercase: bool) -> Vec<MenuItem<CustomEvent>> {
+        vec![MenuItem::Submenu {
+            is_enabled: true,
+            label: "Text".to_string(),
+            menu_items: vec![
+                MenuItem::Action {
+                    app_event: CustomEvent::ToggleUppercase,
+                    is_checked: is_uppercase,
+                    is_enabled: true,
+                    label: "Uppercase".to_string(),
+                    shortcut: Some(Shortcut::primary("u")),
+                },
+                MenuItem::Separator,
+                MenuItem::Action {
+                    app_event: CustomEvent::Reset,
+                    is_checked: false,
+                    is_enabled: is_uppercase,
+                    label: "Reset".to_string(),
+                    shortcut: None,
+                },
+            ],
+        }]
+    }
+}
+
+impl PietApplication for App {
+    fn handle_event(&mut self, _window_id: WindowId, event: &Event) {
+        // Handle the given event, possibly creating widget events.
+        let widget_events = self.widget_manager.handle_event(event, None).unwrap();
+
+        // Iterate over the generated widget events.
+        for widget_event in widget_events {
+            match widget_event {
+                CustomEvent::Reset => self.is_uppercase = false,
+                CustomEvent::ToggleUppercase => self.is_uppercase = !self.is_uppercase,
+            }
+
+            let text = if self.is_uppercase {
+                "USE THE MENU BAR"
+            } else {
+                "Use the menu bar"
+            };
+
+            // Update the text and the menu bar.
+            self.widget_manager
+                .handle_commands(vec![
+                    Command::ToWidget(
+                        self.text,
+                        vec![WidgetCommand::SetValue(WidgetValue::Text(text.to_string()))],
+                    ),
+                    Command::SetMenuBar(App::menu_bar(self.is_uppercase)),
+                ])
+                .unwrap();
+        }
+    }
+
+    fn paint(&mut self, _window_id: WindowId, piet: &mut Piet, region: &Region) {
+        self.widget_manager.paint(piet, region).unwrap();
+    }
+
+    fn resize(&mut self, _window_id: WindowId, size: kurbo::Size) {
+        self.widget_manager.resize(size);
+    }
+
+    fn set_clipboard(&mut self, _clipboard: Clipboard) {}
+
+    fn window_requests(&mut self, _window_id: WindowId) -> Vec<WindowRequest> {
+        self.widget_manager.window_requests()
+    }
+}
+
+pub fn main() {
+    run(Box::new(App::new()), "menu bar", (400.0, 200.0).into());
+}
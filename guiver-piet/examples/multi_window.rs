@@ -0,0 +1,185 @@
+use druid_shell::kurbo;
+use guiver::widget_manager::WidgetManager;
+use guiver::WidgetEventType;
+use guiver_piet::{
+    run, Clipboard, CloseResponse, Command, Event, Piet, PietApplication, PietWidgetManager,
+    Region, WidgetType, WindowId, WindowOptions, WindowRequest, MAIN_WINDOW_ID,
+};
+use std::collections::HashMap;
+
+///
+#[derive(Clone)]
+enum CustomEvent {
+    CloseWindow,
+    OpenWindow,
+}
+
+pub(crate) struct App {
+    next_window_id: WindowId,
+    /// Each window has its own widget manager.
+    widget_managers: HashMap<WindowId, PietWidgetManager<CustomEvent>>,
+    window_requests: HashMap<WindowId, Vec<WindowRequest>>,
+}
+
+impl App {
+    pub(crate) fn new() -> Self {
+        let mut app = App {
+            next_window_id: MAIN_WINDOW_ID + 1,
+            widget_managers: HashMap::new(),
+            window_requests: HashMap::new(),
+        };
+
+        app.widget_managers.insert(
+            MAIN_WINDOW_ID,
+            App::widget_manager("Open window", CustomEvent::OpenWindow),
+        );
+
+        app
+    }
+
+    /// Returns a widget manager with a button with the given text and application event.
+    fn widget_manager(button_text: &str, app_event: CustomEvent) -> PietWidgetManager<CustomEvent> {
+        let mut widget_manager = PietWidgetManager::new();
+
+        let layout_center = widget_manager.widget_id_provider().next_widget_id();
+        let button = widget_manager.widget_id_provider().next_widget_id();
+
+        let button_clicked = widget_manager
+            .widget_id_provider()
+            .next_event_observation_handle();
+
+        widget_manager
+            .handle_commands(vec![
+                // Create the widgets.
+                // =================================================================================
+                Command::CreateWidget(layout_center, WidgetType::LayoutCenter),
+                Command::CreateWidget(button, WidgetType::TextButton(button_text.to_string())),
+                // Compose the widgets.
+                // =================================================================================
+                Command::SetMainWidget(layout_center),
+                Command::AddChild {
+                    parent_widget_id: layout_center,
+                    widget_placement: None,
+                    child_widget_id: button,
+                },
+                // Add event observations.
+                // =================================================================================
+                Command::AddEventObservation(
+                    button,
+                    button_clicked,
+                    WidgetEventType::Clicked,
+                    app_event,
+                ),
+            ])
+            .unwrap();
+
+        widget_manager
+    }
+}
+
+impl PietApplication for App {
+    fn close_requested(&mut self, window_id: WindowId) -> CloseResponse {
+        // Closing the main window closes the other windows, too.
+        if window_id == MAIN_WINDOW_ID {
+            let other_window_ids = self
+                .widget_managers
+                .keys()
+                .copied()
+                .filter(|other_window_id| *other_window_id != MAIN_WINDOW_ID)
+                .collect::<Vec<_>>();
+
+            self.window_requests
+                .entry(window_id)
+                .or_default()
+                .extend(other_window_ids.into_iter().map(WindowRequest::CloseWindow));
+        }
+
+        CloseResponse::Allow
+    }
+
+    fn handle_event(&mut self, window_id: WindowId, event: &Event) {
+        // The window was closed.
+        if let Event::WindowClosed = event {
+            self.widget_managers.remove(&window_id);
+            return;
+        }
+
+        // The window has a widget manager.
+        let widget_manager = if let Some(widget_manager) = self.widget_managers.get_mut(&window_id)
+        {
+            widget_manager
+        }
+        // The window has no widget manager.
+        else {
+            return;
+        };
+
+        // Handle the given event, possibly creating widget events.
+        let widget_events = widget_manager.handle_event(event, None).unwrap();
+
+        // Iterate over the generated widget events.
+        for widget_event in widget_events {
+            match widget_event {
+                CustomEvent::CloseWindow => {
+                    self.window_requests
+                        .entry(window_id)
+                        .or_default()
+                        .push(WindowRequest::CloseWindow(window_id));
+                }
+                CustomEvent::OpenWindow => {
+                    let new_window_id = self.next_window_id;
+                    self.next_window_id += 1;
+
+                    let mut widget_manager =
+                        App::widget_manager("Close window", CustomEvent::CloseWindow);
+                    widget_manager.set_window_id(new_window_id);
+
+                    self.widget_managers.insert(new_window_id, widget_manager);
+                    self.window_requests.entry(window_id).or_default().push(
+                        WindowRequest::OpenWindow {
+                            window_id: new_window_id,
+                            window_options: WindowOptions {
+                                is_always_on_top: true,
+                                is_resizable: false,
+                                size: (300.0, 100.0).into(),
+                                title: format!("window {}", new_window_id),
+                                ..WindowOptions::default()
+                            },
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    fn paint(&mut self, window_id: WindowId, piet: &mut Piet, region: &Region) {
+        // The window has a widget manager.
+        if let Some(widget_manager) = self.widget_managers.get(&window_id) {
+            widget_manager.paint(piet, region).unwrap();
+        }
+    }
+
+    fn resize(&mut self, window_id: WindowId, size: kurbo::Size) {
+        // The window has a widget manager.
+        if let Some(widget_manager) = self.widget_managers.get_mut(&window_id) {
+            widget_manager.resize(size);
+        }
+    }
+
+    fn set_clipboard(&mut self, _clipboard: Clipboard) {}
+
+    fn window_requests(&mut self, window_id: WindowId) -> Vec<WindowRequest> {
+        let mut window_requests = self.window_requests.remove(&window_id).unwrap_or_default();
+
+        // The window has a widget manager.
+        if let Some(widget_manager) = self.widget_managers.get_mut(&window_id) {
+            window_requests.extend(widget_manager.window_requests());
+        }
+
+        window_requests
+    }
+}
+
+pub fn main() {
+    run(Box::new(App::new()), "multi window", (400.0, 200.0).into());
+}
@@ -0,0 +1,88 @@
+use accesskit::{Node, NodeId, Rect, Role, Tree, TreeUpdate};
+use guiver::{AccessibilityNode, AccessibilityRole};
+
+/// Converts the given accessibility tree, e.g. from `PietWidgetManager::accessibility_tree()`, to a
+/// complete AccessKit tree update. An AccessKit platform adapter passes it on to screen readers.
+/// The AccessKit node IDs are the widget IDs.
+pub fn accesskit_tree_update(accessibility_tree: &AccessibilityNode) -> TreeUpdate {
+    let mut nodes = vec![];
+    let mut focus = None;
+
+    add_accesskit_nodes(accessibility_tree, &mut nodes, &mut focus);
+
+    let root = NodeId(accessibility_tree.widget_id as u64);
+
+    TreeUpdate {
+        nodes,
+        tree: Some(Tree::new(root)),
+        // Without a focused widget, the root has the focus.
+        focus: focus.unwrap_or(root),
+    }
+}
+
+/// Adds the AccessKit nodes of the given accessibility node and its descendants.
+fn add_accesskit_nodes(
+    accessibility_node: &AccessibilityNode,
+    nodes: &mut Vec<(NodeId, Node)>,
+    focus: &mut Option<NodeId>,
+) {
+    let node_id = NodeId(accessibility_node.widget_id as u64);
+    let mut node = Node::new(accesskit_role(accessibility_node.role));
+
+    node.set_bounds(Rect::new(
+        accessibility_node.bounds.x0,
+        accessibility_node.bounds.y0,
+        accessibility_node.bounds.x1,
+        accessibility_node.bounds.y1,
+    ));
+    node.set_children(
+        accessibility_node
+            .children
+            .iter()
+            .map(|child| NodeId(child.widget_id as u64))
+            .collect::<Vec<_>>(),
+    );
+
+    // The node has a name.
+    if let Some(name) = &accessibility_node.name {
+        node.set_label(name.clone());
+    }
+
+    // The node has a value.
+    if let Some(value) = &accessibility_node.value {
+        node.set_value(value.clone());
+    }
+
+    // The node is disabled.
+    if accessibility_node.is_disabled {
+        node.set_disabled();
+    }
+
+    // The node is focused.
+    if accessibility_node.is_focused {
+        *focus = Some(node_id);
+    }
+
+    // The node is hidden.
+    if accessibility_node.is_hidden {
+        node.set_hidden();
+    }
+
+    nodes.push((node_id, node));
+
+    // Iterate over the child nodes.
+    for child in &accessibility_node.children {
+        add_accesskit_nodes(child, nodes, focus);
+    }
+}
+
+///
+fn accesskit_role(accessibility_role: AccessibilityRole) -> Role {
+    match accessibility_role {
+        AccessibilityRole::Button => Role::Button,
+        AccessibilityRole::Group => Role::Group,
+        AccessibilityRole::Label => Role::Label,
+        AccessibilityRole::Link => Role::Link,
+        AccessibilityRole::TextField => Role::TextInput,
+    }
+}
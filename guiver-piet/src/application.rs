@@ -1,26 +1,229 @@
 use crate::window_event_handler::WindowEventHandler;
-use crate::Event;
+use crate::{Event, WindowOptions, WindowRequest};
 use druid_shell::kurbo::Size;
 use druid_shell::piet::Piet;
-use druid_shell::{Clipboard, Region, WindowBuilder};
+use druid_shell::{Clipboard, Region, WindowBuilder, WindowHandle};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Identifies one of the application's windows. The window created by `run()` has the ID
+/// `MAIN_WINDOW_ID`, the IDs of further windows are chosen with `WindowRequest::OpenWindow`.
+pub type WindowId = usize;
+
+/// The configuration of an application that is run with `run_with_config()`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ApplicationConfig {
+    /// The options of the window that is created at start, with the ID `MAIN_WINDOW_ID`.
+    pub main_window_options: WindowOptions,
+}
+
+/// An application's answer to a request to close one of its windows.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CloseResponse {
+    /// The window is closed.
+    Allow,
+    /// The window stays open, e.g. since it has unsaved changes. To close it after all, e.g. after
+    /// the user confirmed it in a dialog, the application sends `WindowRequest::CloseWindow` with the
+    /// window's ID.
+    Deny,
+}
+
+/// The ID of the window created by `run()`.
+pub const MAIN_WINDOW_ID: WindowId = 0;
 
 ///
 pub trait PietApplication {
-    ///
-    fn handle_event(&mut self, event: &Event);
+    /// Called when the user asks to close the window with the given ID, e.g. with its close button.
+    /// By default, the window is closed.
+    fn close_requested(&mut self, _window_id: WindowId) -> CloseResponse {
+        CloseResponse::Allow
+    }
 
-    ///
-    fn paint(&mut self, piet: &mut Piet, region: &Region);
+    /// Handles the given event that occurred in the window with the given ID.
+    fn handle_event(&mut self, window_id: WindowId, event: &Event);
+
+    /// Called after the last window was closed. Returns whether the application quits, which it does
+    /// by default.
+    fn last_window_closed(&mut self) -> bool {
+        true
+    }
+
+    /// Paints the window with the given ID.
+    fn paint(&mut self, window_id: WindowId, piet: &mut Piet, region: &Region);
 
     ///
-    fn resize(&mut self, size: Size);
+    fn resize(&mut self, window_id: WindowId, size: Size);
 
     ///
     fn set_clipboard(&mut self, clipboard: Clipboard);
+
+    /// Returns the requests to the window with the given ID, e.g. to set its menu bar. This is called
+    /// when the window is created and after each of its events.
+    fn window_requests(&mut self, _window_id: WindowId) -> Vec<WindowRequest> {
+        vec![]
+    }
 }
 
-///
-pub fn run(mut application: Box<dyn PietApplication>, title: impl Into<String>, size: Size) {
+/// A platform window as far as the windows' shared state needs it, so that the state can be tested
+/// without druid-shell.
+pub(crate) trait PlatformWindow: Clone {
+    /// Closes the window. The platform then destroys it.
+    fn close(&self);
+}
+
+impl PlatformWindow for WindowHandle {
+    fn close(&self) {
+        WindowHandle::close(self);
+    }
+}
+
+/// The state that the windows of an application share.
+pub(crate) struct Windows<WINDOW: PlatformWindow = WindowHandle> {
+    pub(crate) application: Rc<RefCell<Box<dyn PietApplication>>>,
+    /// The open windows per window ID.
+    pub(crate) window_per_window_id: HashMap<WindowId, WINDOW>,
+}
+
+impl<WINDOW: PlatformWindow> Windows<WINDOW> {
+    ///
+    pub(crate) fn new(application: Box<dyn PietApplication>) -> Self {
+        Windows {
+            application: Rc::new(RefCell::new(application)),
+            window_per_window_id: HashMap::new(),
+        }
+    }
+}
+
+/// Closes the window with the given ID, which can be any of the application's windows. If no window
+/// with that ID is open, nothing happens.
+pub(crate) fn close_window<WINDOW: PlatformWindow>(
+    windows: &Rc<RefCell<Windows<WINDOW>>>,
+    window_id: WindowId,
+) {
+    // Clone the window, since closing it may destroy it right away, which needs the shared state.
+    let window = windows
+        .borrow()
+        .window_per_window_id
+        .get(&window_id)
+        .cloned();
+
+    // The window is open.
+    if let Some(window) = window {
+        window.close();
+    }
+}
+
+/// Asks the application whether the window with the given ID may be closed and, if so, closes it.
+/// In between, `handle_window_requests` applies the requests that the application made, e.g. to
+/// close other windows along with this one or to ask for a confirmation.
+pub(crate) fn request_close<WINDOW: PlatformWindow>(
+    windows: &Rc<RefCell<Windows<WINDOW>>>,
+    window_id: WindowId,
+    handle_window_requests: impl FnOnce(),
+) -> CloseResponse {
+    let application = windows.borrow().application.clone();
+    let close_response = application.borrow_mut().close_requested(window_id);
+
+    handle_window_requests();
+
+    // The application allows closing the window.
+    if close_response == CloseResponse::Allow {
+        close_window(windows, window_id);
+    }
+
+    close_response
+}
+
+/// Tells the application that the window with the given ID was closed and forgets the window.
+/// Returns whether the application quits, i.e. whether it was the last window and the application
+/// agrees to quit.
+pub(crate) fn window_closed<WINDOW: PlatformWindow>(
+    windows: &Rc<RefCell<Windows<WINDOW>>>,
+    window_id: WindowId,
+) -> bool {
+    let application = windows.borrow().application.clone();
+    application
+        .borrow_mut()
+        .handle_event(window_id, &Event::WindowClosed);
+
+    let is_last_window = {
+        let mut windows = windows.borrow_mut();
+        windows.window_per_window_id.remove(&window_id);
+        windows.window_per_window_id.is_empty()
+    };
+
+    is_last_window && application.borrow_mut().last_window_closed()
+}
+
+/// Creates a window with the given ID and options and shows it.
+pub(crate) fn open_window(
+    windows: &Rc<RefCell<Windows>>,
+    window_id: WindowId,
+    window_options: WindowOptions,
+) {
+    // A window with the given ID is open already.
+    if windows
+        .borrow()
+        .window_per_window_id
+        .contains_key(&window_id)
+    {
+        return;
+    }
+
+    // Create a window builder.
+    let mut window_builder = WindowBuilder::new(druid_shell::Application::global());
+    window_builder.set_handler(Box::new(WindowEventHandler::new(
+        windows.clone(),
+        window_id,
+        window_options.maximum_size,
+    )));
+    window_builder.set_title(window_options.title);
+    window_builder.set_size(window_options.size);
+    window_builder.resizable(window_options.is_resizable);
+    window_builder.set_transparent(window_options.is_transparent);
+    window_builder.set_always_on_top(window_options.is_always_on_top);
+
+    // A minimum size is given.
+    if let Some(minimum_size) = window_options.minimum_size {
+        window_builder.set_min_size(minimum_size);
+    }
+
+    // A position is given.
+    if let Some(position) = window_options.position {
+        window_builder.set_position(position);
+    }
+
+    // Create a window and show it.
+    let window_handle = window_builder.build().unwrap();
+    window_handle.show();
+
+    windows
+        .borrow_mut()
+        .window_per_window_id
+        .insert(window_id, window_handle);
+}
+
+/// Runs the given application with a main window of the given title and size. The main window has
+/// the ID `MAIN_WINDOW_ID`.
+pub fn run(application: Box<dyn PietApplication>, title: impl Into<String>, size: Size) {
+    run_with_config(
+        application,
+        ApplicationConfig {
+            main_window_options: WindowOptions {
+                size,
+                title: title.into(),
+                ..WindowOptions::default()
+            },
+        },
+    );
+}
+
+/// Runs the given application with the given configuration.
+pub fn run_with_config(
+    mut application: Box<dyn PietApplication>,
+    application_config: ApplicationConfig,
+) {
     // Create a druid shell application.
     let druid_shell_application = druid_shell::Application::new().unwrap();
 
@@ -28,17 +231,154 @@ pub fn run(mut application: Box<dyn PietApplication>, title: impl Into<String>,
     application.set_clipboard(druid_shell_application.clipboard());
 
     // Set the initial size.
-    application.resize(size);
+    application.resize(MAIN_WINDOW_ID, application_config.main_window_options.size);
 
-    // Create a window builder.
-    let mut window_builder = WindowBuilder::new(druid_shell_application.clone());
-    window_builder.set_handler(Box::new(WindowEventHandler::new(application)));
-    window_builder.set_title(title);
-    window_builder.set_size(size);
+    let windows = Rc::new(RefCell::new(Windows::new(application)));
 
-    // Create a window and show it.
-    let window_handle = window_builder.build().unwrap();
-    window_handle.show();
+    open_window(
+        &windows,
+        MAIN_WINDOW_ID,
+        application_config.main_window_options,
+    );
 
     druid_shell_application.run(None);
 }
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// A window that records whether it was closed.
+    #[derive(Clone)]
+    struct TestWindow {
+        is_closed: Rc<Cell<bool>>,
+    }
+
+    impl PlatformWindow for TestWindow {
+        fn close(&self) {
+            self.is_closed.set(true);
+        }
+    }
+
+    /// An application whose state the test shares.
+    #[derive(Clone, Default)]
+    struct TestApplication {
+        /// The IDs of the windows that the application got an `Event::WindowClosed` for.
+        closed_window_ids: Rc<RefCell<Vec<WindowId>>>,
+        /// Whether the application denies closing windows.
+        denies_closing: Rc<Cell<bool>>,
+        number_of_last_window_closed_calls: Rc<Cell<usize>>,
+    }
+
+    impl PietApplication for TestApplication {
+        fn close_requested(&mut self, _window_id: WindowId) -> CloseResponse {
+            // The application denies closing the window.
+            if self.denies_closing.get() {
+                CloseResponse::Deny
+            }
+            // The application allows closing the window.
+            else {
+                CloseResponse::Allow
+            }
+        }
+
+        fn handle_event(&mut self, window_id: WindowId, event: &Event) {
+            // The window was closed.
+            if let Event::WindowClosed = event {
+                self.closed_window_ids.borrow_mut().push(window_id);
+            }
+        }
+
+        fn last_window_closed(&mut self) -> bool {
+            self.number_of_last_window_closed_calls
+                .set(self.number_of_last_window_closed_calls.get() + 1);
+            true
+        }
+
+        fn paint(&mut self, _window_id: WindowId, _piet: &mut Piet, _region: &Region) {}
+
+        fn resize(&mut self, _window_id: WindowId, _size: Size) {}
+
+        fn set_clipboard(&mut self, _clipboard: Clipboard) {}
+    }
+
+    /// Returns the windows of the given application with a main window and another window.
+    fn windows(
+        application: &TestApplication,
+    ) -> (Rc<RefCell<Windows<TestWindow>>>, TestWindow, TestWindow) {
+        let windows = Rc::new(RefCell::new(Windows::new(Box::new(application.clone()))));
+        let main_window = TestWindow {
+            is_closed: Rc::new(Cell::new(false)),
+        };
+        let other_window = TestWindow {
+            is_closed: Rc::new(Cell::new(false)),
+        };
+        windows
+            .borrow_mut()
+            .window_per_window_id
+            .insert(MAIN_WINDOW_ID, main_window.clone());
+        windows
+            .borrow_mut()
+            .window_per_window_id
+            .insert(1, other_window.clone());
+
+        (windows, main_window, other_window)
+    }
+
+    #[test]
+    fn test_close_window() {
+        let (windows, main_window, other_window) = windows(&TestApplication::default());
+
+        // Close the other window, e.g. from the main window.
+        close_window(&windows, 1);
+        assert!(other_window.is_closed.get());
+        assert!(!main_window.is_closed.get());
+
+        // Closing a window that is not open does nothing.
+        close_window(&windows, 2);
+        assert!(!main_window.is_closed.get());
+    }
+
+    #[test]
+    fn test_close_requests() {
+        let application = TestApplication::default();
+        let (windows, main_window, other_window) = windows(&application);
+
+        // A denied close request keeps the window open, but the application's requests, e.g. to
+        // ask for a confirmation, are handled.
+        application.denies_closing.set(true);
+        let window_requests_were_handled = Cell::new(false);
+        assert_eq!(
+            request_close(&windows, 1, || window_requests_were_handled.set(true)),
+            CloseResponse::Deny
+        );
+        assert!(window_requests_were_handled.get());
+        assert!(!other_window.is_closed.get());
+        assert!(windows.borrow().window_per_window_id.contains_key(&1));
+
+        // An allowed close request closes the window. Another window is still open, so the
+        // application does not quit.
+        application.denies_closing.set(false);
+        assert_eq!(request_close(&windows, 1, || {}), CloseResponse::Allow);
+        assert!(other_window.is_closed.get());
+        assert!(!window_closed(&windows, 1));
+        assert_eq!(*application.closed_window_ids.borrow(), vec![1]);
+        assert_eq!(application.number_of_last_window_closed_calls.get(), 0);
+
+        // Closing the last window asks the application whether to quit.
+        assert_eq!(
+            request_close(&windows, MAIN_WINDOW_ID, || {}),
+            CloseResponse::Allow
+        );
+        assert!(main_window.is_closed.get());
+        assert!(window_closed(&windows, MAIN_WINDOW_ID));
+        assert_eq!(
+            *application.closed_window_ids.borrow(),
+            vec![1, MAIN_WINDOW_ID]
+        );
+        assert_eq!(application.number_of_last_window_closed_calls.get(), 1);
+    }
+}
@@ -0,0 +1,6 @@
+use druid_shell::piet::Color;
+
+/// Converts the given backend-neutral color to a Piet color.
+pub(crate) fn piet_color(color: guiver::Color) -> Color {
+    Color::rgba8(color.red, color.green, color.blue, color.alpha)
+}
@@ -1,13 +1,76 @@
-use druid_shell::{KeyEvent, MouseEvent};
+use crate::{FileDialogId, TextFieldState};
+use druid_shell::{KeyEvent, Modifiers, MouseEvent, Scale};
+use std::path::PathBuf;
 
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub enum Event {
     ClipboardPaste(String),
+    /// A double click, recognized by the widget manager. The mouse event is the second click's.
+    DoubleClicked(MouseEvent),
+    /// The end of a drag, recognized by the widget manager.
+    DragEnded(MouseEvent),
+    /// The start of a drag, recognized by the widget manager.
+    DragStarted(MouseEvent),
+    /// A pointer movement during a drag, recognized by the widget manager.
+    Dragged(MouseEvent),
+    /// The file dialog with the given ID was closed. If it was cancelled, there are no paths.
+    FileChosen(FileDialogId, Vec<PathBuf>),
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
+    /// A long press, recognized by the widget manager. The mouse event is the press'.
+    LongPressed(MouseEvent),
+    MenuCommand(u32),
     MouseDown(MouseEvent),
     MouseMove(MouseEvent),
     MouseUp(MouseEvent),
-    RequestClose,
+    /// A wheel or touchpad scroll. The mouse event's `wheel_delta` gives the scroll distance.
+    MouseWheel(MouseEvent),
+    /// The window's scale changed, e.g. since it was moved to a screen with a different resolution.
+    ScaleChanged(Scale),
+    SelectAll,
+    /// The platform's text input, e.g. an IME, edited the focused text widget's state. While
+    /// composing, the state has a composition range with the preedit text.
+    TextFieldEdited(TextFieldState),
+    /// A timer requested via `WindowRequest::RequestTimer` elapsed.
+    Timer,
+    Undo,
+    /// The window was closed. It gets no further events.
+    WindowClosed,
+    /// The window gained the keyboard focus.
+    WindowFocused,
+    /// The window lost the keyboard focus.
+    WindowUnfocused,
+    /// A zoom gesture, e.g. pinching on a touchpad, by the given delta.
+    Zoom(f64),
+}
+
+/// The phase of an event's dispatch along the event path, which leads from the main widget to the
+/// event's target widget.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventPhase {
+    /// The event travels from the main widget down to the target widget's parent.
+    Capture,
+    /// The event arrived at the target widget.
+    Target,
+    /// The event travels from the target widget's parent back up to the main widget.
+    Bubble,
+}
+
+/// Whether an event continues along the event path.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventPropagation {
+    Continue,
+    /// The remaining widgets of the event path do not get the event.
+    Stop,
+}
+
+/// Converts the given modifier keys to backend-neutral ones.
+pub(crate) fn modifiers(mods: Modifiers) -> guiver::Modifiers {
+    guiver::Modifiers {
+        has_alt: mods.alt(),
+        has_ctrl: mods.ctrl(),
+        has_meta: mods.meta(),
+        has_shift: mods.shift(),
+    }
 }
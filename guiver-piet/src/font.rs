@@ -1,3 +1,4 @@
+use crate::color::piet_color;
 use druid_shell::piet::{
     Color, FontFamily, FontWeight, PietText, PietTextLayout, Text, TextAttribute,
     TextLayoutBuilder, TextStorage,
@@ -41,3 +42,24 @@ impl Font {
             .unwrap()
     }
 }
+
+impl From<guiver::Font> for Font {
+    fn from(font: guiver::Font) -> Self {
+        let font_family = match font.font_family {
+            guiver::FontFamily::Monospace => FontFamily::MONOSPACE,
+            guiver::FontFamily::Named(name) => FontFamily::new_unchecked(name),
+            guiver::FontFamily::SansSerif => FontFamily::SANS_SERIF,
+            guiver::FontFamily::Serif => FontFamily::SERIF,
+            guiver::FontFamily::SystemUi => FontFamily::SYSTEM_UI,
+        };
+
+        Font {
+            font_color: piet_color(font.font_color),
+            font_family,
+            font_size: font.font_size,
+            font_weight: FontWeight::new(font.font_weight),
+            has_strikethrough: font.has_strikethrough,
+            has_underline: font.has_underline,
+        }
+    }
+}
@@ -1,22 +1,38 @@
+#[cfg(feature = "accesskit")]
+mod accessibility;
 mod application;
+mod color;
 mod event;
 pub mod font;
+mod localization;
 mod shared_state;
 pub mod stroke;
 mod style;
 mod text;
+mod text_field;
 pub mod widget;
 mod widget_manager;
 mod window_event_handler;
+mod window_options;
+mod window_request;
 
-pub use application::{run, PietApplication};
+#[cfg(feature = "accesskit")]
+pub use accessibility::accesskit_tree_update;
+pub use application::{
+    run, run_with_config, ApplicationConfig, CloseResponse, PietApplication, WindowId,
+    MAIN_WINDOW_ID,
+};
 pub use druid_shell::piet::{
     Color, Error, FontFamily, FontWeight, LinearGradient, PaintBrush, Piet, PietTextLayout,
     RenderContext, StrokeDash, StrokeStyle, TextLayout, UnitPoint,
 };
 pub use druid_shell::{Clipboard, Region};
-pub use event::Event;
+pub use event::{Event, EventPhase, EventPropagation};
+pub use localization::{Date, LocaleFormat};
 pub use shared_state::{piet_text, PietSharedState};
 pub use style::Style;
+pub use text_field::TextFieldState;
 use widget::PietWidget;
 pub use widget_manager::{Command, PietWidgetManager, WidgetBox, WidgetType};
+pub use window_options::WindowOptions;
+pub use window_request::{FileDialogId, WindowRequest};
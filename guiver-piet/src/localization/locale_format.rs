@@ -0,0 +1,296 @@
+use unic_langid::LanguageIdentifier;
+
+/// A calendar date.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Date {
+    /// The day of the month, starting with 1.
+    pub day: u8,
+    /// The month, starting with 1 for January.
+    pub month: u8,
+    pub year: i32,
+}
+
+impl Date {
+    /// Returns `true` if the month has the day, e.g. not for February 30.
+    pub fn is_valid(&self) -> bool {
+        let is_leap_year = (self.year % 4 == 0 && self.year % 100 != 0) || self.year % 400 == 0;
+        let number_of_days = match self.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if is_leap_year => 29,
+            2 => 28,
+            // There is no such month.
+            _ => return false,
+        };
+
+        (1..=number_of_days).contains(&self.day)
+    }
+}
+
+/// The order of a date's parts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+/// Formats and parses numbers and dates the way a locale writes them, e.g. "1.234,5" and
+/// "18.10.2026" in German. Event mappers that validate a `TextInput`'s text use it to accept what
+/// users type in their language. It covers the separators and the date order of common locales,
+/// but always uses the digits 0 to 9.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocaleFormat {
+    date_order: DateOrder,
+    date_separator: char,
+    decimal_separator: char,
+    /// The separator between groups of three integer digits.
+    grouping_separator: char,
+}
+
+impl LocaleFormat {
+    /// Creates the format of the given locale, e.g. "de-AT". Unknown or invalid locales result in
+    /// the format of "en-US".
+    pub fn new(locale: &str) -> Self {
+        let language_identifier: LanguageIdentifier = locale.parse().unwrap_or_default();
+        let language = language_identifier.language.as_str();
+        let region = language_identifier
+            .region
+            .as_ref()
+            .map(|region| region.as_str());
+
+        let (decimal_separator, grouping_separator) = match language {
+            "da" | "de" | "el" | "es" | "id" | "it" | "nl" | "pt" | "tr" => (',', '.'),
+            "cs" | "fi" | "fr" | "nb" | "no" | "pl" | "ru" | "sk" | "sv" | "uk" => (',', '\u{a0}'),
+            _ => ('.', ','),
+        };
+
+        let (date_order, date_separator) = match (language, region) {
+            ("cs" | "da" | "de" | "fi" | "nb" | "no" | "pl" | "ru" | "sk" | "tr" | "uk", _) => {
+                (DateOrder::DayMonthYear, '.')
+            }
+            ("nl", _) => (DateOrder::DayMonthYear, '-'),
+            ("ja" | "zh", _) => (DateOrder::YearMonthDay, '/'),
+            ("lt" | "sv", _) => (DateOrder::YearMonthDay, '-'),
+            ("en", None | Some("US")) | ("und", _) => (DateOrder::MonthDayYear, '/'),
+            _ => (DateOrder::DayMonthYear, '/'),
+        };
+
+        LocaleFormat {
+            date_order,
+            date_separator,
+            decimal_separator,
+            grouping_separator,
+        }
+    }
+
+    /// Formats the date with two-digit days and months, e.g. "18.10.2026".
+    pub fn format_date(&self, date: &Date) -> String {
+        let separator = self.date_separator;
+
+        match self.date_order {
+            DateOrder::DayMonthYear => format!(
+                "{:02}{separator}{:02}{separator}{}",
+                date.day, date.month, date.year
+            ),
+            DateOrder::MonthDayYear => format!(
+                "{:02}{separator}{:02}{separator}{}",
+                date.month, date.day, date.year
+            ),
+            DateOrder::YearMonthDay => format!(
+                "{}{separator}{:02}{separator}{:02}",
+                date.year, date.month, date.day
+            ),
+        }
+    }
+
+    /// Formats the number with the given number of fraction digits and grouped integer digits,
+    /// e.g. "1,234.50".
+    pub fn format_number(&self, number: f64, fraction_digits: usize) -> String {
+        let formatted = format!("{:.*}", fraction_digits, number.abs());
+        let (integer_digits, fraction) = formatted
+            .split_once('.')
+            .unwrap_or((formatted.as_str(), ""));
+
+        let mut text = String::new();
+
+        // The number is negative and not rounded to zero.
+        if number < 0.0
+            && formatted
+                .chars()
+                .any(|character| ('1'..='9').contains(&character))
+        {
+            text.push('-');
+        }
+
+        // Iterate over the integer digits.
+        for (index, digit) in integer_digits.chars().enumerate() {
+            // A new group of three digits starts.
+            if index > 0 && (integer_digits.len() - index) % 3 == 0 {
+                text.push(self.grouping_separator);
+            }
+
+            text.push(digit);
+        }
+
+        // There are fraction digits.
+        if !fraction.is_empty() {
+            text.push(self.decimal_separator);
+            text.push_str(fraction);
+        }
+
+        text
+    }
+
+    /// Parses a date in the locale's order, e.g. "18.10.2026" or "1.2.2026" with single-digit days
+    /// and months. Returns `None` for invalid dates, e.g. February 30.
+    pub fn parse_date(&self, text: &str) -> Option<Date> {
+        let parts = text
+            .trim()
+            .split(self.date_separator)
+            .map(|part| part.trim())
+            .collect::<Vec<_>>();
+
+        // A date has exactly three parts.
+        if parts.len() != 3 || parts.iter().any(|part| !is_digits(part)) {
+            return None;
+        }
+
+        let (day, month, year) = match self.date_order {
+            DateOrder::DayMonthYear => (parts[0], parts[1], parts[2]),
+            DateOrder::MonthDayYear => (parts[1], parts[0], parts[2]),
+            DateOrder::YearMonthDay => (parts[2], parts[1], parts[0]),
+        };
+
+        let date = Date {
+            day: day.parse().ok()?,
+            month: month.parse().ok()?,
+            year: year.parse().ok()?,
+        };
+
+        // The date exists.
+        if date.is_valid() {
+            Some(date)
+        }
+        // The date does not exist.
+        else {
+            None
+        }
+    }
+
+    /// Parses a number with the locale's decimal separator and optional grouping separators, e.g.
+    /// "-1.234,5" in German. Returns `None` if the text is no number.
+    pub fn parse_number(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(text) => ("-", text),
+            None => ("", text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (integer_part, fraction_part) = text
+            .split_once(self.decimal_separator)
+            .unwrap_or((text, ""));
+
+        // Grouping separators are only allowed between integer digits.
+        let integer_digits = integer_part
+            .chars()
+            .filter(|character| !self.is_grouping_separator(*character))
+            .collect::<String>();
+
+        // There are no digits or other characters than digits.
+        if integer_digits.len() + fraction_part.len() == 0
+            || !is_digits(&integer_digits)
+            || !is_digits(fraction_part)
+            || integer_part.starts_with(|character| self.is_grouping_separator(character))
+        {
+            return None;
+        }
+
+        format!("{sign}{integer_digits}.{fraction_part}")
+            .trim_end_matches('.')
+            .parse()
+            .ok()
+    }
+
+    /// Returns `true` if the character separates groups of integer digits. Users type spaces
+    /// instead of no-break spaces.
+    fn is_grouping_separator(&self, character: char) -> bool {
+        character == self.grouping_separator
+            || (self.grouping_separator == '\u{a0}' && matches!(character, ' ' | '\u{202f}'))
+    }
+}
+
+/// Returns `true` if the text has only the digits 0 to 9.
+fn is_digits(text: &str) -> bool {
+    text.chars().all(|character| character.is_ascii_digit())
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        let english = LocaleFormat::new("en-US");
+        let german = LocaleFormat::new("de-DE");
+        let french = LocaleFormat::new("fr");
+
+        // Formatting.
+        assert_eq!(english.format_number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(german.format_number(1234567.891, 2), "1.234.567,89");
+        assert_eq!(french.format_number(-1234.5, 1), "-1\u{a0}234,5");
+        assert_eq!(english.format_number(123.0, 0), "123");
+        assert_eq!(english.format_number(-0.001, 2), "0.00");
+
+        // Parsing.
+        assert_eq!(english.parse_number("1,234.5"), Some(1234.5));
+        assert_eq!(german.parse_number(" -1.234,5 "), Some(-1234.5));
+        assert_eq!(german.parse_number("1234"), Some(1234.0));
+        assert_eq!(german.parse_number(",5"), Some(0.5));
+        assert_eq!(french.parse_number("1 234,5"), Some(1234.5));
+
+        // Invalid numbers.
+        assert_eq!(german.parse_number("1,234.5"), None);
+        assert_eq!(english.parse_number("1.2.3"), None);
+        assert_eq!(english.parse_number(",123"), None);
+        assert_eq!(english.parse_number("12a"), None);
+        assert_eq!(english.parse_number(""), None);
+        assert_eq!(english.parse_number("-"), None);
+    }
+
+    #[test]
+    fn test_dates() {
+        let date = Date {
+            day: 18,
+            month: 10,
+            year: 2026,
+        };
+
+        // Formatting.
+        assert_eq!(LocaleFormat::new("en-US").format_date(&date), "10/18/2026");
+        assert_eq!(LocaleFormat::new("en-GB").format_date(&date), "18/10/2026");
+        assert_eq!(LocaleFormat::new("de").format_date(&date), "18.10.2026");
+        assert_eq!(LocaleFormat::new("he-IL").format_date(&date), "18/10/2026");
+        assert_eq!(LocaleFormat::new("ja").format_date(&date), "2026/10/18");
+
+        // Parsing.
+        assert_eq!(LocaleFormat::new("de").parse_date("18.10.2026"), Some(date));
+        assert_eq!(LocaleFormat::new("en").parse_date("10/18/2026"), Some(date));
+        assert_eq!(LocaleFormat::new("sv").parse_date("2026-10-18"), Some(date));
+        assert_eq!(
+            LocaleFormat::new("de").parse_date("1.2.2024"),
+            Some(Date {
+                day: 1,
+                month: 2,
+                year: 2024,
+            })
+        );
+
+        // Invalid dates.
+        assert_eq!(LocaleFormat::new("de").parse_date("29.2.2026"), None);
+        assert_eq!(LocaleFormat::new("de").parse_date("18/10/2026"), None);
+        assert_eq!(LocaleFormat::new("de").parse_date("18.13.2026"), None);
+        assert_eq!(LocaleFormat::new("de").parse_date("18.10."), None);
+    }
+}
@@ -0,0 +1,241 @@
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use guiver::{Message, MessageArgument, WidgetError};
+use std::rc::Rc;
+use unic_langid::LanguageIdentifier;
+
+/// The message catalogs in Fluent syntax and the locale in which messages are looked up.
+#[derive(Clone)]
+pub(crate) struct MessageCatalogs {
+    /// The catalogs with their locales, in the order they were added.
+    catalogs: Vec<(LanguageIdentifier, Rc<FluentResource>)>,
+    /// The current locale.
+    locale: LanguageIdentifier,
+}
+
+impl MessageCatalogs {
+    ///
+    pub(crate) fn new() -> Self {
+        MessageCatalogs {
+            catalogs: vec![],
+            locale: "en-US".parse().unwrap(),
+        }
+    }
+
+    /// Parses the Fluent source and adds it as a catalog for the given locale.
+    pub(crate) fn add_catalog(
+        &mut self,
+        locale: &str,
+        fluent_source: String,
+    ) -> Result<(), WidgetError> {
+        let language_identifier = language_identifier(locale)?;
+        let fluent_resource = FluentResource::try_new(fluent_source).map_err(|(_, errors)| {
+            WidgetError::InvalidMessageCatalog {
+                locale: locale.to_string(),
+                description: errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            }
+        })?;
+
+        self.catalogs
+            .push((language_identifier, Rc::new(fluent_resource)));
+
+        Ok(())
+    }
+
+    /// Returns the bundle of the catalogs that apply to the current locale: first those of its
+    /// language, e.g. "de" for "de-AT", then those of the locale itself, so that they take
+    /// precedence.
+    pub(crate) fn bundle(&self) -> FluentBundle<Rc<FluentResource>> {
+        let language = LanguageIdentifier::from_parts(self.locale.language, None, None, &[]);
+        let mut bundle = FluentBundle::new(vec![self.locale.clone()]);
+
+        // The catalogs of the locale's language.
+        for (language_identifier, fluent_resource) in &self.catalogs {
+            if *language_identifier == language && language != self.locale {
+                bundle.add_resource_overriding(fluent_resource.clone());
+            }
+        }
+
+        // The catalogs of the locale.
+        for (language_identifier, fluent_resource) in &self.catalogs {
+            if *language_identifier == self.locale {
+                bundle.add_resource_overriding(fluent_resource.clone());
+            }
+        }
+
+        bundle
+    }
+
+    ///
+    pub(crate) fn locale(&self) -> &LanguageIdentifier {
+        &self.locale
+    }
+
+    ///
+    pub(crate) fn set_locale(&mut self, locale: &str) -> Result<(), WidgetError> {
+        self.locale = language_identifier(locale)?;
+
+        Ok(())
+    }
+}
+
+/// Parses the given locale, e.g. "en-US".
+fn language_identifier(locale: &str) -> Result<LanguageIdentifier, WidgetError> {
+    locale
+        .parse()
+        .map_err(|_| WidgetError::InvalidLocale(locale.to_string()))
+}
+
+/// Returns the given message's translation in the bundle. A key like `dialog.title` refers to the
+/// attribute `title` of the message `dialog`. A message that the bundle does not have results in
+/// its key, so that it is noticed in the user interface.
+pub(crate) fn localized_text(
+    bundle: &FluentBundle<Rc<FluentResource>>,
+    message: &Message,
+) -> String {
+    let (message_id, attribute_name) = match message.key.split_once('.') {
+        // The key refers to an attribute.
+        Some((message_id, attribute_name)) => (message_id, Some(attribute_name)),
+        // The key refers to a message's value.
+        None => (message.key.as_str(), None),
+    };
+
+    let pattern = bundle
+        .get_message(message_id)
+        .and_then(|fluent_message| match attribute_name {
+            Some(attribute_name) => fluent_message
+                .get_attribute(attribute_name)
+                .map(|attribute| attribute.value()),
+            None => fluent_message.value(),
+        });
+
+    // The bundle has the message.
+    if let Some(pattern) = pattern {
+        let mut fluent_args = FluentArgs::new();
+
+        // Iterate over the message's arguments.
+        for (name, argument) in &message.arguments {
+            match argument {
+                MessageArgument::Number(number) => {
+                    fluent_args.set(name.as_str(), FluentValue::from(*number))
+                }
+                MessageArgument::Text(text) => fluent_args.set(name.as_str(), text.as_str()),
+            }
+        }
+
+        // Arguments missing in the message are shown as their names, so the errors are ignored.
+        let mut errors = vec![];
+
+        bundle
+            .format_pattern(pattern, Some(&fluent_args), &mut errors)
+            .into_owned()
+    }
+    // The bundle does not have the message.
+    else {
+        message.key.clone()
+    }
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fluent isolates placeables from the surrounding text, so that e.g. a Hebrew name in an
+    /// English sentence does not reorder the sentence.
+    fn isolated(text: &str) -> String {
+        format!("\u{2068}{}\u{2069}", text)
+    }
+
+    #[test]
+    fn test_localized_text() {
+        let mut message_catalogs = MessageCatalogs::new();
+        message_catalogs
+            .add_catalog(
+                "en",
+                "greeting = Hello, { $name }!
+files = { $count ->
+    [one] One file
+   *[other] { $count } files
+}
+dialog =
+    .title = Settings
+only-english = Only in English"
+                    .to_string(),
+            )
+            .unwrap();
+        message_catalogs
+            .add_catalog("de", "greeting = Hallo, { $name }!".to_string())
+            .unwrap();
+        message_catalogs
+            .add_catalog("de-AT", "greeting = Servus, { $name }!".to_string())
+            .unwrap();
+
+        let text = |message_catalogs: &MessageCatalogs, message: &Message| {
+            localized_text(&message_catalogs.bundle(), message)
+        };
+        let greeting = Message::new("greeting").with_argument("name", "Ada");
+
+        // Arguments, plurals and attributes.
+        assert_eq!(
+            text(&message_catalogs, &greeting),
+            format!("Hello, {}!", isolated("Ada"))
+        );
+        assert_eq!(
+            text(
+                &message_catalogs,
+                &Message::new("files").with_argument("count", 1.0)
+            ),
+            "One file"
+        );
+        assert_eq!(
+            text(
+                &message_catalogs,
+                &Message::new("files").with_argument("count", 3.0)
+            ),
+            format!("{} files", isolated("3"))
+        );
+        assert_eq!(
+            text(&message_catalogs, &Message::new("dialog.title")),
+            "Settings"
+        );
+
+        // A missing message results in its key.
+        assert_eq!(text(&message_catalogs, &Message::new("missing")), "missing");
+
+        // A locale uses the catalogs of its language, with its own catalogs taking precedence.
+        message_catalogs.set_locale("de-DE").unwrap();
+        assert_eq!(
+            text(&message_catalogs, &greeting),
+            format!("Hallo, {}!", isolated("Ada"))
+        );
+        message_catalogs.set_locale("de-AT").unwrap();
+        assert_eq!(
+            text(&message_catalogs, &greeting),
+            format!("Servus, {}!", isolated("Ada"))
+        );
+        assert_eq!(
+            text(&message_catalogs, &Message::new("only-english")),
+            "only-english"
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut message_catalogs = MessageCatalogs::new();
+
+        assert!(matches!(
+            message_catalogs.add_catalog("en", "greeting Hello".to_string()),
+            Err(WidgetError::InvalidMessageCatalog { .. })
+        ));
+        assert!(matches!(
+            message_catalogs.set_locale("not a locale"),
+            Err(WidgetError::InvalidLocale(_))
+        ));
+        assert_eq!(message_catalogs.locale().to_string(), "en-US");
+    }
+}
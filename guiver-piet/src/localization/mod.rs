@@ -0,0 +1,5 @@
+mod locale_format;
+mod message_catalogs;
+
+pub use locale_format::{Date, LocaleFormat};
+pub(crate) use message_catalogs::{localized_text, MessageCatalogs};
@@ -1,4 +1,7 @@
+use druid_shell::kurbo::Rect;
 use druid_shell::piet::PietText;
+use druid_shell::Scale;
+use guiver::WidgetId;
 
 #[cfg(any(target_os = "linux", target_os = "openbsd", target_os = "freebsd"))]
 use druid_shell::piet::CairoText;
@@ -25,6 +28,13 @@ pub fn piet_text() -> PietText {
 ///
 pub struct PietSharedState {
     piet_text: PietText,
+    /// The IDs of the widgets under the pointer, from the main widget down to the topmost widget.
+    /// The widget manager sets this before it dispatches a pointer event.
+    pointer_widget_path: Vec<WidgetId>,
+    /// The window's scale from logical units to device pixels.
+    scale: Scale,
+    /// The factor by which the user zoomed the window's content.
+    zoom_factor: f64,
 }
 
 impl PietSharedState {
@@ -32,11 +42,128 @@ impl PietSharedState {
     pub fn new() -> Self {
         PietSharedState {
             piet_text: piet_text(),
+            pointer_widget_path: vec![],
+            scale: Scale::default(),
+            zoom_factor: 1.0,
         }
     }
 
+    /// Returns `true` if the widget with the given ID is the topmost widget under the pointer or one
+    /// of its ancestors. Widgets use this instead of checking their rectangle, so that hidden widgets,
+    /// clipping and overlays are respected.
+    pub fn is_under_pointer(&self, widget_id: WidgetId) -> bool {
+        self.pointer_widget_path.contains(&widget_id)
+    }
+
     ///
     pub fn piet_text(&mut self) -> &mut PietText {
         &mut self.piet_text
     }
+
+    /// Returns the window's scale from logical units to device pixels, without the zoom factor.
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    ///
+    pub(crate) fn set_pointer_widget_path(&mut self, pointer_widget_path: Vec<WidgetId>) {
+        self.pointer_widget_path = pointer_widget_path;
+    }
+
+    /// Sets the window's scale. The widget manager does this when the scale changes. Without a
+    /// window, e.g. in tests, a synthetic scale can be set.
+    pub fn set_scale(&mut self, scale: Scale) {
+        self.scale = scale;
+    }
+
+    ///
+    pub(crate) fn set_zoom_factor(&mut self, zoom_factor: f64) {
+        self.zoom_factor = zoom_factor;
+    }
+
+    /// Returns the given rectangle, moved to the device pixel grid, so that a stroke of the given
+    /// width along its edges covers whole device pixels instead of blurring across two of them.
+    pub fn snap_stroke_rect(&self, rect: Rect, stroke_width: f64) -> Rect {
+        let (scale_x, scale_y) = self.device_scale();
+
+        Rect::new(
+            snap(rect.x0, stroke_width, scale_x),
+            snap(rect.y0, stroke_width, scale_y),
+            snap(rect.x1, stroke_width, scale_x),
+            snap(rect.y1, stroke_width, scale_y),
+        )
+    }
+
+    /// Returns the given stroke width, rounded to whole device pixels, but at least one.
+    pub fn snap_stroke_width(&self, stroke_width: f64) -> f64 {
+        let (scale_x, _) = self.device_scale();
+
+        (stroke_width * scale_x).round().max(1.0) / scale_x
+    }
+
+    /// Returns the factor by which the user zoomed the window's content.
+    pub fn zoom_factor(&self) -> f64 {
+        self.zoom_factor
+    }
+
+    /// Returns the horizontal and vertical scale from zoomed logical units to device pixels.
+    fn device_scale(&self) -> (f64, f64) {
+        (
+            self.scale.x() * self.zoom_factor,
+            self.scale.y() * self.zoom_factor,
+        )
+    }
+}
+
+/// Snaps the given coordinate of a stroke with the given width to the device pixel grid with the
+/// given scale: a stroke that is an odd number of device pixels wide is centered on a pixel, an even
+/// one on the border between two pixels.
+fn snap(coordinate: f64, stroke_width: f64, scale: f64) -> f64 {
+    let device_coordinate = coordinate * scale;
+    let device_stroke_width = (stroke_width * scale).round().max(1.0);
+
+    let snapped_device_coordinate = if device_stroke_width % 2.0 == 1.0 {
+        device_coordinate.floor() + 0.5
+    } else {
+        device_coordinate.round()
+    };
+
+    snapped_device_coordinate / scale
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::PietSharedState;
+    use druid_shell::kurbo::Rect;
+    use druid_shell::Scale;
+
+    #[test]
+    fn test_snap_stroke_rect() {
+        let mut shared_state = PietSharedState::new();
+        let rect = Rect::new(10.2, 10.7, 20.2, 20.7);
+
+        // A one pixel wide stroke is centered on device pixels.
+        assert_eq!(
+            shared_state.snap_stroke_rect(rect, 1.0),
+            Rect::new(10.5, 10.5, 20.5, 20.5)
+        );
+
+        // With a synthetic scale of 2, the stroke is two device pixels wide.
+        shared_state.set_scale(Scale::new(2.0, 2.0));
+        assert_eq!(
+            shared_state.snap_stroke_rect(rect, 1.0),
+            Rect::new(10.0, 10.5, 20.0, 20.5)
+        );
+        assert_eq!(shared_state.snap_stroke_width(0.2), 0.5);
+
+        // Zooming multiplies the scale.
+        shared_state.set_scale(Scale::new(1.0, 1.0));
+        shared_state.set_zoom_factor(2.0);
+        assert_eq!(
+            shared_state.snap_stroke_rect(rect, 1.0),
+            Rect::new(10.0, 10.5, 20.0, 20.5)
+        );
+    }
 }
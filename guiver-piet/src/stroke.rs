@@ -1,3 +1,4 @@
+use crate::color::piet_color;
 use druid_shell::piet::{Color, PaintBrush, StrokeStyle};
 
 ///
@@ -17,3 +18,16 @@ impl Default for Stroke {
         }
     }
 }
+
+impl From<guiver::Stroke> for Stroke {
+    fn from(stroke: guiver::Stroke) -> Self {
+        let mut stroke_style = StrokeStyle::default();
+        stroke_style.set_dash_pattern(stroke.stroke_dash_pattern);
+
+        Stroke {
+            stroke_brush: PaintBrush::Color(piet_color(stroke.stroke_color)),
+            stroke_style,
+            stroke_width: stroke.stroke_width,
+        }
+    }
+}
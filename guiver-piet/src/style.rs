@@ -8,6 +8,8 @@ use guiver::VerticalAlignment;
 pub struct Style {
     pub accent_color: Color,
     pub debug_rendering_stroke: Stroke,
+    /// The color that is painted over disabled widgets to dim them.
+    pub disabled_overlay_color: Color,
     pub font: Font,
     pub frame_color: Color,
     pub padding: f64,
@@ -24,6 +26,7 @@ impl Default for Style {
                 stroke_style: StrokeStyle::default(),
                 stroke_width: 1.0,
             },
+            disabled_overlay_color: Color::rgba8(0, 0, 0, 128),
             font: Font::default(),
             frame_color: Color::rgb8(120, 120, 120),
             padding: 15.0,
@@ -1,2 +1,14 @@
+mod text_bidi;
+mod text_boundary;
 mod text_cursor;
 mod text_selection;
+
+pub(crate) use text_bidi::{
+    paragraph_direction, text_cursor_moved_left, text_cursor_moved_right, visual_selection_ranges,
+};
+pub(crate) use text_boundary::grapheme_boundary_at_or_before;
+pub(crate) use text_cursor::{
+    left_character_removed, left_word_removed, right_character_removed, right_word_removed,
+    text_inserted, TextCursor,
+};
+pub(crate) use text_selection::{selected_text, selected_text_replaced, TextSelection};
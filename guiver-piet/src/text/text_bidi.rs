@@ -0,0 +1,242 @@
+use crate::text::text_boundary::grapheme_boundary_at_or_before;
+use crate::text::text_cursor::TextCursor;
+use guiver::LayoutDirection;
+use std::ops::Range;
+use unicode_bidi::{get_base_direction, Direction, Level, ParagraphBidiInfo};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Returns the direction of the text's first character with a strong direction, e.g. right-to-left
+/// for a Hebrew letter. Returns `None` for texts without such characters, e.g. digits only.
+pub(crate) fn paragraph_direction(text: &str) -> Option<LayoutDirection> {
+    match get_base_direction(text) {
+        Direction::Ltr => Some(LayoutDirection::LeftToRight),
+        Direction::Rtl => Some(LayoutDirection::RightToLeft),
+        // There is no character with a strong direction.
+        Direction::Mixed => None,
+    }
+}
+
+/// Returns the text cursor that is visually left of the given text cursor in a paragraph with the
+/// given direction. Within right-to-left runs, e.g. Arabic or Hebrew words, this moves towards the
+/// end of the text.
+pub(crate) fn text_cursor_moved_left(
+    text: &str,
+    text_cursor: &TextCursor,
+    paragraph_direction: LayoutDirection,
+) -> TextCursor {
+    let visual_byte_indices = visual_byte_indices(text, paragraph_direction);
+    let byte_index = grapheme_boundary_at_or_before(text, text_cursor.left_of_byte_index);
+
+    // Find the text cursor's visual position.
+    let left_of_byte_index = match visual_byte_indices.iter().position(|i| *i == byte_index) {
+        // The text cursor is not at the left edge.
+        Some(position) if position > 0 => visual_byte_indices[position - 1],
+        // The text cursor stays where it is.
+        _ => byte_index,
+    };
+
+    TextCursor { left_of_byte_index }
+}
+
+/// Returns the text cursor that is visually right of the given text cursor in a paragraph with the
+/// given direction. Within right-to-left runs this moves towards the beginning of the text.
+pub(crate) fn text_cursor_moved_right(
+    text: &str,
+    text_cursor: &TextCursor,
+    paragraph_direction: LayoutDirection,
+) -> TextCursor {
+    let visual_byte_indices = visual_byte_indices(text, paragraph_direction);
+    let byte_index = grapheme_boundary_at_or_before(text, text_cursor.left_of_byte_index);
+
+    // Find the text cursor's visual position.
+    let left_of_byte_index = match visual_byte_indices.iter().position(|i| *i == byte_index) {
+        // The text cursor is not at the right edge.
+        Some(position) if position + 1 < visual_byte_indices.len() => {
+            visual_byte_indices[position + 1]
+        }
+        // The text cursor stays where it is.
+        _ => byte_index,
+    };
+
+    TextCursor { left_of_byte_index }
+}
+
+/// Returns the text cursor positions, i.e. the byte indices of grapheme cluster boundaries, in
+/// visual order from left to right.
+fn visual_byte_indices(text: &str, paragraph_direction: LayoutDirection) -> Vec<usize> {
+    let mut visual_byte_indices = vec![];
+    let mut right_edge_byte_index = 0;
+
+    // Iterate over the runs from left to right.
+    for (run, run_direction) in visual_runs(text, paragraph_direction) {
+        let graphemes = text[run.clone()]
+            .grapheme_indices(true)
+            .map(|(byte_index, grapheme)| {
+                (
+                    run.start + byte_index,
+                    run.start + byte_index + grapheme.len(),
+                )
+            });
+
+        match run_direction {
+            // The left edge of each grapheme cluster is its beginning.
+            LayoutDirection::LeftToRight => {
+                visual_byte_indices.extend(graphemes.map(|(begin, _)| begin));
+                right_edge_byte_index = run.end;
+            }
+            // The left edge of each grapheme cluster is its end.
+            LayoutDirection::RightToLeft => {
+                visual_byte_indices.extend(graphemes.rev().map(|(_, end)| end));
+                right_edge_byte_index = run.start;
+            }
+        }
+    }
+
+    visual_byte_indices.push(right_edge_byte_index);
+
+    visual_byte_indices
+}
+
+/// Returns the parts of the text with a single direction, e.g. an Arabic word within an English
+/// sentence, in visual order from left to right.
+pub(crate) fn visual_runs(
+    text: &str,
+    paragraph_direction: LayoutDirection,
+) -> Vec<(Range<usize>, LayoutDirection)> {
+    // There are no runs.
+    if text.is_empty() {
+        return vec![];
+    }
+
+    let paragraph_level = match paragraph_direction {
+        LayoutDirection::LeftToRight => Level::ltr(),
+        LayoutDirection::RightToLeft => Level::rtl(),
+    };
+    let paragraph_bidi_info = ParagraphBidiInfo::new(text, Some(paragraph_level));
+    let (levels, runs) = paragraph_bidi_info.visual_runs(0..text.len());
+
+    runs.into_iter()
+        .map(|run| {
+            let run_direction = if levels[run.start].is_rtl() {
+                LayoutDirection::RightToLeft
+            } else {
+                LayoutDirection::LeftToRight
+            };
+
+            (run, run_direction)
+        })
+        .collect()
+}
+
+/// Returns the parts of the selected byte range that are painted as separate rectangles, in visual
+/// order from left to right. A selection across a direction change is visually discontiguous.
+pub(crate) fn visual_selection_ranges(
+    text: &str,
+    selection: Range<usize>,
+    paragraph_direction: LayoutDirection,
+) -> Vec<Range<usize>> {
+    visual_runs(text, paragraph_direction)
+        .into_iter()
+        .map(|(run, _)| run.start.max(selection.start)..run.end.min(selection.end))
+        .filter(|range| range.start < range.end)
+        .collect()
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LTR: LayoutDirection = LayoutDirection::LeftToRight;
+    const RTL: LayoutDirection = LayoutDirection::RightToLeft;
+
+    #[test]
+    fn test_paragraph_direction() {
+        assert_eq!(paragraph_direction("abc"), Some(LTR));
+        assert_eq!(paragraph_direction("שלום"), Some(RTL));
+        assert_eq!(paragraph_direction("مرحبا"), Some(RTL));
+        assert_eq!(paragraph_direction("123 שלום abc"), Some(RTL));
+        assert_eq!(paragraph_direction("abc שלום"), Some(LTR));
+        assert_eq!(paragraph_direction("123"), None);
+        assert_eq!(paragraph_direction(""), None);
+    }
+
+    #[test]
+    fn test_text_cursor_moved() {
+        let moved_left = |text: &str, left_of_byte_index: usize, direction: LayoutDirection| {
+            text_cursor_moved_left(text, &TextCursor { left_of_byte_index }, direction)
+                .left_of_byte_index
+        };
+        let moved_right = |text: &str, left_of_byte_index: usize, direction: LayoutDirection| {
+            text_cursor_moved_right(text, &TextCursor { left_of_byte_index }, direction)
+                .left_of_byte_index
+        };
+
+        // Left-to-right text.
+        {
+            assert_eq!(moved_right("abc", 1, LTR), 2);
+            assert_eq!(moved_right("abc", 3, LTR), 3);
+            assert_eq!(moved_left("abc", 1, LTR), 0);
+            assert_eq!(moved_left("abc", 0, LTR), 0);
+            assert_eq!(moved_right("äbc", 0, LTR), 2);
+            assert_eq!(moved_right("e\u{301}x", 0, LTR), 3);
+        }
+
+        // Right-to-left text. Each Hebrew letter takes two bytes.
+        {
+            assert_eq!(moved_left("אבג", 0, RTL), 2);
+            assert_eq!(moved_left("אבג", 2, RTL), 4);
+            assert_eq!(moved_left("אבג", 6, RTL), 6);
+            assert_eq!(moved_right("אבג", 4, RTL), 2);
+            assert_eq!(moved_right("אבג", 0, RTL), 0);
+        }
+
+        // A right-to-left word in a left-to-right paragraph, displayed as "ab בא".
+        {
+            let text = "ab אב";
+
+            assert_eq!(moved_right(text, 0, LTR), 1);
+            assert_eq!(moved_right(text, 2, LTR), 7);
+            assert_eq!(moved_right(text, 7, LTR), 5);
+            assert_eq!(moved_right(text, 5, LTR), 3);
+            assert_eq!(moved_left(text, 3, LTR), 5);
+        }
+
+        // Out of range text cursor.
+        {
+            assert_eq!(moved_left("", 0, LTR), 0);
+            assert_eq!(moved_right("abc", 10, LTR), 3);
+        }
+    }
+
+    #[test]
+    fn test_visual_runs() {
+        assert_eq!(visual_runs("", LTR), vec![]);
+        assert_eq!(visual_runs("abc", LTR), vec![(0..3, LTR)]);
+        assert_eq!(visual_runs("אבג", RTL), vec![(0..6, RTL)]);
+
+        // The runs of a right-to-left paragraph are in reverse order.
+        assert_eq!(visual_runs("abc אבג", LTR), vec![(0..4, LTR), (4..10, RTL)]);
+        assert_eq!(visual_runs("אבג abc", RTL), vec![(7..10, LTR), (0..7, RTL)]);
+    }
+
+    #[test]
+    fn test_visual_selection_ranges() {
+        // A contiguous selection.
+        assert_eq!(visual_selection_ranges("abc", 1..3, LTR), vec![1..3]);
+
+        // A selection across a direction change.
+        assert_eq!(
+            visual_selection_ranges("abc אבג", 2..6, LTR),
+            vec![2..4, 4..6]
+        );
+        assert_eq!(
+            visual_selection_ranges("אבג abc", 4..9, RTL),
+            vec![7..9, 4..7]
+        );
+
+        // An empty selection.
+        assert_eq!(visual_selection_ranges("abc", 1..1, LTR), vec![]);
+    }
+}
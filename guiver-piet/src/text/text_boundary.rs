@@ -0,0 +1,160 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Returns the byte index of the grapheme cluster boundary at or before the given byte index. Grapheme
+/// clusters are the characters that users perceive, e.g. "e" with a combining accent or an emoji
+/// joined from several emojis. An index beyond the text results in the text's length.
+pub(crate) fn grapheme_boundary_at_or_before(text: &str, byte_index: usize) -> usize {
+    // The index is at or beyond the text's end.
+    if byte_index >= text.len() {
+        return text.len();
+    }
+
+    text.grapheme_indices(true)
+        .map(|(grapheme_byte_index, _)| grapheme_byte_index)
+        .take_while(|grapheme_byte_index| *grapheme_byte_index <= byte_index)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Returns the byte index of the grapheme cluster boundary at or after the given byte index. An
+/// index beyond the text results in the text's length.
+pub(crate) fn grapheme_boundary_at_or_after(text: &str, byte_index: usize) -> usize {
+    // The index is a boundary.
+    if grapheme_boundary_at_or_before(text, byte_index) == byte_index {
+        byte_index
+    }
+    // The index is within a grapheme cluster.
+    else {
+        next_grapheme_boundary(text, byte_index)
+    }
+}
+
+/// Returns the byte index of the grapheme cluster boundary after the given byte index, or the text's
+/// length if there is none.
+pub(crate) fn next_grapheme_boundary(text: &str, byte_index: usize) -> usize {
+    text.grapheme_indices(true)
+        .map(|(grapheme_byte_index, _)| grapheme_byte_index)
+        .find(|grapheme_byte_index| *grapheme_byte_index > byte_index)
+        .unwrap_or(text.len())
+}
+
+/// Returns the byte index of the end of the word after the given byte index, or the text's length
+/// if there is none. Words are found with the Unicode word boundary rules, so e.g. each CJK
+/// ideograph is a word of its own. Whitespace and punctuation are skipped.
+pub(crate) fn next_word_boundary(text: &str, byte_index: usize) -> usize {
+    text.split_word_bound_indices()
+        .filter(|(_, segment)| is_word(segment))
+        .map(|(segment_byte_index, segment)| segment_byte_index + segment.len())
+        .find(|word_end_byte_index| *word_end_byte_index > byte_index)
+        .unwrap_or(text.len())
+}
+
+/// Returns the byte index of the grapheme cluster boundary before the given byte index, or 0 if
+/// there is none.
+pub(crate) fn previous_grapheme_boundary(text: &str, byte_index: usize) -> usize {
+    text.grapheme_indices(true)
+        .map(|(grapheme_byte_index, _)| grapheme_byte_index)
+        .take_while(|grapheme_byte_index| *grapheme_byte_index < byte_index)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Returns the byte index of the start of the word before the given byte index, or 0 if there is
+/// none. Whitespace and punctuation are skipped.
+pub(crate) fn previous_word_boundary(text: &str, byte_index: usize) -> usize {
+    text.split_word_bound_indices()
+        .filter(|(_, segment)| is_word(segment))
+        .map(|(segment_byte_index, _)| segment_byte_index)
+        .take_while(|word_byte_index| *word_byte_index < byte_index)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Returns `true` if the given segment of a text split at word boundaries is a word, i.e. neither
+/// whitespace nor punctuation.
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grapheme_boundaries() {
+        // An umlaut takes two bytes.
+        {
+            let text = "äbc";
+
+            assert_eq!(grapheme_boundary_at_or_before(text, 1), 0);
+            assert_eq!(grapheme_boundary_at_or_after(text, 1), 2);
+            assert_eq!(next_grapheme_boundary(text, 0), 2);
+            assert_eq!(previous_grapheme_boundary(text, 2), 0);
+        }
+
+        // A combining accent belongs to the letter before it.
+        {
+            let text = "e\u{301}x";
+
+            assert_eq!(next_grapheme_boundary(text, 0), 3);
+            assert_eq!(previous_grapheme_boundary(text, 3), 0);
+            assert_eq!(grapheme_boundary_at_or_before(text, 1), 0);
+            assert_eq!(grapheme_boundary_at_or_after(text, 1), 3);
+        }
+
+        // An emoji joined with zero width joiners is a single grapheme cluster.
+        {
+            let text = "a👩\u{200d}👩\u{200d}👧b";
+            let family_end = text.len() - 1;
+
+            assert_eq!(next_grapheme_boundary(text, 1), family_end);
+            assert_eq!(previous_grapheme_boundary(text, family_end), 1);
+            assert_eq!(grapheme_boundary_at_or_before(text, 5), 1);
+        }
+
+        // Each CJK ideograph is a grapheme cluster of three bytes.
+        {
+            let text = "日本語";
+
+            assert_eq!(next_grapheme_boundary(text, 0), 3);
+            assert_eq!(next_grapheme_boundary(text, 3), 6);
+            assert_eq!(previous_grapheme_boundary(text, 9), 6);
+        }
+
+        // Out of range indices.
+        {
+            assert_eq!(grapheme_boundary_at_or_before("abc", 10), 3);
+            assert_eq!(grapheme_boundary_at_or_after("abc", 10), 3);
+            assert_eq!(next_grapheme_boundary("abc", 10), 3);
+            assert_eq!(previous_grapheme_boundary("", 0), 0);
+        }
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        let text = "Grüße, Welt!  Foo";
+
+        // Whitespace and punctuation are skipped.
+        assert_eq!(next_word_boundary(text, 0), "Grüße".len());
+        assert_eq!(next_word_boundary(text, "Grüße".len()), "Grüße, Welt".len());
+        assert_eq!(
+            previous_word_boundary(text, text.len()),
+            "Grüße, Welt!  ".len()
+        );
+        assert_eq!(
+            previous_word_boundary(text, "Grüße, Welt!  ".len()),
+            "Grüße, ".len()
+        );
+        assert_eq!(previous_word_boundary(text, 3), 0);
+
+        // Each CJK ideograph is a word.
+        assert_eq!(next_word_boundary("日本語", 0), 3);
+        assert_eq!(previous_word_boundary("日本語", 9), 6);
+
+        // There is no word.
+        assert_eq!(next_word_boundary("  ", 0), 2);
+        assert_eq!(previous_word_boundary("  ", 2), 0);
+    }
+}
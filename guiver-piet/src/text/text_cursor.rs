@@ -1,3 +1,7 @@
+use crate::text::text_boundary::{
+    grapheme_boundary_at_or_before, next_grapheme_boundary, next_word_boundary,
+    previous_grapheme_boundary, previous_word_boundary,
+};
 use crate::text::text_selection::{selected_text_replaced, TextSelection};
 
 ///
@@ -6,81 +10,100 @@ pub(crate) struct TextCursor {
     pub(crate) left_of_byte_index: usize,
 }
 
-///
+/// Removes the grapheme cluster, i.e. the character that users perceive, left of the text cursor.
+/// A text cursor within a grapheme cluster is moved to its beginning first.
 pub(crate) fn left_character_removed(mut text: String, text_cursor: &TextCursor) -> String {
     // The text cursor is out of range.
     if text_cursor.left_of_byte_index == 0 || text_cursor.left_of_byte_index > text.len() {
         return text;
     }
 
-    let mut left_char_boundary = text_cursor.left_of_byte_index - 1;
-    let mut right_char_boundary = text_cursor.left_of_byte_index;
+    let right_boundary = grapheme_boundary_at_or_before(&text, text_cursor.left_of_byte_index);
+    let left_boundary = previous_grapheme_boundary(&text, right_boundary);
 
-    // Find valid character boundaries.
-    {
-        while !text.is_char_boundary(right_char_boundary) && right_char_boundary > 0 {
-            right_char_boundary -= 1;
-        }
+    // Remove the grapheme cluster.
+    text.replace_range(left_boundary..right_boundary, "");
 
-        while !text.is_char_boundary(left_char_boundary) && left_char_boundary > 0 {
-            left_char_boundary -= 1;
-        }
+    text
+}
+
+/// Removes the word left of the text cursor, including the whitespace and punctuation between the
+/// word and the text cursor.
+pub(crate) fn left_word_removed(mut text: String, text_cursor: &TextCursor) -> String {
+    // The text cursor is out of range.
+    if text_cursor.left_of_byte_index == 0 || text_cursor.left_of_byte_index > text.len() {
+        return text;
     }
 
-    // Remove the character.
-    text.replace_range(left_char_boundary..right_char_boundary, "");
+    let right_boundary = grapheme_boundary_at_or_before(&text, text_cursor.left_of_byte_index);
+    let left_boundary = previous_word_boundary(&text, right_boundary);
+
+    // Remove the word.
+    text.replace_range(left_boundary..right_boundary, "");
 
     text
 }
 
-///
+/// Removes the grapheme cluster right of the text cursor. A text cursor within a grapheme cluster
+/// is moved to its beginning first.
 pub(crate) fn right_character_removed(mut text: String, text_cursor: &TextCursor) -> String {
-    let mut left_char_boundary = text_cursor.left_of_byte_index;
-    let mut right_char_boundary = text_cursor.left_of_byte_index + 1;
+    // The text cursor is out of range.
+    if text_cursor.left_of_byte_index >= text.len() {
+        return text;
+    }
 
-    // Find valid character boundaries.
-    {
-        while !text.is_char_boundary(right_char_boundary) && right_char_boundary > 0 {
-            right_char_boundary += 1;
-        }
+    let left_boundary = grapheme_boundary_at_or_before(&text, text_cursor.left_of_byte_index);
+    let right_boundary = next_grapheme_boundary(&text, left_boundary);
 
-        while !text.is_char_boundary(left_char_boundary) && left_char_boundary > 0 {
-            left_char_boundary += 1;
-        }
-    }
+    // Remove the grapheme cluster.
+    text.replace_range(left_boundary..right_boundary, "");
 
+    text
+}
+
+/// Removes the word right of the text cursor, including the whitespace and punctuation between the
+/// text cursor and the word.
+pub(crate) fn right_word_removed(mut text: String, text_cursor: &TextCursor) -> String {
     // The text cursor is out of range.
-    if left_char_boundary >= text.len() {
+    if text_cursor.left_of_byte_index >= text.len() {
         return text;
     }
 
-    // The text cursor is out of range.
-    if right_char_boundary >= text.len() {
-        right_char_boundary = text.len();
-    }
+    let left_boundary = grapheme_boundary_at_or_before(&text, text_cursor.left_of_byte_index);
+    let right_boundary = next_word_boundary(&text, left_boundary);
 
-    // Remove the character.
-    text.replace_range(left_char_boundary..right_char_boundary, "");
+    // Remove the word.
+    text.replace_range(left_boundary..right_boundary, "");
 
     text
 }
 
-///
+/// Inserts the given text at the text cursor. A text cursor within a grapheme cluster is moved to
+/// its beginning first.
 pub(crate) fn text_inserted(
     text: String,
     text_cursor: &TextCursor,
     insertion_text: &str,
 ) -> String {
+    // The text cursor is out of range.
+    if text_cursor.left_of_byte_index > text.len() {
+        return text;
+    }
+
+    let left_of_byte_index = grapheme_boundary_at_or_before(&text, text_cursor.left_of_byte_index);
+
     selected_text_replaced(
         text,
         &TextSelection {
-            left_of_byte_index_begin: text_cursor.left_of_byte_index,
-            left_of_byte_index_end: text_cursor.left_of_byte_index,
+            left_of_byte_index_begin: left_of_byte_index,
+            left_of_byte_index_end: left_of_byte_index,
         },
         insertion_text,
     )
 }
 
+// =================================================================================================
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +205,125 @@ mod tests {
                 "bc".to_string()
             );
         }
+
+        // Grapheme clusters of several characters.
+        {
+            // A combining accent.
+            assert_eq!(
+                left_character_removed(
+                    "ae\u{301}".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 4
+                    },
+                ),
+                "a".to_string()
+            );
+            assert_eq!(
+                left_character_removed(
+                    "ae\u{301}".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 2
+                    },
+                ),
+                "e\u{301}".to_string()
+            );
+
+            // Emojis joined with zero width joiners.
+            assert_eq!(
+                left_character_removed(
+                    "a👩\u{200d}👩\u{200d}👧".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 19
+                    },
+                ),
+                "a".to_string()
+            );
+
+            // CJK ideographs.
+            assert_eq!(
+                left_character_removed(
+                    "日本語".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 6
+                    },
+                ),
+                "日語".to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_left_word_removed() {
+        // Out of range text cursor.
+        {
+            assert_eq!(
+                left_word_removed(
+                    "".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                ),
+                "".to_string()
+            );
+            assert_eq!(
+                left_word_removed(
+                    "abc".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 10
+                    },
+                ),
+                "abc".to_string()
+            );
+        }
+
+        // In range text cursor.
+        {
+            assert_eq!(
+                left_word_removed(
+                    "foo bar".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 7
+                    },
+                ),
+                "foo ".to_string()
+            );
+            assert_eq!(
+                left_word_removed(
+                    "foo bar, ".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 9
+                    },
+                ),
+                "foo ".to_string()
+            );
+            assert_eq!(
+                left_word_removed(
+                    "foo bar".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 5
+                    },
+                ),
+                "foo ar".to_string()
+            );
+            assert_eq!(
+                left_word_removed(
+                    "Grüße".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 7
+                    },
+                ),
+                "".to_string()
+            );
+            assert_eq!(
+                left_word_removed(
+                    "日本語".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 9
+                    },
+                ),
+                "日本".to_string()
+            );
+        }
     }
 
     #[test]
@@ -290,10 +432,249 @@ mod tests {
                 "äc".to_string()
             );
         }
+
+        // Grapheme clusters of several characters.
+        {
+            // A combining accent.
+            assert_eq!(
+                right_character_removed(
+                    "e\u{301}a".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                ),
+                "a".to_string()
+            );
+            assert_eq!(
+                right_character_removed(
+                    "e\u{301}a".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 1
+                    },
+                ),
+                "a".to_string()
+            );
+
+            // Emojis joined with zero width joiners.
+            assert_eq!(
+                right_character_removed(
+                    "👩\u{200d}👩\u{200d}👧a".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                ),
+                "a".to_string()
+            );
+
+            // CJK ideographs.
+            assert_eq!(
+                right_character_removed(
+                    "日本語".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 3
+                    },
+                ),
+                "日語".to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_right_word_removed() {
+        // Out of range text cursor.
+        {
+            assert_eq!(
+                right_word_removed(
+                    "".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                ),
+                "".to_string()
+            );
+            assert_eq!(
+                right_word_removed(
+                    "abc".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 3
+                    },
+                ),
+                "abc".to_string()
+            );
+        }
+
+        // In range text cursor.
+        {
+            assert_eq!(
+                right_word_removed(
+                    "foo bar".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                ),
+                " bar".to_string()
+            );
+            assert_eq!(
+                right_word_removed(
+                    "foo, bar".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 3
+                    },
+                ),
+                "foo".to_string()
+            );
+            assert_eq!(
+                right_word_removed(
+                    "foo bar".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 1
+                    },
+                ),
+                "f bar".to_string()
+            );
+            assert_eq!(
+                right_word_removed(
+                    "Grüße".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                ),
+                "".to_string()
+            );
+            assert_eq!(
+                right_word_removed(
+                    "日本語".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                ),
+                "本語".to_string()
+            );
+        }
     }
 
     #[test]
     fn test_text_inserted() {
-        // TODO
+        // Out of range text cursor.
+        {
+            assert_eq!(
+                text_inserted(
+                    "".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 10
+                    },
+                    "foo"
+                ),
+                "".to_string()
+            );
+            assert_eq!(
+                text_inserted(
+                    "abc".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 10
+                    },
+                    "foo"
+                ),
+                "abc".to_string()
+            );
+        }
+
+        // In range text cursor.
+        {
+            assert_eq!(
+                text_inserted(
+                    "".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                    "foo"
+                ),
+                "foo".to_string()
+            );
+            assert_eq!(
+                text_inserted(
+                    "abc".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 0
+                    },
+                    "foo"
+                ),
+                "fooabc".to_string()
+            );
+            assert_eq!(
+                text_inserted(
+                    "abc".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 1
+                    },
+                    "foo"
+                ),
+                "afoobc".to_string()
+            );
+            assert_eq!(
+                text_inserted(
+                    "abc".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 3
+                    },
+                    "foo"
+                ),
+                "abcfoo".to_string()
+            );
+            assert_eq!(
+                text_inserted(
+                    "äbc".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 2
+                    },
+                    "ö"
+                ),
+                "äöbc".to_string()
+            );
+            assert_eq!(
+                text_inserted(
+                    "äbc".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 1
+                    },
+                    "ö"
+                ),
+                "öäbc".to_string()
+            );
+        }
+
+        // Grapheme clusters of several characters.
+        {
+            assert_eq!(
+                text_inserted(
+                    "e\u{301}".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 2
+                    },
+                    "x"
+                ),
+                "xe\u{301}".to_string()
+            );
+            assert_eq!(
+                text_inserted(
+                    "👩\u{200d}👩\u{200d}👧".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 5
+                    },
+                    "x"
+                ),
+                "x👩\u{200d}👩\u{200d}👧".to_string()
+            );
+            assert_eq!(
+                text_inserted(
+                    "日語".to_string(),
+                    &TextCursor {
+                        left_of_byte_index: 3
+                    },
+                    "本"
+                ),
+                "日本語".to_string()
+            );
+        }
     }
 }
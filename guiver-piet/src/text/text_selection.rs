@@ -1,3 +1,5 @@
+use crate::text::text_boundary::{grapheme_boundary_at_or_after, grapheme_boundary_at_or_before};
+
 ///
 #[derive(Copy, Clone, Debug)]
 pub(crate) struct TextSelection {
@@ -7,11 +9,12 @@ pub(crate) struct TextSelection {
 
 // =================================================================================================
 
-/// Returns normalized indices, that is sorted indices where the end index is within range of the
-/// given string.
-fn normalized_indices(text: &str, text_selection: &TextSelection) -> (usize, usize) {
+/// Returns normalized indices, that is sorted indices on grapheme cluster boundaries where the end
+/// index is within range of the given string. The selection is widened to whole grapheme clusters.
+/// Returns `None` if the selection begins beyond the string's end.
+fn normalized_indices(text: &str, text_selection: &TextSelection) -> Option<(usize, usize)> {
     // Make sure that the indices are ordered.
-    let (mut left_of_byte_index_begin, mut left_of_byte_index_end) =
+    let (left_of_byte_index_begin, left_of_byte_index_end) =
         if text_selection.left_of_byte_index_begin > text_selection.left_of_byte_index_end {
             (
                 text_selection.left_of_byte_index_end,
@@ -24,34 +27,28 @@ fn normalized_indices(text: &str, text_selection: &TextSelection) -> (usize, usi
             )
         };
 
-    // Find valid character boundaries.
-    {
-        while !text.is_char_boundary(left_of_byte_index_begin) && left_of_byte_index_begin > 0 {
-            left_of_byte_index_begin -= 1;
-        }
-
-        while !text.is_char_boundary(left_of_byte_index_end) && left_of_byte_index_end > 0 {
-            left_of_byte_index_end -= 1;
-        }
-    }
-
-    // Trim the selection.
-    if left_of_byte_index_end > text.len() {
-        left_of_byte_index_end = text.len();
+    // The selection is out of range.
+    if left_of_byte_index_begin > text.len() {
+        return None;
     }
 
-    // TODO: use `str::is_char_boundary()`
-
-    (left_of_byte_index_begin, left_of_byte_index_end)
+    // Find valid grapheme cluster boundaries and trim the selection.
+    Some((
+        grapheme_boundary_at_or_before(text, left_of_byte_index_begin),
+        grapheme_boundary_at_or_after(text, left_of_byte_index_end),
+    ))
 }
 
 /// Tries to return a sub-string as defined by the given text selection.
 pub(crate) fn selected_text<'a>(text: &'a str, text_selection: &TextSelection) -> &'a str {
     // Normalize the indices.
-    let (left_of_byte_index_begin, left_of_byte_index_end) =
-        normalized_indices(text, text_selection);
-
-    &text[left_of_byte_index_begin..left_of_byte_index_end]
+    match normalized_indices(text, text_selection) {
+        Some((left_of_byte_index_begin, left_of_byte_index_end)) => {
+            &text[left_of_byte_index_begin..left_of_byte_index_end]
+        }
+        // The selection is out of range.
+        None => "",
+    }
 }
 
 /// Tries to replace a sub-string as defined by the given text selection.
@@ -62,12 +59,11 @@ pub(crate) fn selected_text_replaced(
 ) -> String {
     // Normalize the indices.
     let (left_of_byte_index_begin, left_of_byte_index_end) =
-        normalized_indices(&text, text_selection);
-
-    // The selection is out of range.
-    if left_of_byte_index_begin > text.len() {
-        return text;
-    }
+        match normalized_indices(&text, text_selection) {
+            Some(indices) => indices,
+            // The selection is out of range.
+            None => return text,
+        };
 
     // Replace the text selection.
     text.replace_range(
@@ -278,6 +274,80 @@ mod tests {
                 );
             }
         }
+
+        // Umlauts and grapheme clusters of several characters.
+        {
+            assert_eq!(
+                selected_text(
+                    "äbc",
+                    &TextSelection {
+                        left_of_byte_index_begin: 0,
+                        left_of_byte_index_end: 2,
+                    }
+                ),
+                "ä"
+            );
+            assert_eq!(
+                selected_text(
+                    "äbc",
+                    &TextSelection {
+                        left_of_byte_index_begin: 1,
+                        left_of_byte_index_end: 3,
+                    }
+                ),
+                "äb"
+            );
+            assert_eq!(
+                selected_text(
+                    "äbc",
+                    &TextSelection {
+                        left_of_byte_index_begin: 0,
+                        left_of_byte_index_end: 1,
+                    }
+                ),
+                "ä"
+            );
+            assert_eq!(
+                selected_text(
+                    "e\u{301}x",
+                    &TextSelection {
+                        left_of_byte_index_begin: 0,
+                        left_of_byte_index_end: 1,
+                    }
+                ),
+                "e\u{301}"
+            );
+            assert_eq!(
+                selected_text(
+                    "a👩\u{200d}👩\u{200d}👧b",
+                    &TextSelection {
+                        left_of_byte_index_begin: 1,
+                        left_of_byte_index_end: 5,
+                    }
+                ),
+                "👩\u{200d}👩\u{200d}👧"
+            );
+            assert_eq!(
+                selected_text(
+                    "日本語",
+                    &TextSelection {
+                        left_of_byte_index_begin: 3,
+                        left_of_byte_index_end: 6,
+                    }
+                ),
+                "本"
+            );
+            assert_eq!(
+                selected_text(
+                    "日本語",
+                    &TextSelection {
+                        left_of_byte_index_begin: 4,
+                        left_of_byte_index_end: 5,
+                    }
+                ),
+                "本"
+            );
+        }
     }
 
     #[test]
@@ -438,5 +508,75 @@ mod tests {
                 }
             }
         }
+
+        // Umlauts and grapheme clusters of several characters.
+        {
+            assert_eq!(
+                selected_text_replaced(
+                    "äbc".to_string(),
+                    &TextSelection {
+                        left_of_byte_index_begin: 0,
+                        left_of_byte_index_end: 2,
+                    },
+                    "ö"
+                ),
+                "öbc".to_string()
+            );
+            assert_eq!(
+                selected_text_replaced(
+                    "äbc".to_string(),
+                    &TextSelection {
+                        left_of_byte_index_begin: 1,
+                        left_of_byte_index_end: 2,
+                    },
+                    "ö"
+                ),
+                "öbc".to_string()
+            );
+            assert_eq!(
+                selected_text_replaced(
+                    "äbc".to_string(),
+                    &TextSelection {
+                        left_of_byte_index_begin: 2,
+                        left_of_byte_index_end: 10,
+                    },
+                    "ö"
+                ),
+                "äö".to_string()
+            );
+            assert_eq!(
+                selected_text_replaced(
+                    "e\u{301}x".to_string(),
+                    &TextSelection {
+                        left_of_byte_index_begin: 0,
+                        left_of_byte_index_end: 1,
+                    },
+                    "é"
+                ),
+                "éx".to_string()
+            );
+            assert_eq!(
+                selected_text_replaced(
+                    "a👩\u{200d}👩\u{200d}👧b".to_string(),
+                    &TextSelection {
+                        left_of_byte_index_begin: 1,
+                        left_of_byte_index_end: 5,
+                    },
+                    ""
+                ),
+                "ab".to_string()
+            );
+            assert_eq!(
+                selected_text_replaced(
+                    "日本語".to_string(),
+                    &TextSelection {
+                        left_of_byte_index_begin: 3,
+                        left_of_byte_index_end: 6,
+                    },
+                    ""
+                ),
+                "日語".to_string()
+            );
+        }
     }
 }
@@ -0,0 +1,208 @@
+use druid_shell::kurbo::{Point, Rect};
+use druid_shell::piet::HitTestPoint;
+use druid_shell::text::{Action, Affinity, InputHandler, Selection};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// The state of the focused text widget that the platform's text input, e.g. an input method
+/// editor (IME) for Chinese or Japanese or dead keys for accents, works on. The indices are byte
+/// indices into the text, the rectangles are in window coordinates.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextFieldState {
+    /// The text widget's rectangle.
+    pub bounding_box: Rect,
+    /// The caret's rectangle. The IME places its candidate window next to it.
+    pub caret_rectangle: Rect,
+    /// The x coordinates of the carets at the text's grapheme cluster boundaries, by their byte
+    /// indices. They resolve points and ranges for the IME.
+    pub caret_x_per_byte_index: Vec<(usize, f64)>,
+    /// The range of the preedit text, which the IME is still composing. It is painted underlined.
+    pub composition_range: Option<Range<usize>>,
+    /// The selection. If nothing is selected, both ends are at the caret.
+    pub selection: Selection,
+    ///
+    pub text: String,
+}
+
+/// Lets the platform's text input read and edit a copy of the focused text widget's state. The
+/// window event handler passes the edited state on in an `Event::TextFieldEdited`.
+pub(crate) struct TextFieldInputHandler {
+    text_field_state: Rc<RefCell<TextFieldState>>,
+    /// Set to `true` once the state was edited.
+    was_edited: Rc<Cell<bool>>,
+}
+
+impl TextFieldInputHandler {
+    ///
+    pub(crate) fn new(
+        text_field_state: Rc<RefCell<TextFieldState>>,
+        was_edited: Rc<Cell<bool>>,
+    ) -> Self {
+        TextFieldInputHandler {
+            text_field_state,
+            was_edited,
+        }
+    }
+}
+
+impl InputHandler for TextFieldInputHandler {
+    fn selection(&self) -> Selection {
+        self.text_field_state.borrow().selection
+    }
+
+    fn set_selection(&mut self, selection: Selection) {
+        self.text_field_state.borrow_mut().selection = selection;
+        self.was_edited.set(true);
+    }
+
+    fn composition_range(&self) -> Option<Range<usize>> {
+        self.text_field_state.borrow().composition_range.clone()
+    }
+
+    fn set_composition_range(&mut self, range: Option<Range<usize>>) {
+        self.text_field_state.borrow_mut().composition_range = range;
+        self.was_edited.set(true);
+    }
+
+    fn is_char_boundary(&self, i: usize) -> bool {
+        self.text_field_state.borrow().text.is_char_boundary(i)
+    }
+
+    fn len(&self) -> usize {
+        self.text_field_state.borrow().text.len()
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Owned(self.text_field_state.borrow().text[range].to_string())
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, text: &str) {
+        let mut text_field_state = self.text_field_state.borrow_mut();
+
+        text_field_state.text.replace_range(range.clone(), text);
+
+        // Put the caret behind the inserted text, as platforms expect.
+        text_field_state.selection = Selection::caret(range.start + text.len());
+
+        self.was_edited.set(true);
+    }
+
+    fn hit_test_point(&self, point: Point) -> HitTestPoint {
+        let text_field_state = self.text_field_state.borrow();
+
+        // The point resolves to the grapheme cluster boundary with the nearest caret.
+        let byte_index = text_field_state
+            .caret_x_per_byte_index
+            .iter()
+            .min_by(|(_, caret_x1), (_, caret_x2)| {
+                (caret_x1 - point.x)
+                    .abs()
+                    .total_cmp(&(caret_x2 - point.x).abs())
+            })
+            .map(|(byte_index, _)| *byte_index)
+            .unwrap_or(text_field_state.selection.active);
+
+        HitTestPoint::new(byte_index, text_field_state.bounding_box.contains(point))
+    }
+
+    fn line_range(&self, _index: usize, _affinity: Affinity) -> Range<usize> {
+        // Text widgets have a single line.
+        0..self.len()
+    }
+
+    fn bounding_box(&self) -> Option<Rect> {
+        Some(self.text_field_state.borrow().bounding_box)
+    }
+
+    fn slice_bounding_box(&self, range: Range<usize>) -> Option<Rect> {
+        let text_field_state = self.text_field_state.borrow();
+        let caret_x = |byte_index| {
+            text_field_state
+                .caret_x_per_byte_index
+                .iter()
+                .find(|(caret_byte_index, _)| *caret_byte_index == byte_index)
+                .map(|(_, caret_x)| *caret_x)
+        };
+
+        // Both ends of the range are on grapheme cluster boundaries. The IME asks for the
+        // composed text's rectangle to place its candidate window.
+        if let (Some(x0), Some(x1)) = (caret_x(range.start), caret_x(range.end)) {
+            Some(Rect::new(
+                x0.min(x1),
+                text_field_state.caret_rectangle.y0,
+                x0.max(x1),
+                text_field_state.caret_rectangle.y1,
+            ))
+        }
+        // The range is within grapheme clusters.
+        else {
+            Some(text_field_state.caret_rectangle)
+        }
+    }
+
+    fn handle_action(&mut self, action: Action) {
+        // Cancelling discards the preedit text.
+        if let Action::Cancel = action {
+            let mut text_field_state = self.text_field_state.borrow_mut();
+
+            // There is preedit text.
+            if let Some(composition_range) = text_field_state.composition_range.take() {
+                text_field_state
+                    .text
+                    .replace_range(composition_range.clone(), "");
+                text_field_state.selection = Selection::caret(composition_range.start);
+
+                self.was_edited.set(true);
+            }
+        }
+        // Other actions, like moving the caret or deleting, arrive as key events.
+    }
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::text_field::{TextFieldInputHandler, TextFieldState};
+    use druid_shell::kurbo::{Point, Rect};
+    use druid_shell::text::{InputHandler, Selection};
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_hit_test_point() {
+        // "aä" with a caret in front of each grapheme cluster and at the end.
+        let text_field_state = TextFieldState {
+            bounding_box: Rect::new(0.0, 0.0, 100.0, 20.0),
+            caret_rectangle: Rect::new(10.0, 5.0, 10.0, 15.0),
+            caret_x_per_byte_index: vec![(0, 10.0), (1, 20.0), (3, 30.0)],
+            selection: Selection::caret(3),
+            text: "aä".to_string(),
+            ..Default::default()
+        };
+        let input_handler = TextFieldInputHandler::new(
+            Rc::new(RefCell::new(text_field_state)),
+            Rc::new(Cell::new(false)),
+        );
+
+        // Points resolve to the nearest grapheme cluster boundary, not to the caret.
+        let hit_test_point = input_handler.hit_test_point(Point::new(14.0, 10.0));
+        assert_eq!(hit_test_point.idx, 0);
+        assert!(hit_test_point.is_inside);
+        assert_eq!(input_handler.hit_test_point(Point::new(24.0, 10.0)).idx, 1);
+        assert_eq!(input_handler.hit_test_point(Point::new(26.0, 10.0)).idx, 3);
+
+        // Points beside the text resolve to its nearest end.
+        let hit_test_point = input_handler.hit_test_point(Point::new(-5.0, 10.0));
+        assert_eq!(hit_test_point.idx, 0);
+        assert!(!hit_test_point.is_inside);
+
+        // Ranges span the carets at their ends.
+        assert_eq!(
+            input_handler.slice_bounding_box(1..3),
+            Some(Rect::new(20.0, 5.0, 30.0, 15.0))
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::shared_state::PietSharedState;
 
+use crate::color::piet_color;
+use crate::event::modifiers;
 use crate::font::Font;
 use crate::stroke::Stroke;
 use crate::widget::widget_core::WidgetCore;
@@ -9,13 +11,13 @@ use druid_shell::kurbo::RoundedRect;
 use druid_shell::piet::{Color, LinearGradient, PaintBrush, Piet, RenderContext, UnitPoint};
 use druid_shell::{kurbo, piet, KbKey, Region};
 use guiver::{
-    HorizontalAlignment, Point, Rectangle, Size, SizeConstraints, VerticalAlignment, Widget,
-    WidgetError, WidgetEvent, WidgetEventType, WidgetId, WidgetIdProvider, WidgetPlacement,
+    AccessibilityRole, EventObservationHandle, LayoutDirection, Point, Rectangle, Size,
+    SizeConstraints, Widget, WidgetCommand, WidgetError, WidgetEvent, WidgetEventData,
+    WidgetEventObservation, WidgetEventType, WidgetId, WidgetIdProvider, WidgetPlacement,
 };
-use std::any::Any;
 
 ///
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Button<APP_EVENT: Clone> {
     child_widget: Option<WidgetBox<APP_EVENT>>,
     core: WidgetCore<APP_EVENT>,
@@ -23,7 +25,6 @@ pub struct Button<APP_EVENT: Clone> {
     fill_brush_down: Option<PaintBrush>,
     fill_brush_up: Option<PaintBrush>,
     has_focus: bool,
-    is_disabled: bool,
     is_down: bool,
     is_hot: bool,
     padding_horizontal: f64,
@@ -53,7 +54,6 @@ impl<APP_EVENT: Clone> Button<APP_EVENT> {
                 (Color::rgb8(100, 100, 100), Color::rgb8(50, 50, 50)),
             ))),
             has_focus: false,
-            is_disabled: false,
             is_down: false,
             is_hot: false,
             padding_horizontal: 4.0,
@@ -112,20 +112,55 @@ impl<APP_EVENT: Clone> Button<APP_EVENT> {
                 .with_size(*self.core.size_constraints.minimum());
         }
     }
+
+    /// Tells the child widget whether it has a disabled ancestor and resets the button's pressed
+    /// state if it is disabled.
+    fn propagate_is_disabled(&mut self) {
+        // The button is disabled.
+        if self.core.is_disabled() {
+            self.is_down = false;
+            self.is_hot = false;
+        }
+
+        // There is a child widget.
+        if let Some(child_widget) = &self.child_widget {
+            child_widget
+                .borrow_mut()
+                .set_has_disabled_ancestor(self.core.is_disabled());
+        }
+    }
+
+    /// Tells the child widget the layout direction.
+    fn propagate_layout_direction(&mut self) {
+        // There is a child widget.
+        if let Some(child_widget) = &self.child_widget {
+            child_widget
+                .borrow_mut()
+                .set_ancestor_layout_direction(self.core.layout_direction());
+        }
+    }
 }
 
 impl<APP_EVENT: Clone> Widget<APP_EVENT> for Button<APP_EVENT> {
+    fn accessibility_role(&self) -> AccessibilityRole {
+        AccessibilityRole::Button
+    }
+
     fn accepts_focus(&self) -> bool {
         true
     }
 
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.core
-            .add_event_observation(widget_event_type, widget_event);
+        self.core.add_event_observation(
+            event_observation_handle,
+            widget_event_type,
+            widget_event_observation,
+        );
     }
 
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
@@ -139,11 +174,19 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Button<APP_EVENT> {
         Size::new(size.width, size.height)
     }
 
-    fn event_observation(
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.core.event_observation(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.core.event_observations(widget_event_type)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.core.is_disabled()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.core.is_hidden
     }
 
     fn rectangle(&self) -> &Rectangle {
@@ -155,35 +198,34 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Button<APP_EVENT> {
         Ok(())
     }
 
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.core.remove_event_observation(widget_event_type);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        self.core.remove_event_observation(event_observation_handle);
     }
 
-    fn set_debug_rendering(&mut self, debug_rendering: bool) {
-        self.core.debug_rendering = debug_rendering;
-    }
+    fn set_ancestor_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        self.core.ancestor_layout_direction = layout_direction;
 
-    fn set_has_focus(&mut self, has_focus: bool) -> Result<(), WidgetError> {
-        self.has_focus = has_focus;
-        Ok(())
-    }
+        // Pass the layout direction on to the child widget.
+        self.propagate_layout_direction();
 
-    fn set_horizontal_alignment(
-        &mut self,
-        _horizontal_alignment: HorizontalAlignment,
-    ) -> Result<(), WidgetError> {
-        // TODO
-        println!("`Button::set_horizontal_alignment()`: TODO");
+        // Layout the child widget.
+        self.layout_child_widget();
+    }
 
-        Ok(())
+    fn set_debug_rendering(&mut self, debug_rendering: bool) {
+        self.core.debug_rendering = debug_rendering;
     }
 
-    fn set_is_disabled(&mut self, is_disabled: bool) {
-        self.is_disabled = is_disabled;
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+        self.core.has_disabled_ancestor = has_disabled_ancestor;
+
+        // Pass the disabled state on to the child widget.
+        self.propagate_is_disabled();
     }
 
-    fn set_is_hidden(&mut self, is_hidden: bool) {
-        self.core.is_hidden = is_hidden;
+    fn set_has_focus(&mut self, has_focus: bool) -> Result<(), WidgetError> {
+        self.has_focus = has_focus;
+        Ok(())
     }
 
     fn set_origin(&mut self, origin: Point) {
@@ -193,22 +235,12 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Button<APP_EVENT> {
         self.layout_child_widget();
     }
 
-    fn set_vertical_alignment(
-        &mut self,
-        _vertical_alignment: VerticalAlignment,
-    ) -> Result<(), WidgetError> {
-        // TODO
-        println!("`Button::set_vertical_alignment()`: TODO");
-
-        Ok(())
-    }
-
     fn widget_id(&self) -> &WidgetId {
         &self.core.widget_id
     }
 }
 
-impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> PietWidget<APP_EVENT> for Button<APP_EVENT> {
     fn add_child(
         &mut self,
         _widget_placement: Option<WidgetPlacement>,
@@ -218,37 +250,110 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
 
         self.child_widget = Some(child_widget.clone());
 
+        // Pass the disabled state on to the child widget.
+        self.propagate_is_disabled();
+
+        // Pass the layout direction on to the child widget.
+        self.propagate_layout_direction();
+
         // Layout the child widget.
         self.layout_child_widget();
 
         Ok(())
     }
 
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>> {
+        Box::new(self.clone())
+    }
+
+    fn handle_command(
+        &mut self,
+        widget_command: WidgetCommand,
+        shared_state: &mut PietSharedState,
+        _widget_id_provider: &mut WidgetIdProvider,
+        _commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        match widget_command {
+            WidgetCommand::SetFill(fill) => {
+                self.set_fill(fill.map(|color| PaintBrush::Color(piet_color(color))))?;
+            }
+            WidgetCommand::SetFont(font) => {
+                self.set_font(font.into(), shared_state)?;
+            }
+            WidgetCommand::SetHorizontalAlignment(_horizontal_alignment) => {
+                // TODO
+                println!("`Button::handle_command(SetHorizontalAlignment)`: TODO");
+            }
+            WidgetCommand::SetIsDisabled(is_disabled) => {
+                self.core.is_disabled = is_disabled;
+
+                // Pass the disabled state on to the child widget.
+                self.propagate_is_disabled();
+            }
+            WidgetCommand::SetIsHidden(is_hidden) => {
+                self.core.is_hidden = is_hidden;
+            }
+            WidgetCommand::SetLayoutDirection(layout_direction) => {
+                self.core.layout_direction = layout_direction;
+
+                // Pass the layout direction on to the child widget.
+                self.propagate_layout_direction();
+
+                // Layout the child widget.
+                self.layout_child_widget();
+            }
+            WidgetCommand::SetStroke(stroke) => {
+                self.set_stroke(stroke.map(Stroke::from))?;
+            }
+            WidgetCommand::SetValue(value) => {
+                return Err(WidgetError::NotHandled {
+                    widget_id: self.core.widget_id,
+                    description: format!("`handle_command(SetValue({:?}))`", value),
+                });
+            }
+            WidgetCommand::SetVerticalAlignment(_vertical_alignment) => {
+                // TODO
+                println!("`Button::handle_command(SetVerticalAlignment)`: TODO");
+            }
+        }
+
+        Ok(())
+    }
+
     fn handle_event(
         &mut self,
         event: &Event,
-        _shared_state: &mut PietSharedState,
+        shared_state: &mut PietSharedState,
         _widget_id_provider: &mut WidgetIdProvider,
         widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
     ) {
+        // The button is disabled.
+        if self.core.is_disabled() {
+            return;
+        }
+
         match event {
             Event::KeyDown(key_event) => {
                 if key_event.key == KbKey::Enter {
                     // `Enter` on a (focused) button is like a click.
-                    if let Some(widget_event) =
-                        self.core.event_observation(&WidgetEventType::Clicked)
+                    let widget_event_data = WidgetEventData {
+                        click_count: 1,
+                        modifiers: modifiers(key_event.mods),
+                        ..WidgetEventData::new(self.core.widget_id)
+                    };
+
+                    // Iterate over the widget event observations.
+                    for widget_event_observation in
+                        self.core.event_observations(&WidgetEventType::Clicked)
                     {
-                        widget_events.push(widget_event.clone());
+                        widget_events
+                            .push(widget_event_observation.widget_event(&widget_event_data));
                     }
                 }
             }
-            Event::MouseDown(mouse_event) => {
+            Event::MouseDown(mouse_event) if mouse_event.button.is_left() => {
                 // The mouse is down within this button.
-                if self
-                    .core
-                    .rectangle
-                    .contains(mouse_event.pos.x, mouse_event.pos.y)
-                {
+                if shared_state.is_under_pointer(self.core.widget_id) {
                     // This widget was not focused.
                     if !self.has_focus {
                         // Give it focus.
@@ -260,6 +365,9 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
 
                     self.is_down = true;
                     self.is_hot = true;
+
+                    // Get the following pointer events, even outside of this button.
+                    widget_events.push(WidgetEvent::CapturedPointer(self.core.widget_id));
                 }
                 // The mouse is down outside of this button.
                 else {
@@ -274,14 +382,9 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
                     self.is_hot = false;
                 }
             }
-            Event::MouseMove(mouse_event) => {
+            Event::MouseMove(_) => {
                 // The mouse moved inside of this button.
-                if self.is_hot
-                    && self
-                        .core
-                        .rectangle
-                        .contains(mouse_event.pos.x, mouse_event.pos.y)
-                {
+                if self.is_hot && shared_state.is_under_pointer(self.core.widget_id) {
                     self.is_down = true;
                 }
                 // The mouse moved outside of this button.
@@ -289,18 +392,25 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
                     self.is_down = false;
                 }
             }
-            Event::MouseUp(mouse_event) => {
-                if self.is_hot
-                    && self
-                        .core
-                        .rectangle
-                        .contains(mouse_event.pos.x, mouse_event.pos.y)
-                {
-                    // There is a widget event observation.
-                    if let Some(widget_event) =
-                        self.core.event_observation(&WidgetEventType::Clicked)
+            Event::MouseUp(mouse_event) if mouse_event.button.is_left() => {
+                // This button captured the pointer.
+                if self.is_hot {
+                    widget_events.push(WidgetEvent::ReleasedPointer(self.core.widget_id));
+                }
+
+                if self.is_hot && shared_state.is_under_pointer(self.core.widget_id) {
+                    let widget_event_data = WidgetEventData {
+                        click_count: mouse_event.count,
+                        modifiers: modifiers(mouse_event.mods),
+                        ..WidgetEventData::new(self.core.widget_id)
+                    };
+
+                    // Iterate over the widget event observations.
+                    for widget_event_observation in
+                        self.core.event_observations(&WidgetEventType::Clicked)
                     {
-                        widget_events.push(widget_event.clone());
+                        widget_events
+                            .push(widget_event_observation.widget_event(&widget_event_data));
                     }
                 }
 
@@ -311,7 +421,12 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
         }
     }
 
-    fn paint(&self, piet: &mut Piet, region: &Region) -> Result<(), piet::Error> {
+    fn paint(
+        &self,
+        piet: &mut Piet,
+        shared_state: &PietSharedState,
+        region: &Region,
+    ) -> Result<(), piet::Error> {
         // The button widget is hidden.
         if self.core.is_hidden {
             return Ok(());
@@ -319,12 +434,26 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
 
         // Paint the button itself.
         {
+            let stroke = if self.has_focus {
+                &self.stroke_focused
+            } else {
+                &self.stroke
+            };
+            let stroke_width = stroke
+                .as_ref()
+                .map(|stroke| stroke.stroke_width)
+                .unwrap_or(1.0);
+
+            // Snap the shape to device pixels to keep the stroke sharp.
             let button_shape = RoundedRect::from_rect(
-                kurbo::Rect::new(
-                    self.core.rectangle.x0,
-                    self.core.rectangle.y0,
-                    self.core.rectangle.x1,
-                    self.core.rectangle.y1,
+                shared_state.snap_stroke_rect(
+                    kurbo::Rect::new(
+                        self.core.rectangle.x0,
+                        self.core.rectangle.y0,
+                        self.core.rectangle.x1,
+                        self.core.rectangle.y1,
+                    ),
+                    stroke_width,
                 ),
                 self.corner_radius,
             );
@@ -337,27 +466,20 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
                 piet.fill(button_shape, brush);
             }
 
-            // Stroke.
-            // The button is focused.
-            if self.has_focus {
-                // There is a focuse stroke brush.
-                if let Some(stroke) = &self.stroke_focused {
-                    piet.stroke(button_shape, &stroke.stroke_brush, stroke.stroke_width);
-                }
-            }
-            // The button is not focused.
-            else {
-                // There is a stroke brush.
-                if let Some(stroke) = &self.stroke {
-                    piet.stroke(button_shape, &stroke.stroke_brush, stroke.stroke_width);
-                }
+            // Stroke, depending on whether the button is focused.
+            if let Some(stroke) = stroke {
+                piet.stroke(
+                    button_shape,
+                    &stroke.stroke_brush,
+                    shared_state.snap_stroke_width(stroke.stroke_width),
+                );
             }
         }
 
         // There is a child widget.
         if let Some(child_widget_rc) = &self.child_widget {
             // Paint the child widget.
-            child_widget_rc.borrow().paint(piet, region)?;
+            child_widget_rc.borrow().paint(piet, shared_state, region)?;
         }
 
         // Render debug hints.
@@ -398,25 +520,22 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Button<APP_EVENT> {
         self.stroke = stroke;
         Ok(())
     }
-
-    fn set_value(
-        &mut self,
-        _value: Box<dyn Any>,
-        _shared_state: &mut PietSharedState,
-        _widget_id_provider: &mut WidgetIdProvider,
-        _commands: &mut Vec<Command<APP_EVENT>>,
-    ) -> Result<(), WidgetError> {
-        // TODO
-        println!("`Button::set_value()`: TODO");
-
-        Ok(())
-    }
 }
 
 // =================================================================================================
 
 #[cfg(test)]
 mod tests {
+    use crate::shared_state::PietSharedState;
+    use crate::stroke::Stroke;
+    use crate::widget::{Button, Placeholder};
+    use crate::{Event, PietWidget};
+    use druid_shell::{KbKey, KeyEvent};
+    use guiver::{
+        Size, Widget, WidgetCommand, WidgetEventObservation, WidgetEventType, WidgetIdProvider,
+    };
+    use std::cell::RefCell;
+    use std::rc::Rc;
 
     #[test]
     fn test_apply_size_constraints() {
@@ -434,6 +553,106 @@ mod tests {
 
     #[test]
     fn test_handle_event() {
-        // TODO
+        let mut shared_state = PietSharedState::new();
+        let mut widget_id_provider = WidgetIdProvider::new();
+        let mut commands = vec![];
+        let mut widget_events = vec![];
+
+        let child_widget = Placeholder::new(1, Stroke::default(), Size::new(10.0, 10.0));
+        let child_widget_box: Rc<RefCell<Box<dyn PietWidget<()>>>> =
+            Rc::new(RefCell::new(Box::new(child_widget)));
+        let mut button: Button<()> = Button::new(
+            0,
+            Stroke::default(),
+            child_widget_box.clone(),
+            None,
+            None,
+            None,
+        );
+        button.add_event_observation(
+            1,
+            WidgetEventType::Clicked,
+            WidgetEventObservation::AppEvent(()),
+        );
+
+        let enter = Event::KeyDown(KeyEvent {
+            key: KbKey::Enter,
+            ..Default::default()
+        });
+
+        // `Enter` clicks the button.
+        button.handle_event(
+            &enter,
+            &mut shared_state,
+            &mut widget_id_provider,
+            &mut widget_events,
+        );
+        assert_eq!(widget_events.len(), 1);
+
+        // A disabled button ignores input and disables its child widget.
+        button
+            .handle_command(
+                WidgetCommand::SetIsDisabled(true),
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut commands,
+            )
+            .unwrap();
+        assert!(child_widget_box.borrow().is_disabled());
+        button.handle_event(
+            &enter,
+            &mut shared_state,
+            &mut widget_id_provider,
+            &mut widget_events,
+        );
+        assert_eq!(widget_events.len(), 1);
+
+        // A disabled ancestor disables the button, but the button keeps its own setting.
+        button.set_has_disabled_ancestor(true);
+        button
+            .handle_command(
+                WidgetCommand::SetIsDisabled(false),
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut commands,
+            )
+            .unwrap();
+        assert!(button.is_disabled());
+        assert!(child_widget_box.borrow().is_disabled());
+
+        button.set_has_disabled_ancestor(false);
+        assert!(!button.is_disabled());
+        assert!(!child_widget_box.borrow().is_disabled());
+        button.handle_event(
+            &enter,
+            &mut shared_state,
+            &mut widget_id_provider,
+            &mut widget_events,
+        );
+        assert_eq!(widget_events.len(), 2);
+
+        // Every observation of the widget event type produces an event.
+        button.add_event_observation(
+            2,
+            WidgetEventType::Clicked,
+            WidgetEventObservation::AppEvent(()),
+        );
+        button.handle_event(
+            &enter,
+            &mut shared_state,
+            &mut widget_id_provider,
+            &mut widget_events,
+        );
+        assert_eq!(widget_events.len(), 4);
+
+        // A removed observation produces no more events.
+        button.remove_event_observation(1);
+        button.handle_event(
+            &enter,
+            &mut shared_state,
+            &mut widget_id_provider,
+            &mut widget_events,
+        );
+        assert_eq!(widget_events.len(), 5);
     }
 }
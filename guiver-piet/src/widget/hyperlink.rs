@@ -1,17 +1,20 @@
+use crate::event::modifiers;
 use crate::font::Font;
 use crate::shared_state::PietSharedState;
 use crate::stroke::Stroke;
 use crate::widget::{Text, WidgetError};
-use crate::{Event, Piet, PietWidget};
+use crate::{Command, Event, Piet, PietWidget};
 use druid_shell::piet::{Error, PietText};
 use druid_shell::Region;
 use guiver::{
-    Point, Rectangle, Size, SizeConstraints, Widget, WidgetEvent, WidgetEventType, WidgetId,
-    WidgetIdProvider,
+    AccessibilityRole, EventObservationHandle, LayoutDirection, Point, Rectangle, Size,
+    SizeConstraints, Widget, WidgetCommand, WidgetEvent, WidgetEventData, WidgetEventObservation,
+    WidgetEventType, WidgetId, WidgetIdProvider, WidgetValueKind,
 };
 use std::borrow::BorrowMut;
 
 ///
+#[derive(Clone)]
 pub struct Hyperlink<APP_EVENT: Clone> {
     is_being_clicked: bool,
     font_is_being_clicked: Font,
@@ -26,7 +29,7 @@ fn adjust_font(font: &mut Font) {
     font.has_underline = true;
 }
 
-impl<APP_EVENT: Clone> Hyperlink<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> Hyperlink<APP_EVENT> {
     ///
     pub fn new(
         widget_id: WidgetId,
@@ -101,45 +104,71 @@ impl<APP_EVENT: Clone> Hyperlink<APP_EVENT> {
 }
 
 impl<APP_EVENT: Clone> Widget<APP_EVENT> for Hyperlink<APP_EVENT> {
+    fn accessibility_name(&self) -> Option<String> {
+        self.text_widget.accessibility_name()
+    }
+
+    fn accessibility_role(&self) -> AccessibilityRole {
+        AccessibilityRole::Link
+    }
+
+    fn accepted_value_kinds(&self) -> &'static [WidgetValueKind] {
+        self.text_widget.accepted_value_kinds()
+    }
+
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.text_widget
-            .add_event_observation(widget_event_type, widget_event);
+        self.text_widget.add_event_observation(
+            event_observation_handle,
+            widget_event_type,
+            widget_event_observation,
+        );
     }
 
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
         self.text_widget.apply_size_constraints(size_constraints)
     }
 
-    fn event_observation(
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.text_widget.event_observation(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.text_widget.event_observations(widget_event_type)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.text_widget.is_disabled()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.text_widget.is_hidden()
     }
 
     fn rectangle(&self) -> &Rectangle {
         self.text_widget.rectangle()
     }
 
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.text_widget.remove_event_observation(widget_event_type);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        self.text_widget
+            .remove_event_observation(event_observation_handle);
     }
 
-    fn set_debug_rendering(&mut self, debug_rendering: bool) {
-        self.text_widget.set_debug_rendering(debug_rendering);
+    fn set_ancestor_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        self.text_widget
+            .set_ancestor_layout_direction(layout_direction);
     }
 
-    fn set_is_disabled(&mut self, _is_disabled: bool) {
-        // TODO
-        println!("`Hyperlink::set_is_disabled()`: TODO");
+    fn set_debug_rendering(&mut self, debug_rendering: bool) {
+        self.text_widget.set_debug_rendering(debug_rendering);
     }
 
-    fn set_is_hidden(&mut self, is_hidden: bool) {
-        self.text_widget.set_is_hidden(is_hidden);
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+        self.text_widget
+            .set_has_disabled_ancestor(has_disabled_ancestor);
     }
 
     fn set_origin(&mut self, origin: Point) {
@@ -151,7 +180,22 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Hyperlink<APP_EVENT> {
     }
 }
 
-impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Hyperlink<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> PietWidget<APP_EVENT> for Hyperlink<APP_EVENT> {
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>> {
+        Box::new(self.clone())
+    }
+
+    fn handle_command(
+        &mut self,
+        widget_command: WidgetCommand,
+        shared_state: &mut PietSharedState,
+        widget_id_provider: &mut WidgetIdProvider,
+        commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        self.text_widget
+            .handle_command(widget_command, shared_state, widget_id_provider, commands)
+    }
+
     fn handle_event(
         &mut self,
         event: &Event,
@@ -159,14 +203,15 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Hyperlink<APP_EVENT> {
         _widget_id_provider: &mut WidgetIdProvider,
         widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
     ) {
+        // The hyperlink widget is disabled.
+        if self.text_widget.is_disabled() {
+            return;
+        }
+
         match event {
-            Event::MouseDown(mouse_event) => {
+            Event::MouseDown(mouse_event) if mouse_event.button.is_left() => {
                 // The click is outside of the text.
-                if !self
-                    .text_widget
-                    .rectangle()
-                    .contains(mouse_event.pos.x, mouse_event.pos.y)
-                {
+                if !shared_state.is_under_pointer(*self.text_widget.widget_id()) {
                     self.set_is_being_clicked(shared_state, false);
                     return;
                 }
@@ -174,21 +219,31 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Hyperlink<APP_EVENT> {
                 // The hyperlink is being clicked.
                 self.set_is_being_clicked(shared_state, true);
 
-                // There is a widget event observation.
-                if let Some(widget_event) = self
+                // Get the following pointer events, even outside of this hyperlink.
+                widget_events.push(WidgetEvent::CapturedPointer(*self.text_widget.widget_id()));
+
+                let widget_event_data = WidgetEventData {
+                    click_count: mouse_event.count,
+                    modifiers: modifiers(mouse_event.mods),
+                    ..WidgetEventData::new(*self.text_widget.widget_id())
+                };
+
+                // Iterate over the widget event observations.
+                for widget_event_observation in self
                     .text_widget
-                    .event_observation(&WidgetEventType::Clicked)
+                    .event_observations(&WidgetEventType::Clicked)
                 {
-                    widget_events.push(widget_event.clone());
+                    widget_events.push(widget_event_observation.widget_event(&widget_event_data));
                 }
             }
-            Event::MouseUp(mouse_event) => {
+            Event::MouseUp(mouse_event) if mouse_event.button.is_left() => {
+                // This hyperlink captured the pointer.
+                if self.is_being_clicked {
+                    widget_events.push(WidgetEvent::ReleasedPointer(*self.text_widget.widget_id()));
+                }
+
                 // The click is outside of the text.
-                if !self
-                    .text_widget
-                    .rectangle()
-                    .contains(mouse_event.pos.x, mouse_event.pos.y)
-                {
+                if !shared_state.is_under_pointer(*self.text_widget.widget_id()) {
                     // The hyperlink is not being clicked.
                     self.set_is_being_clicked(shared_state, false);
                     return;
@@ -197,12 +252,18 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Hyperlink<APP_EVENT> {
                 if self.is_being_clicked {
                     self.set_was_visited(shared_state);
 
-                    // There is a widget event observation.
-                    if let Some(widget_event) = self
+                    let widget_event_data = WidgetEventData {
+                        modifiers: modifiers(mouse_event.mods),
+                        ..WidgetEventData::new(*self.text_widget.widget_id())
+                    };
+
+                    // Iterate over the widget event observations.
+                    for widget_event_observation in self
                         .text_widget
-                        .event_observation(&WidgetEventType::Submitted)
+                        .event_observations(&WidgetEventType::Submitted)
                     {
-                        widget_events.push(widget_event.clone());
+                        widget_events
+                            .push(widget_event_observation.widget_event(&widget_event_data));
                     }
                 } else {
                     // The hyperlink is not being clicked.
@@ -213,8 +274,13 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Hyperlink<APP_EVENT> {
         }
     }
 
-    fn paint(&self, piet: &mut Piet, region: &Region) -> Result<(), Error> {
-        self.text_widget.paint(piet, region)
+    fn paint(
+        &self,
+        piet: &mut Piet,
+        shared_state: &PietSharedState,
+        region: &Region,
+    ) -> Result<(), Error> {
+        self.text_widget.paint(piet, shared_state, region)
     }
 
     fn set_stroke(&mut self, stroke: Option<Stroke>) -> Result<(), WidgetError> {
@@ -2,11 +2,12 @@ use crate::shared_state::PietSharedState;
 use crate::stroke::Stroke;
 use crate::widget::widget_core::WidgetCore;
 use crate::widget_manager::WidgetBox;
-use crate::{Event, PietWidget};
+use crate::{Command, Event, PietWidget};
 use druid_shell::piet::Piet;
 use druid_shell::{kurbo, piet, Region};
 use guiver::{
-    HorizontalAlignment, Point, Rectangle, Size, SizeConstraints, Widget, WidgetError, WidgetEvent,
+    EventObservationHandle, HorizontalAlignment, LayoutDirection, Point, Rectangle, Size,
+    SizeConstraints, Widget, WidgetCommand, WidgetError, WidgetEvent, WidgetEventObservation,
     WidgetEventType, WidgetId, WidgetIdProvider, WidgetPlacement,
 };
 use piet::RenderContext;
@@ -14,6 +15,7 @@ use std::borrow::{Borrow, BorrowMut};
 use std::cell::RefCell;
 
 /// A layout widget that positions its child widgets in a vertical column.
+#[derive(Clone)]
 pub struct Column<APP_EVENT: Clone> {
     child_widgets: Vec<WidgetBox<APP_EVENT>>,
     core: WidgetCore<APP_EVENT>,
@@ -97,6 +99,9 @@ impl<APP_EVENT: Clone> Column<APP_EVENT> {
             (self.core.rectangle.height() - child_and_spacing_size_sum.height).max(0.0);
 
         let mut child_y = self.core.rectangle.origin().y;
+        let horizontal_alignment = self
+            .horizontal_alignment
+            .resolved(self.core.layout_direction());
 
         // Second pass over the child widgets.
         for child_widget in &mut self.child_widgets {
@@ -127,14 +132,17 @@ impl<APP_EVENT: Clone> Column<APP_EVENT> {
                 expanded_child_size
             };
 
-            // Determine the child widget's horizontal position.
-            let child_x = match self.horizontal_alignment {
+            // Determine the child widget's horizontal position. `Start` and `End` are resolved to
+            // `Left` and `Right` already.
+            let child_x = match horizontal_alignment {
                 HorizontalAlignment::Center => {
                     self.core.rectangle.origin().x
                         + 0.5 * (self.core.rectangle.size().width - child_size.width).max(0.0)
                 }
-                HorizontalAlignment::Left => self.core.rectangle.origin().x,
-                HorizontalAlignment::Right => {
+                HorizontalAlignment::Left | HorizontalAlignment::Start => {
+                    self.core.rectangle.origin().x
+                }
+                HorizontalAlignment::Right | HorizontalAlignment::End => {
                     self.core.rectangle.origin().x + self.core.rectangle.size().width
                         - child_size.width
                 }
@@ -148,16 +156,40 @@ impl<APP_EVENT: Clone> Column<APP_EVENT> {
             child_y += child_size.height + self.spacing;
         }
     }
+
+    /// Tells the child widgets whether they have a disabled ancestor.
+    fn propagate_is_disabled(&mut self) {
+        let is_disabled = self.core.is_disabled();
+
+        // Iterate over the child widgets.
+        for child_widget in &self.child_widgets {
+            RefCell::borrow_mut(child_widget).set_has_disabled_ancestor(is_disabled);
+        }
+    }
+
+    /// Tells the child widgets the layout direction.
+    fn propagate_layout_direction(&mut self) {
+        let layout_direction = self.core.layout_direction();
+
+        // Iterate over the child widgets.
+        for child_widget in &self.child_widgets {
+            RefCell::borrow_mut(child_widget).set_ancestor_layout_direction(layout_direction);
+        }
+    }
 }
 
 impl<APP_EVENT: Clone> Widget<APP_EVENT> for Column<APP_EVENT> {
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.core
-            .add_event_observation(widget_event_type, widget_event);
+        self.core.add_event_observation(
+            event_observation_handle,
+            widget_event_type,
+            widget_event_observation,
+        );
     }
 
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
@@ -171,11 +203,19 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Column<APP_EVENT> {
         Size::new(size.width, size.height)
     }
 
-    fn event_observation(
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.core.event_observation(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.core.event_observations(widget_event_type)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.core.is_disabled()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.core.is_hidden
     }
 
     fn rectangle(&self) -> &Rectangle {
@@ -204,33 +244,29 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Column<APP_EVENT> {
         Ok(())
     }
 
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.core.remove_event_observation(widget_event_type);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        self.core.remove_event_observation(event_observation_handle);
     }
 
-    fn set_debug_rendering(&mut self, debug_rendering: bool) {
-        self.core.debug_rendering = debug_rendering;
-    }
+    fn set_ancestor_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        self.core.ancestor_layout_direction = layout_direction;
 
-    fn set_horizontal_alignment(
-        &mut self,
-        horizontal_alignment: HorizontalAlignment,
-    ) -> Result<(), WidgetError> {
-        self.horizontal_alignment = horizontal_alignment;
+        // Pass the layout direction on to the child widgets.
+        self.propagate_layout_direction();
 
         // Layout the child widgets.
         self.layout_child_widgets();
-
-        Ok(())
     }
 
-    fn set_is_disabled(&mut self, _is_disabled: bool) {
-        // TODO
-        println!("`Column::set_is_disabled()`: TODO");
+    fn set_debug_rendering(&mut self, debug_rendering: bool) {
+        self.core.debug_rendering = debug_rendering;
     }
 
-    fn set_is_hidden(&mut self, is_hidden: bool) {
-        self.core.is_hidden = is_hidden;
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+        self.core.has_disabled_ancestor = has_disabled_ancestor;
+
+        // Pass the disabled state on to the child widgets.
+        self.propagate_is_disabled();
     }
 
     fn set_origin(&mut self, origin: Point) {
@@ -245,7 +281,7 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Column<APP_EVENT> {
     }
 }
 
-impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Column<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> PietWidget<APP_EVENT> for Column<APP_EVENT> {
     fn add_child(
         &mut self,
         widget_placement: Option<WidgetPlacement>,
@@ -273,6 +309,12 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Column<APP_EVENT> {
         // No widget placement is given.
         else {
             self.child_widgets.push(child_widget.clone());
+
+            // Pass the disabled state on to the child widget.
+            self.propagate_is_disabled();
+
+            // Pass the layout direction on to the child widget.
+            self.propagate_layout_direction();
         }
 
         // Layout the child widgets.
@@ -281,6 +323,53 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Column<APP_EVENT> {
         Ok(())
     }
 
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>> {
+        Box::new(self.clone())
+    }
+
+    fn handle_command(
+        &mut self,
+        widget_command: WidgetCommand,
+        _shared_state: &mut PietSharedState,
+        _widget_id_provider: &mut WidgetIdProvider,
+        _commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        match widget_command {
+            WidgetCommand::SetHorizontalAlignment(horizontal_alignment) => {
+                self.horizontal_alignment = horizontal_alignment;
+
+                // Layout the child widgets.
+                self.layout_child_widgets();
+            }
+            WidgetCommand::SetIsDisabled(is_disabled) => {
+                self.core.is_disabled = is_disabled;
+
+                // Pass the disabled state on to the child widgets.
+                self.propagate_is_disabled();
+            }
+            WidgetCommand::SetIsHidden(is_hidden) => {
+                self.core.is_hidden = is_hidden;
+            }
+            WidgetCommand::SetLayoutDirection(layout_direction) => {
+                self.core.layout_direction = layout_direction;
+
+                // Pass the layout direction on to the child widgets.
+                self.propagate_layout_direction();
+
+                // Layout the child widgets.
+                self.layout_child_widgets();
+            }
+            widget_command => {
+                return Err(WidgetError::NotHandled {
+                    widget_id: self.core.widget_id,
+                    description: format!("`handle_command({:?})`", widget_command),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     fn handle_event(
         &mut self,
         event: &Event,
@@ -300,7 +389,12 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Column<APP_EVENT> {
         }
     }
 
-    fn paint(&self, piet: &mut Piet, region: &Region) -> Result<(), piet::Error> {
+    fn paint(
+        &self,
+        piet: &mut Piet,
+        shared_state: &PietSharedState,
+        region: &Region,
+    ) -> Result<(), piet::Error> {
         // The row widget is hidden.
         if self.core.is_hidden {
             return Ok(());
@@ -309,7 +403,7 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Column<APP_EVENT> {
         // Iterate over the child widgets.
         for child_widget in &self.child_widgets {
             // Paint the current child widget.
-            RefCell::borrow(child_widget).paint(piet, region)?;
+            RefCell::borrow(child_widget).paint(piet, shared_state, region)?;
         }
 
         // Render debug hints.
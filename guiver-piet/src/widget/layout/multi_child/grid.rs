@@ -3,12 +3,13 @@ use crate::stroke::Stroke;
 use crate::widget::widget_core::WidgetCore;
 use crate::widget::WidgetError;
 use crate::widget_manager::WidgetBox;
-use crate::{Event, Piet, PietWidget};
+use crate::{Command, Event, Piet, PietWidget};
 use druid_shell::piet::{Error, RenderContext};
 use druid_shell::{kurbo, Region};
 use guiver::{
-    GridColumnProperties, GridRowProperties, Point, Rectangle, Size, SizeConstraints, Widget,
-    WidgetEvent, WidgetEventType, WidgetId, WidgetIdProvider, WidgetPlacement,
+    EventObservationHandle, GridColumnProperties, GridRowProperties, LayoutDirection, Point,
+    Rectangle, Size, SizeConstraints, Widget, WidgetCommand, WidgetEvent, WidgetEventObservation,
+    WidgetEventType, WidgetId, WidgetIdProvider, WidgetPlacement,
 };
 use std::borrow::BorrowMut;
 use std::cell::RefCell;
@@ -17,6 +18,7 @@ use std::collections::{HashMap, HashSet};
 // =================================================================================================
 
 /// A layout widget that positions its child widgets in a 2-dimensional grid.
+#[derive(Clone)]
 pub struct Grid<APP_EVENT: Clone> {
     child_widget_id_per_cell: HashMap<(usize, usize), WidgetId>,
     child_widget_ids_per_column: HashMap<usize, HashSet<WidgetId>>,
@@ -236,6 +238,7 @@ impl<APP_EVENT: Clone> Grid<APP_EVENT> {
         {
             // Iterate over the grid columns.
             {
+                let is_right_to_left = self.core.layout_direction() == LayoutDirection::RightToLeft;
                 let mut child_x = self.core.rectangle.origin().x;
 
                 // Iterate over the grid's column indices.
@@ -257,13 +260,22 @@ impl<APP_EVENT: Clone> Grid<APP_EVENT> {
                             .get(child_widget_id_in_column)
                             .unwrap();
 
+                        let child_rectangle = *child_widget_in_column.borrow().rectangle();
+
+                        // Right-to-left grids mirror the columns, so that the first column is on
+                        // the right and the child widgets are at their column's right edge.
+                        let mirrored_child_x = if is_right_to_left {
+                            self.core.rectangle.x0 + self.core.rectangle.x1
+                                - child_x
+                                - child_rectangle.width()
+                        } else {
+                            child_x
+                        };
+
                         // Set the child widget's x position.
                         RefCell::borrow_mut(child_widget_in_column)
                             .borrow_mut()
-                            .set_origin(Point::new(
-                                child_x,
-                                child_widget_in_column.borrow().rectangle().y0,
-                            ));
+                            .set_origin(Point::new(mirrored_child_x, child_rectangle.y0));
                     }
 
                     // Add the column width.
@@ -324,6 +336,26 @@ impl<APP_EVENT: Clone> Grid<APP_EVENT> {
             .unwrap_or(&self.default_row_properties)
     }
 
+    /// Tells the child widgets whether they have a disabled ancestor.
+    fn propagate_is_disabled(&mut self) {
+        let is_disabled = self.core.is_disabled();
+
+        // Iterate over the child widgets.
+        for child_widget in self.child_widget_per_id.values() {
+            RefCell::borrow_mut(child_widget).set_has_disabled_ancestor(is_disabled);
+        }
+    }
+
+    /// Tells the child widgets the layout direction.
+    fn propagate_layout_direction(&mut self) {
+        let layout_direction = self.core.layout_direction();
+
+        // Iterate over the child widgets.
+        for child_widget in self.child_widget_per_id.values() {
+            RefCell::borrow_mut(child_widget).set_ancestor_layout_direction(layout_direction);
+        }
+    }
+
     /// Removes the child widget with the given ID from the grid.
     fn remove_child_widget(&mut self, child_widget_id: &WidgetId) {
         // Remove the child widget.
@@ -368,11 +400,15 @@ impl<APP_EVENT: Clone> Grid<APP_EVENT> {
 impl<APP_EVENT: Clone> Widget<APP_EVENT> for Grid<APP_EVENT> {
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.core
-            .add_event_observation(widget_event_type, widget_event);
+        self.core.add_event_observation(
+            event_observation_handle,
+            widget_event_type,
+            widget_event_observation,
+        );
     }
 
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
@@ -386,11 +422,19 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Grid<APP_EVENT> {
         Size::new(size.width, size.height)
     }
 
-    fn event_observation(
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.core.event_observation(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.core.event_observations(widget_event_type)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.core.is_disabled()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.core.is_hidden
     }
 
     fn rectangle(&self) -> &Rectangle {
@@ -412,21 +456,29 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Grid<APP_EVENT> {
         Ok(())
     }
 
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.core.remove_event_observation(widget_event_type);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        self.core.remove_event_observation(event_observation_handle);
+    }
+
+    fn set_ancestor_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        self.core.ancestor_layout_direction = layout_direction;
+
+        // Pass the layout direction on to the child widgets.
+        self.propagate_layout_direction();
+
+        // Layout the child widgets.
+        self.layout_child_widgets();
     }
 
     fn set_debug_rendering(&mut self, debug_rendering: bool) {
         self.core.debug_rendering = debug_rendering;
     }
 
-    fn set_is_disabled(&mut self, _is_disabled: bool) {
-        // TODO
-        println!("`Grid::set_is_disabled()`: TODO");
-    }
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+        self.core.has_disabled_ancestor = has_disabled_ancestor;
 
-    fn set_is_hidden(&mut self, is_hidden: bool) {
-        self.core.is_hidden = is_hidden;
+        // Pass the disabled state on to the child widgets.
+        self.propagate_is_disabled();
     }
 
     fn set_origin(&mut self, origin: Point) {
@@ -441,7 +493,7 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Grid<APP_EVENT> {
     }
 }
 
-impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Grid<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> PietWidget<APP_EVENT> for Grid<APP_EVENT> {
     fn add_child(
         &mut self,
         widget_placement: Option<WidgetPlacement>,
@@ -455,6 +507,12 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Grid<APP_EVENT> {
         {
             self.add_child_widget(child_widget.clone(), column_index, row_index);
 
+            // Pass the disabled state on to the child widget.
+            self.propagate_is_disabled();
+
+            // Pass the layout direction on to the child widget.
+            self.propagate_layout_direction();
+
             return Ok(());
         }
 
@@ -464,6 +522,47 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Grid<APP_EVENT> {
         })
     }
 
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>> {
+        Box::new(self.clone())
+    }
+
+    fn handle_command(
+        &mut self,
+        widget_command: WidgetCommand,
+        _shared_state: &mut PietSharedState,
+        _widget_id_provider: &mut WidgetIdProvider,
+        _commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        match widget_command {
+            WidgetCommand::SetIsDisabled(is_disabled) => {
+                self.core.is_disabled = is_disabled;
+
+                // Pass the disabled state on to the child widgets.
+                self.propagate_is_disabled();
+            }
+            WidgetCommand::SetIsHidden(is_hidden) => {
+                self.core.is_hidden = is_hidden;
+            }
+            WidgetCommand::SetLayoutDirection(layout_direction) => {
+                self.core.layout_direction = layout_direction;
+
+                // Pass the layout direction on to the child widgets.
+                self.propagate_layout_direction();
+
+                // Layout the child widgets.
+                self.layout_child_widgets();
+            }
+            widget_command => {
+                return Err(WidgetError::NotHandled {
+                    widget_id: self.core.widget_id,
+                    description: format!("`handle_command({:?})`", widget_command),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     fn handle_event(
         &mut self,
         event: &Event,
@@ -483,7 +582,12 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Grid<APP_EVENT> {
         }
     }
 
-    fn paint(&self, piet: &mut Piet, region: &Region) -> Result<(), Error> {
+    fn paint(
+        &self,
+        piet: &mut Piet,
+        shared_state: &PietSharedState,
+        region: &Region,
+    ) -> Result<(), Error> {
         // The grid widget is hidden.
         if self.core.is_hidden {
             return Ok(());
@@ -492,7 +596,7 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Grid<APP_EVENT> {
         // Iterate over the child widgets.
         for child_widget in self.child_widget_per_id.values() {
             // Paint the current child widget.
-            RefCell::borrow(child_widget).paint(piet, region)?;
+            RefCell::borrow(child_widget).paint(piet, shared_state, region)?;
         }
 
         // Render debug hints.
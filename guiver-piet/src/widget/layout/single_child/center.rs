@@ -2,15 +2,17 @@ use crate::shared_state::PietSharedState;
 use crate::stroke::Stroke;
 use crate::widget::widget_core::WidgetCore;
 use crate::widget_manager::WidgetBox;
-use crate::{Event, PietWidget};
+use crate::{Command, Event, PietWidget};
 use druid_shell::piet::{Piet, RenderContext};
 use druid_shell::{kurbo, piet, Region};
 use guiver::{
-    Point, Rectangle, Size, SizeConstraints, Widget, WidgetError, WidgetEvent, WidgetEventType,
-    WidgetId, WidgetIdProvider, WidgetPlacement,
+    EventObservationHandle, LayoutDirection, Point, Rectangle, Size, SizeConstraints, Widget,
+    WidgetCommand, WidgetError, WidgetEvent, WidgetEventObservation, WidgetEventType, WidgetId,
+    WidgetIdProvider, WidgetPlacement,
 };
 
 /// A layout widget that centers its child widget.
+#[derive(Clone)]
 pub struct Center<APP_EVENT: Clone> {
     child_widget: Option<WidgetBox<APP_EVENT>>,
     core: WidgetCore<APP_EVENT>,
@@ -54,16 +56,40 @@ impl<APP_EVENT: Clone> Center<APP_EVENT> {
                 .with_size(*self.core.size_constraints.minimum());
         }
     }
+
+    /// Tells the child widget whether it has a disabled ancestor.
+    fn propagate_is_disabled(&mut self) {
+        // There is a child widget.
+        if let Some(child_widget) = &self.child_widget {
+            child_widget
+                .borrow_mut()
+                .set_has_disabled_ancestor(self.core.is_disabled());
+        }
+    }
+
+    /// Tells the child widget the layout direction.
+    fn propagate_layout_direction(&mut self) {
+        // There is a child widget.
+        if let Some(child_widget) = &self.child_widget {
+            child_widget
+                .borrow_mut()
+                .set_ancestor_layout_direction(self.core.layout_direction());
+        }
+    }
 }
 
 impl<APP_EVENT: Clone> Widget<APP_EVENT> for Center<APP_EVENT> {
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.core
-            .add_event_observation(widget_event_type, widget_event);
+        self.core.add_event_observation(
+            event_observation_handle,
+            widget_event_type,
+            widget_event_observation,
+        );
     }
 
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
@@ -77,11 +103,19 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Center<APP_EVENT> {
         Size::new(size.width, size.height)
     }
 
-    fn event_observation(
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.core.event_observation(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.core.event_observations(widget_event_type)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.core.is_disabled()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.core.is_hidden
     }
 
     fn rectangle(&self) -> &Rectangle {
@@ -107,21 +141,29 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Center<APP_EVENT> {
         Ok(())
     }
 
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.core.remove_event_observation(widget_event_type);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        self.core.remove_event_observation(event_observation_handle);
+    }
+
+    fn set_ancestor_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        self.core.ancestor_layout_direction = layout_direction;
+
+        // Pass the layout direction on to the child widget.
+        self.propagate_layout_direction();
+
+        // Layout the child widget.
+        self.layout_child_widget();
     }
 
     fn set_debug_rendering(&mut self, debug_rendering: bool) {
         self.core.debug_rendering = debug_rendering;
     }
 
-    fn set_is_disabled(&mut self, _is_disabled: bool) {
-        // TODO
-        println!("`Center::set_is_disabled()`: TODO");
-    }
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+        self.core.has_disabled_ancestor = has_disabled_ancestor;
 
-    fn set_is_hidden(&mut self, is_hidden: bool) {
-        self.core.is_hidden = is_hidden;
+        // Pass the disabled state on to the child widget.
+        self.propagate_is_disabled();
     }
 
     fn set_origin(&mut self, origin: Point) {
@@ -136,7 +178,7 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Center<APP_EVENT> {
     }
 }
 
-impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Center<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> PietWidget<APP_EVENT> for Center<APP_EVENT> {
     fn add_child(
         &mut self,
         _widget_placement: Option<WidgetPlacement>,
@@ -144,12 +186,59 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Center<APP_EVENT> {
     ) -> Result<(), WidgetError> {
         self.child_widget = Some(child_widget);
 
+        // Pass the disabled state on to the child widget.
+        self.propagate_is_disabled();
+
+        // Pass the layout direction on to the child widget.
+        self.propagate_layout_direction();
+
         // Layout the child widget.
         self.layout_child_widget();
 
         Ok(())
     }
 
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>> {
+        Box::new(self.clone())
+    }
+
+    fn handle_command(
+        &mut self,
+        widget_command: WidgetCommand,
+        _shared_state: &mut PietSharedState,
+        _widget_id_provider: &mut WidgetIdProvider,
+        _commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        match widget_command {
+            WidgetCommand::SetIsDisabled(is_disabled) => {
+                self.core.is_disabled = is_disabled;
+
+                // Pass the disabled state on to the child widget.
+                self.propagate_is_disabled();
+            }
+            WidgetCommand::SetIsHidden(is_hidden) => {
+                self.core.is_hidden = is_hidden;
+            }
+            WidgetCommand::SetLayoutDirection(layout_direction) => {
+                self.core.layout_direction = layout_direction;
+
+                // Pass the layout direction on to the child widget.
+                self.propagate_layout_direction();
+
+                // Layout the child widget.
+                self.layout_child_widget();
+            }
+            widget_command => {
+                return Err(WidgetError::NotHandled {
+                    widget_id: self.core.widget_id,
+                    description: format!("`handle_command({:?})`", widget_command),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     fn handle_event(
         &mut self,
         event: &Event,
@@ -169,7 +258,12 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Center<APP_EVENT> {
         }
     }
 
-    fn paint(&self, piet: &mut Piet, region: &Region) -> Result<(), piet::Error> {
+    fn paint(
+        &self,
+        piet: &mut Piet,
+        shared_state: &PietSharedState,
+        region: &Region,
+    ) -> Result<(), piet::Error> {
         // The center widget is hidden.
         if self.core.is_hidden {
             return Ok(());
@@ -178,7 +272,7 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Center<APP_EVENT> {
         // There is a child widget.
         if let Some(child_widget_rc) = &self.child_widget {
             // Paint the child widget.
-            child_widget_rc.borrow().paint(piet, region)?;
+            child_widget_rc.borrow().paint(piet, shared_state, region)?;
         }
 
         // Render debug hints.
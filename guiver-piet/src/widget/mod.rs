@@ -10,15 +10,16 @@ use crate::font::Font;
 use crate::shared_state::PietSharedState;
 use crate::stroke::Stroke;
 use crate::widget_manager::WidgetBox;
-use crate::{Command, Event};
+use crate::{Command, Event, EventPhase, EventPropagation, TextFieldState};
 pub use button::Button;
 use druid_shell::piet;
 use druid_shell::piet::PaintBrush;
 use druid_shell::Region;
-use guiver::{Widget, WidgetError, WidgetEvent, WidgetIdProvider, WidgetPlacement};
+use guiver::{
+    Widget, WidgetCommand, WidgetError, WidgetEvent, WidgetIdProvider, WidgetPlacement, WidgetValue,
+};
 pub use hyperlink::Hyperlink;
 pub use placeholder::Placeholder;
-use std::any::Any;
 pub use text::Text;
 pub use text_input::TextInput;
 
@@ -40,6 +41,20 @@ pub trait PietWidget<APP_EVENT: Clone>: Widget<APP_EVENT> {
         })
     }
 
+    /// Returns a copy of the widget. Child widgets are shared with the original, not copied. The
+    /// widget manager uses this to restore widgets if a batch of commands fails.
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>>;
+
+    /// Ask the widget to handle the given widget command, possibly creating `Command`s. Widget
+    /// commands the widget does not support result in `WidgetError::NotHandled`.
+    fn handle_command(
+        &mut self,
+        widget_command: WidgetCommand,
+        shared_state: &mut PietSharedState,
+        widget_id_provider: &mut WidgetIdProvider,
+        commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError>;
+
     /// Ask the widget to handle the given event, possibly creating `WidgetEvent`s.
     fn handle_event(
         &mut self,
@@ -49,8 +64,34 @@ pub trait PietWidget<APP_EVENT: Clone>: Widget<APP_EVENT> {
         widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
     );
 
-    /// Paints the widget.
-    fn paint(&self, piet: &mut piet::Piet, region: &Region) -> Result<(), piet::Error>;
+    /// Ask the widget to handle the given event in the given phase of its dispatch along the event
+    /// path, possibly creating `WidgetEvent`s. `EventPropagation::Stop` keeps the event from the
+    /// remaining widgets of the path. By default, a widget handles the event with `handle_event()`
+    /// in the target phase and ignores the capture and bubble phases. Container widgets can
+    /// override this to intercept events of their descendants, e.g. `Escape`.
+    fn handle_dispatched_event(
+        &mut self,
+        event: &Event,
+        event_phase: EventPhase,
+        shared_state: &mut PietSharedState,
+        widget_id_provider: &mut WidgetIdProvider,
+        widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
+    ) -> EventPropagation {
+        // The widget is the event's target.
+        if event_phase == EventPhase::Target {
+            self.handle_event(event, shared_state, widget_id_provider, widget_events);
+        }
+
+        EventPropagation::Continue
+    }
+
+    /// Paints the widget. The shared state gives the scale, e.g. to snap strokes to device pixels.
+    fn paint(
+        &self,
+        piet: &mut piet::Piet,
+        shared_state: &PietSharedState,
+        region: &Region,
+    ) -> Result<(), piet::Error>;
 
     /// Removes the widget's selected value. This can be e.g. selected text in a `TextInput` widget.
     fn remove_selected_value(
@@ -87,7 +128,7 @@ pub trait PietWidget<APP_EVENT: Clone>: Widget<APP_EVENT> {
     /// Sets the widget's selected value. This can be e.g. selected text in a `TextInput` widget.
     fn set_selected_value(
         &mut self,
-        _value: Box<dyn Any>,
+        _value: WidgetValue,
         _shared_state: &mut PietSharedState,
         _widget_id_provider: &mut WidgetIdProvider,
     ) -> Result<(), WidgetError> {
@@ -105,17 +146,10 @@ pub trait PietWidget<APP_EVENT: Clone>: Widget<APP_EVENT> {
         })
     }
 
-    /// Sets the widget's value.
-    fn set_value(
-        &mut self,
-        _value: Box<dyn Any>,
-        _shared_state: &mut PietSharedState,
-        _widget_id_provider: &mut WidgetIdProvider,
-        _commands: &mut Vec<Command<APP_EVENT>>,
-    ) -> Result<(), WidgetError> {
-        Err(WidgetError::NotHandled {
-            widget_id: *self.widget_id(),
-            description: "`set_value()`".to_string(),
-        })
+    /// Returns the state that the platform's text input, e.g. an IME, edits while the widget has
+    /// focus, or `None` if the widget does not take text input. The rectangles are in widget
+    /// coordinates.
+    fn text_field_state(&self) -> Option<TextFieldState> {
+        None
     }
 }
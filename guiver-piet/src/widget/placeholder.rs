@@ -1,18 +1,20 @@
+use crate::color::piet_color;
 use crate::shared_state::PietSharedState;
 use crate::stroke::Stroke;
 use crate::widget::widget_core::WidgetCore;
 use crate::widget::WidgetError;
-use crate::{Event, PietWidget};
+use crate::{Command, Event, PietWidget};
 use druid_shell::kurbo::Line;
 use druid_shell::piet::{Color, Piet, RenderContext};
 use druid_shell::{kurbo, piet, Region};
 use guiver::{
-    Point, Rectangle, Size, SizeConstraints, Widget, WidgetEvent, WidgetEventType, WidgetId,
-    WidgetIdProvider,
+    EventObservationHandle, Point, Rectangle, Size, SizeConstraints, Widget, WidgetCommand,
+    WidgetEvent, WidgetEventObservation, WidgetEventType, WidgetId, WidgetIdProvider,
 };
 use piet::{PaintBrush, StrokeDash, StrokeStyle};
 
 /// A placeholder widget.
+#[derive(Clone)]
 pub struct Placeholder<APP_EVENT: Clone> {
     core: WidgetCore<APP_EVENT>,
     desired_size: Size,
@@ -45,11 +47,15 @@ impl<APP_EVENT: Clone> Placeholder<APP_EVENT> {
 impl<APP_EVENT: Clone> Widget<APP_EVENT> for Placeholder<APP_EVENT> {
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.core
-            .add_event_observation(widget_event_type, widget_event);
+        self.core.add_event_observation(
+            event_observation_handle,
+            widget_event_type,
+            widget_event_observation,
+        );
     }
 
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
@@ -63,32 +69,35 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Placeholder<APP_EVENT> {
         Size::new(size.width, size.height)
     }
 
-    fn event_observation(
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.core.event_observation(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.core.event_observations(widget_event_type)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.core.is_disabled()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.core.is_hidden
     }
 
     fn rectangle(&self) -> &Rectangle {
         &self.core.rectangle
     }
 
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.core.remove_event_observation(widget_event_type);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        self.core.remove_event_observation(event_observation_handle);
     }
 
     fn set_debug_rendering(&mut self, debug_rendering: bool) {
         self.core.debug_rendering = debug_rendering;
     }
 
-    fn set_is_disabled(&mut self, _is_disabled: bool) {
-        // TODO
-        println!("`Placeholder::set_is_disabled()`: TODO");
-    }
-
-    fn set_is_hidden(&mut self, is_hidden: bool) {
-        self.core.is_hidden = is_hidden;
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+        self.core.has_disabled_ancestor = has_disabled_ancestor;
     }
 
     fn set_origin(&mut self, origin: Point) {
@@ -100,7 +109,42 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Placeholder<APP_EVENT> {
     }
 }
 
-impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Placeholder<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> PietWidget<APP_EVENT> for Placeholder<APP_EVENT> {
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>> {
+        Box::new(self.clone())
+    }
+
+    fn handle_command(
+        &mut self,
+        widget_command: WidgetCommand,
+        _shared_state: &mut PietSharedState,
+        _widget_id_provider: &mut WidgetIdProvider,
+        _commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        match widget_command {
+            WidgetCommand::SetFill(fill) => {
+                self.set_fill(fill.map(|color| PaintBrush::Color(piet_color(color))))?;
+            }
+            WidgetCommand::SetIsDisabled(is_disabled) => {
+                self.core.is_disabled = is_disabled;
+            }
+            WidgetCommand::SetIsHidden(is_hidden) => {
+                self.core.is_hidden = is_hidden;
+            }
+            WidgetCommand::SetStroke(stroke) => {
+                self.set_stroke(stroke.map(Stroke::from))?;
+            }
+            widget_command => {
+                return Err(WidgetError::NotHandled {
+                    widget_id: self.core.widget_id,
+                    description: format!("`handle_command({:?})`", widget_command),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     fn handle_event(
         &mut self,
         _event: &Event,
@@ -111,7 +155,12 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Placeholder<APP_EVENT> {
         // Nothing to do.
     }
 
-    fn paint(&self, piet: &mut Piet, _region: &Region) -> Result<(), piet::Error> {
+    fn paint(
+        &self,
+        piet: &mut Piet,
+        shared_state: &PietSharedState,
+        _region: &Region,
+    ) -> Result<(), piet::Error> {
         // The placeholder widget is hidden.
         if self.core.is_hidden {
             return Ok(());
@@ -154,16 +203,19 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Placeholder<APP_EVENT> {
                 &stroke.stroke_style,
             );
 
-            // Draw the rectangle.
+            // Draw the rectangle, snapped to device pixels to keep it sharp.
             piet.stroke_styled(
-                kurbo::Rect::new(
-                    self.core.rectangle.x0,
-                    self.core.rectangle.y0,
-                    self.core.rectangle.x1,
-                    self.core.rectangle.y1,
+                shared_state.snap_stroke_rect(
+                    kurbo::Rect::new(
+                        self.core.rectangle.x0,
+                        self.core.rectangle.y0,
+                        self.core.rectangle.x1,
+                        self.core.rectangle.y1,
+                    ),
+                    stroke.stroke_width,
                 ),
                 &stroke.stroke_brush,
-                stroke.stroke_width,
+                shared_state.snap_stroke_width(stroke.stroke_width),
                 &stroke.stroke_style,
             );
         }
@@ -190,10 +242,8 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Placeholder<APP_EVENT> {
         Ok(())
     }
 
-    fn set_stroke(&mut self, _stroke: Option<Stroke>) -> Result<(), WidgetError> {
-        // TODO
-        println!("`Placeholder::set_stroke()`: TODO");
-
+    fn set_stroke(&mut self, stroke: Option<Stroke>) -> Result<(), WidgetError> {
+        self.stroke = stroke;
         Ok(())
     }
 }
@@ -202,9 +252,14 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Placeholder<APP_EVENT> {
 
 #[cfg(test)]
 mod tests {
+    use crate::shared_state::PietSharedState;
     use crate::stroke::Stroke;
     use crate::widget::Placeholder;
-    use guiver::{Size, SizeConstraints, Widget};
+    use crate::PietWidget;
+    use guiver::{
+        Color, Size, SizeConstraints, Widget, WidgetCommand, WidgetError, WidgetIdProvider,
+        WidgetValue,
+    };
 
     #[test]
     fn test_apply_size_constraints() {
@@ -231,7 +286,54 @@ mod tests {
 
     #[test]
     fn test_handle_command() {
-        // TODO
+        let mut shared_state = PietSharedState::new();
+        let mut widget_id_provider = WidgetIdProvider::new();
+        let mut commands = vec![];
+        let mut placeholder_widget: Placeholder<()> =
+            Placeholder::new(0, Stroke::default(), Size::new(200.0, 100.0));
+
+        // Hide the placeholder widget.
+        placeholder_widget
+            .handle_command(
+                WidgetCommand::SetIsHidden(true),
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut commands,
+            )
+            .unwrap();
+        assert!(placeholder_widget.is_hidden());
+
+        // Fill and stroke the placeholder widget.
+        placeholder_widget
+            .handle_command(
+                WidgetCommand::SetFill(Some(Color::rgb8(0, 0, 255))),
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut commands,
+            )
+            .unwrap();
+        placeholder_widget
+            .handle_command(
+                WidgetCommand::SetStroke(None),
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut commands,
+            )
+            .unwrap();
+        assert!(placeholder_widget.fill.is_some());
+        assert!(placeholder_widget.stroke.is_none());
+
+        // The placeholder widget has no value.
+        assert!(matches!(
+            placeholder_widget.handle_command(
+                WidgetCommand::SetValue(WidgetValue::from("text")),
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut commands,
+            ),
+            Err(WidgetError::NotHandled { widget_id: 0, .. })
+        ));
+        assert!(commands.is_empty());
     }
 
     #[test]
@@ -1,17 +1,23 @@
+use crate::color::piet_color;
+use crate::event::modifiers;
 use crate::font::Font;
 use crate::shared_state::PietSharedState;
 use crate::stroke::Stroke;
+use crate::text::paragraph_direction;
 use crate::widget::widget_core::WidgetCore;
 use crate::{Command, Event, PietWidget};
 use druid_shell::piet::{PaintBrush, Piet, PietText, PietTextLayout, RenderContext, TextLayout};
 use druid_shell::{kurbo, piet, Region};
 use guiver::{
-    HorizontalAlignment, Point, Rectangle, Size, SizeConstraints, VerticalAlignment, Widget,
-    WidgetError, WidgetEvent, WidgetEventType, WidgetId, WidgetIdProvider,
+    AccessibilityRole, EventObservationHandle, HorizontalAlignment, LayoutDirection, Point,
+    Rectangle, Size, SizeConstraints, VerticalAlignment, Widget, WidgetCommand, WidgetError,
+    WidgetEvent, WidgetEventData, WidgetEventObservation, WidgetEventType, WidgetId,
+    WidgetIdProvider, WidgetValue, WidgetValueKind,
 };
-use std::any::Any;
+use unicode_segmentation::UnicodeSegmentation;
 
 /// A text widget.
+#[derive(Clone)]
 pub struct Text<APP_EVENT: Clone> {
     core: WidgetCore<APP_EVENT>,
     font: Font,
@@ -62,14 +68,20 @@ impl<APP_EVENT: Clone> Text<APP_EVENT> {
                 .with_size(Size::new(size.width, size.height));
         }
 
-        // Determine the text's horizontal position.
-        let text_x = match self.horizontal_alignment {
+        // Determine the text's horizontal position. `Start` and `End` are resolved to `Left` and
+        // `Right` already.
+        let text_x = match self
+            .horizontal_alignment
+            .resolved(self.paragraph_direction())
+        {
             HorizontalAlignment::Center => {
                 self.core.rectangle.origin().x
                     + 0.5 * (self.core.rectangle.size().width - text_size.width).max(0.0)
             }
-            HorizontalAlignment::Left => self.core.rectangle.origin().x,
-            HorizontalAlignment::Right => {
+            HorizontalAlignment::Left | HorizontalAlignment::Start => {
+                self.core.rectangle.origin().x
+            }
+            HorizontalAlignment::Right | HorizontalAlignment::End => {
                 self.core.rectangle.origin().x + self.core.rectangle.size().width - text_size.width
             }
         };
@@ -90,16 +102,55 @@ impl<APP_EVENT: Clone> Text<APP_EVENT> {
         // Set the text's origin.
         self.text_origin = Point::new(text_x, text_y);
     }
+
+    /// Returns the x coordinates of the carets at all grapheme cluster boundaries, including the
+    /// text's end, by their byte indices.
+    pub(crate) fn caret_x_per_byte_index(&self) -> Vec<(usize, f64)> {
+        self.text
+            .grapheme_indices(true)
+            .map(|(byte_index, _)| byte_index)
+            .chain([self.text.len()])
+            .map(|byte_index| (byte_index, self.x_at_byte_index(byte_index)))
+            .collect()
+    }
+
+    /// Returns the direction of the text's first character with a strong direction or, if there is
+    /// none, the layout direction. It decides where `Start` and `End` alignments put the text.
+    pub(crate) fn paragraph_direction(&self) -> LayoutDirection {
+        paragraph_direction(&self.text).unwrap_or(self.core.layout_direction())
+    }
+
+    /// Returns the x coordinate of the caret in front of the grapheme at the given byte index, i.e.
+    /// the grapheme's left edge in left-to-right runs and its right edge in right-to-left runs.
+    pub(crate) fn x_at_byte_index(&self, byte_index: usize) -> f64 {
+        self.text_origin.x + self.text_layout.hit_test_text_position(byte_index).point.x
+    }
 }
 
 impl<APP_EVENT: Clone> Widget<APP_EVENT> for Text<APP_EVENT> {
+    fn accessibility_name(&self) -> Option<String> {
+        Some(self.text.clone())
+    }
+
+    fn accessibility_role(&self) -> AccessibilityRole {
+        AccessibilityRole::Label
+    }
+
+    fn accepted_value_kinds(&self) -> &'static [WidgetValueKind] {
+        &[WidgetValueKind::Number, WidgetValueKind::Text]
+    }
+
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.core
-            .add_event_observation(widget_event_type, widget_event);
+        self.core.add_event_observation(
+            event_observation_handle,
+            widget_event_type,
+            widget_event_observation,
+        );
     }
 
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
@@ -112,49 +163,47 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Text<APP_EVENT> {
         Size::new(size.width, size.height)
     }
 
-    fn event_observation(
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.core.event_observation(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.core.event_observations(widget_event_type)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.core.is_disabled()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.core.is_hidden
     }
 
     fn rectangle(&self) -> &Rectangle {
         &self.core.rectangle
     }
 
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.core.remove_event_observation(widget_event_type);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        self.core.remove_event_observation(event_observation_handle);
     }
 
-    fn selected_value(&self) -> Option<Box<dyn Any>> {
+    fn selected_value(&self) -> Option<WidgetValue> {
         // TODO
-        Some(Box::new(self.text.clone()))
+        Some(WidgetValue::Text(self.text.clone()))
     }
 
-    fn set_debug_rendering(&mut self, debug_rendering: bool) {
-        self.core.debug_rendering = debug_rendering;
-    }
-
-    fn set_horizontal_alignment(
-        &mut self,
-        horizontal_alignment: HorizontalAlignment,
-    ) -> Result<(), WidgetError> {
-        self.horizontal_alignment = horizontal_alignment;
+    fn set_ancestor_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        self.core.ancestor_layout_direction = layout_direction;
 
         // Layout.
         self.layout_text();
-
-        Ok(())
     }
 
-    fn set_is_disabled(&mut self, _is_disabled: bool) {
-        // TODO
-        println!("`Text::set_is_disabled()`: TODO");
+    fn set_debug_rendering(&mut self, debug_rendering: bool) {
+        self.core.debug_rendering = debug_rendering;
     }
 
-    fn set_is_hidden(&mut self, is_hidden: bool) {
-        self.core.is_hidden = is_hidden;
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+        self.core.has_disabled_ancestor = has_disabled_ancestor;
     }
 
     fn set_origin(&mut self, origin: Point) {
@@ -165,53 +214,122 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for Text<APP_EVENT> {
         self.text_origin += delta;
     }
 
-    fn set_vertical_alignment(
+    fn value(&self) -> Option<WidgetValue> {
+        Some(WidgetValue::Text(self.text.clone()))
+    }
+
+    fn widget_id(&self) -> &WidgetId {
+        &self.core.widget_id
+    }
+}
+
+impl<APP_EVENT: Clone + 'static> PietWidget<APP_EVENT> for Text<APP_EVENT> {
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>> {
+        Box::new(self.clone())
+    }
+
+    fn handle_command(
         &mut self,
-        vertical_alignment: VerticalAlignment,
+        widget_command: WidgetCommand,
+        shared_state: &mut PietSharedState,
+        _widget_id_provider: &mut WidgetIdProvider,
+        _commands: &mut Vec<Command<APP_EVENT>>,
     ) -> Result<(), WidgetError> {
-        self.vertical_alignment = vertical_alignment;
+        match widget_command {
+            WidgetCommand::SetFill(fill) => {
+                self.set_fill(fill.map(|color| PaintBrush::Color(piet_color(color))))?;
+            }
+            WidgetCommand::SetFont(font) => {
+                self.set_font(font.into(), shared_state)?;
+            }
+            WidgetCommand::SetHorizontalAlignment(horizontal_alignment) => {
+                self.horizontal_alignment = horizontal_alignment;
 
-        // Layout.
-        self.layout_text();
+                // Layout.
+                self.layout_text();
+            }
+            WidgetCommand::SetIsDisabled(is_disabled) => {
+                self.core.is_disabled = is_disabled;
+            }
+            WidgetCommand::SetIsHidden(is_hidden) => {
+                self.core.is_hidden = is_hidden;
+            }
+            WidgetCommand::SetLayoutDirection(layout_direction) => {
+                self.core.layout_direction = layout_direction;
 
-        Ok(())
-    }
+                // Layout.
+                self.layout_text();
+            }
+            WidgetCommand::SetStroke(stroke) => {
+                self.set_stroke(stroke.map(Stroke::from))?;
+            }
+            WidgetCommand::SetValue(value) => {
+                self.text = match value {
+                    WidgetValue::Number(number) => number.to_string(),
+                    WidgetValue::Text(text) => text,
+                    value => {
+                        return Err(WidgetError::ValueKindNotAccepted {
+                            widget_id: self.core.widget_id,
+                            value_kind: value.kind(),
+                        });
+                    }
+                };
+
+                self.text_layout = self
+                    .font
+                    .text_layout(shared_state.piet_text(), self.text.clone());
+                self.layout_text();
+            }
+            WidgetCommand::SetVerticalAlignment(vertical_alignment) => {
+                self.vertical_alignment = vertical_alignment;
 
-    fn value(&self) -> Option<Box<dyn Any>> {
-        Some(Box::new(self.text.clone()))
-    }
+                // Layout.
+                self.layout_text();
+            }
+        }
 
-    fn widget_id(&self) -> &WidgetId {
-        &self.core.widget_id
+        Ok(())
     }
-}
 
-impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Text<APP_EVENT> {
     fn handle_event(
         &mut self,
         event: &Event,
-        _shared_state: &mut PietSharedState,
+        shared_state: &mut PietSharedState,
         _widget_id_provider: &mut WidgetIdProvider,
         widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
     ) {
+        // The text widget is disabled.
+        if self.core.is_disabled() {
+            return;
+        }
+
         if let Event::MouseDown(mouse_event) = event {
-            // The click is outside of the text.
-            if !self
-                .core
-                .rectangle
-                .contains(mouse_event.pos.x, mouse_event.pos.y)
+            // The click is not a left click or it is outside of the text.
+            if !mouse_event.button.is_left() || !shared_state.is_under_pointer(self.core.widget_id)
             {
                 return;
             }
 
-            // There is a widget event observation.
-            if let Some(widget_event) = self.core.event_observation(&WidgetEventType::Clicked) {
-                widget_events.push(widget_event.clone());
+            let widget_event_data = WidgetEventData {
+                click_count: mouse_event.count,
+                modifiers: modifiers(mouse_event.mods),
+                ..WidgetEventData::new(self.core.widget_id)
+            };
+
+            // Iterate over the widget event observations.
+            for widget_event_observation in self.core.event_observations(&WidgetEventType::Clicked)
+            {
+                widget_events.push(widget_event_observation.widget_event(&widget_event_data));
             }
         }
     }
 
-    fn paint(&self, piet: &mut Piet, _region: &Region) -> Result<(), piet::Error> {
+    fn paint(
+        &self,
+        piet: &mut Piet,
+        _shared_state: &PietSharedState,
+        _region: &Region,
+    ) -> Result<(), piet::Error> {
         // The text widget is hidden.
         if self.core.is_hidden {
             return Ok(());
@@ -252,13 +370,6 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Text<APP_EVENT> {
         Ok(())
     }
 
-    fn set_fill(&mut self, _fill: Option<PaintBrush>) -> Result<(), WidgetError> {
-        // TODO
-        println!("`Text::set_fill()`: TODO");
-
-        Ok(())
-    }
-
     fn set_font(
         &mut self,
         _font: Font,
@@ -273,37 +384,6 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for Text<APP_EVENT> {
 
         Ok(())
     }
-
-    fn set_stroke(&mut self, _stroke: Option<Stroke>) -> Result<(), WidgetError> {
-        // TODO
-        println!("`Text::set_stroke()`: TODO");
-
-        Ok(())
-    }
-
-    fn set_value(
-        &mut self,
-        _value: Box<dyn Any>,
-        _shared_state: &mut PietSharedState,
-        _widget_id_provider: &mut WidgetIdProvider,
-        _commands: &mut Vec<Command<APP_EVENT>>,
-    ) -> Result<(), WidgetError> {
-        // The given value is a string.
-        if let Some(string) = _value.downcast_ref::<String>() {
-            self.text = string.clone();
-        }
-        // The given value is something else.
-        else {
-            self.text = format!("{:?}", _value);
-        }
-
-        self.text_layout = self
-            .font
-            .text_layout(_shared_state.piet_text(), self.text.clone());
-        self.layout_text();
-
-        Ok(())
-    }
 }
 
 // =================================================================================================
@@ -1,40 +1,59 @@
+use crate::color::piet_color;
+use crate::event::modifiers;
 use crate::font::Font;
 use crate::shared_state::PietSharedState;
 use crate::stroke::Stroke;
+use crate::text::{
+    grapheme_boundary_at_or_before, left_character_removed, left_word_removed,
+    right_character_removed, right_word_removed, selected_text, selected_text_replaced,
+    text_cursor_moved_left, text_cursor_moved_right, text_inserted, visual_selection_ranges,
+    TextCursor, TextSelection,
+};
 use crate::widget::widget_core::WidgetCore;
 use crate::widget::Text;
-use crate::{Command, Event, PietWidget};
+use crate::{Command, Event, PietWidget, TextFieldState};
 use druid_shell::kurbo::{Line, RoundedRect};
 use druid_shell::piet::{Color, Error, PaintBrush, Piet, PietText, RenderContext};
+use druid_shell::text::Selection;
 use druid_shell::{kurbo, KbKey, Region};
 use guiver::{
-    HorizontalAlignment, Point, Rectangle, Size, SizeConstraints, VerticalAlignment, Widget,
-    WidgetError, WidgetEvent, WidgetEventType, WidgetId, WidgetIdProvider,
+    AccessibilityRole, EventObservationHandle, HorizontalAlignment, LayoutDirection, Point,
+    Rectangle, Size, SizeConstraints, Widget, WidgetCommand, WidgetError, WidgetEvent,
+    WidgetEventData, WidgetEventObservation, WidgetEventType, WidgetId, WidgetIdProvider,
+    WidgetValue, WidgetValueKind,
 };
-use std::any::Any;
 use std::borrow::BorrowMut;
+use std::ops::Range;
 
 /// A text input widget.
+#[derive(Clone)]
 pub struct TextInput<APP_EVENT: Clone> {
-    caret_character_index: usize,
     caret_x: f64,
     caret_y1: f64,
     caret_y2: f64,
+    /// The range of the preedit text that an IME is composing.
+    composition_range: Option<Range<usize>>,
     core: WidgetCore<APP_EVENT>,
     corner_radius: f64,
     fill: Option<PaintBrush>,
     has_focus: bool,
     horizontal_alignment: HorizontalAlignment,
-    is_disabled: bool,
     padding: f64,
+    selection_color: Color,
     stroke: Stroke,
     stroke_focused: Stroke,
     text: String,
+    /// Where the text is edited. It moves with the arrow keys in visual order, so that it moves
+    /// to the left in right-to-left text as well.
+    text_cursor: TextCursor,
+    text_selection: Option<TextSelection>,
     text_widget: Text<APP_EVENT>,
+    /// The previous texts, the most recent one last.
+    undo_texts: Vec<String>,
     width: f64,
 }
 
-impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> TextInput<APP_EVENT> {
     ///
     pub fn new(
         widget_id: WidgetId,
@@ -49,17 +68,17 @@ impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
         let child_widget_id = 0;
 
         TextInput {
-            caret_character_index: 0,
             caret_x: 0.0,
             caret_y1: 0.0,
             caret_y2: 0.0,
+            composition_range: None,
             core: WidgetCore::new(widget_id, debug_rendering_stroke.clone()),
             corner_radius: 2.0,
             fill: None,
             has_focus: false,
             horizontal_alignment: HorizontalAlignment::Center,
-            is_disabled: true,
             padding: 4.0,
+            selection_color: Color::rgb8(60, 90, 150),
             stroke: Stroke {
                 stroke_brush: PaintBrush::Color(frame_color),
                 stroke_style: Default::default(),
@@ -71,6 +90,10 @@ impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
                 stroke_width: 1.0,
             },
             text: text.clone(),
+            text_cursor: TextCursor {
+                left_of_byte_index: text.len(),
+            },
+            text_selection: None,
             text_widget: Text::new(
                 child_widget_id,
                 debug_rendering_stroke,
@@ -78,6 +101,7 @@ impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
                 font,
                 text,
             ),
+            undo_texts: vec![],
             width,
         }
     }
@@ -96,9 +120,15 @@ impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
 
         assert!(commands.is_empty());
 
-        // There is a widget event observation.
-        if let Some(widget_event) = self.core.event_observation(&WidgetEventType::ValueChanged) {
-            widget_events.push(widget_event.clone());
+        let widget_event_data = WidgetEventData {
+            value: Some(WidgetValue::Text(self.text.clone())),
+            ..WidgetEventData::new(self.core.widget_id)
+        };
+
+        // Iterate over the widget event observations.
+        for widget_event_observation in self.core.event_observations(&WidgetEventType::ValueChanged)
+        {
+            widget_events.push(widget_event_observation.widget_event(&widget_event_data));
         }
     }
 
@@ -133,12 +163,16 @@ impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
         // Set the child widget's origin.
         {
             let child_origin = {
-                let delta_child_x = match self.horizontal_alignment {
+                // `Start` and `End` are resolved to `Left` and `Right` already.
+                let delta_child_x = match self
+                    .horizontal_alignment
+                    .resolved(self.text_widget.paragraph_direction())
+                {
                     HorizontalAlignment::Center => {
                         0.5 * (self.core.rectangle.size().width - child_size.width).max(0.0)
                     }
-                    HorizontalAlignment::Left => self.padding,
-                    HorizontalAlignment::Right => {
+                    HorizontalAlignment::Left | HorizontalAlignment::Start => self.padding,
+                    HorizontalAlignment::Right | HorizontalAlignment::End => {
                         (self.core.rectangle.size().width - child_size.width).max(0.0)
                             - self.padding
                     }
@@ -157,8 +191,40 @@ impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
     }
 
     ///
-    fn update_caret_character_index(&mut self) {
-        // TODO: Decrease the index when the text shrank.
+    fn propagate_layout_direction(&mut self) {
+        self.text_widget
+            .set_ancestor_layout_direction(self.core.layout_direction());
+
+        // Layout the child widget, whose alignment might depend on the layout direction.
+        self.layout_child_widget();
+    }
+
+    /// Replaces the selected text with the given text or, if there is no selection, inserts it at
+    /// the text cursor. The text cursor is put behind the new text.
+    fn replace_selected_text(&mut self, replacement_text: &str) {
+        // Remember the text for undo.
+        self.undo_texts.push(self.text.clone());
+
+        // There is a text selection.
+        if let Some(text_selection) = self.text_selection.take() {
+            self.text =
+                selected_text_replaced(self.text.clone(), &text_selection, replacement_text);
+            self.text_cursor.left_of_byte_index = text_selection
+                .left_of_byte_index_begin
+                .min(text_selection.left_of_byte_index_end)
+                + replacement_text.len();
+        }
+        // There is no text selection.
+        else {
+            self.text = text_inserted(self.text.clone(), &self.text_cursor, replacement_text);
+            self.text_cursor.left_of_byte_index += replacement_text.len();
+        }
+    }
+
+    /// Keeps the text cursor within the text and on a grapheme cluster boundary.
+    fn update_text_cursor(&mut self) {
+        self.text_cursor.left_of_byte_index =
+            grapheme_boundary_at_or_before(&self.text, self.text_cursor.left_of_byte_index);
 
         // Update the caret postion and dimension.
         self.update_caret_position();
@@ -166,8 +232,10 @@ impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
 
     ///
     fn update_caret_position(&mut self) {
-        // Put the care to the right of the text widget.
-        self.caret_x = self.text_widget.rectangle().x1;
+        // Put the caret in front of the grapheme cluster right of the text cursor.
+        self.caret_x = self
+            .text_widget
+            .x_at_byte_index(self.text_cursor.left_of_byte_index);
 
         self.caret_y1 = self.core.rectangle.y0 + self.padding;
         self.caret_y2 = self.core.rectangle.y1 - self.padding;
@@ -193,33 +261,49 @@ impl<APP_EVENT: Clone> TextInput<APP_EVENT> {
         // Pass the updated text to the child text widget.
         self.text_widget
             .borrow_mut()
-            .set_value(
-                Box::new(self.text.clone()),
+            .handle_command(
+                WidgetCommand::SetValue(WidgetValue::Text(self.text.clone())),
                 shared_state,
                 widget_id_provider,
                 commands,
             )
             .unwrap();
 
-        // Update the caret index, if necessary.
-        self.update_caret_character_index();
+        // Update the text cursor, if necessary.
+        self.update_text_cursor();
 
         self.layout_child_widget();
     }
 }
 
-impl<APP_EVENT: Clone> Widget<APP_EVENT> for TextInput<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> Widget<APP_EVENT> for TextInput<APP_EVENT> {
+    fn accessibility_role(&self) -> AccessibilityRole {
+        AccessibilityRole::TextField
+    }
+
+    fn accessibility_value(&self) -> Option<String> {
+        Some(self.text.clone())
+    }
+
+    fn accepted_value_kinds(&self) -> &'static [WidgetValueKind] {
+        self.text_widget.accepted_value_kinds()
+    }
+
     fn accepts_focus(&self) -> bool {
         true
     }
 
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.core
-            .add_event_observation(widget_event_type, widget_event);
+        self.core.add_event_observation(
+            event_observation_handle,
+            widget_event_type,
+            widget_event_observation,
+        );
     }
 
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
@@ -233,51 +317,68 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for TextInput<APP_EVENT> {
         Size::new(size.width, size.height)
     }
 
-    fn event_observation(
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.core.event_observation(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.core.event_observations(widget_event_type)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.core.is_disabled()
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.core.is_hidden
     }
 
     fn rectangle(&self) -> &Rectangle {
         &self.core.rectangle
     }
 
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.core.remove_event_observation(widget_event_type);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        self.core.remove_event_observation(event_observation_handle);
+    }
+
+    fn selected_value(&self) -> Option<WidgetValue> {
+        // There is a text selection.
+        if let Some(text_selection) = &self.text_selection {
+            Some(WidgetValue::Text(
+                selected_text(&self.text, text_selection).to_string(),
+            ))
+        }
+        // There is no text selection.
+        else {
+            self.text_widget.selected_value()
+        }
     }
 
-    fn selected_value(&self) -> Option<Box<dyn Any>> {
-        self.text_widget.selected_value()
+    fn set_ancestor_layout_direction(&mut self, layout_direction: LayoutDirection) {
+        self.core.ancestor_layout_direction = layout_direction;
+
+        // Pass the layout direction on to the text widget.
+        self.propagate_layout_direction();
     }
 
     fn set_debug_rendering(&mut self, debug_rendering: bool) {
         self.core.debug_rendering = debug_rendering;
     }
 
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+        self.core.has_disabled_ancestor = has_disabled_ancestor;
+    }
+
     fn set_has_focus(&mut self, has_focus: bool) -> Result<(), WidgetError> {
         self.has_focus = has_focus;
-        Ok(())
-    }
 
-    fn set_horizontal_alignment(
-        &mut self,
-        horizontal_alignment: HorizontalAlignment,
-    ) -> Result<(), WidgetError> {
-        self.horizontal_alignment = horizontal_alignment;
+        // Losing focus ends the IME's composition.
+        if !has_focus {
+            self.composition_range = None;
+        }
 
         Ok(())
     }
 
-    fn set_is_disabled(&mut self, is_disabled: bool) {
-        self.is_disabled = is_disabled;
-    }
-
-    fn set_is_hidden(&mut self, is_hidden: bool) {
-        self.core.is_hidden = is_hidden;
-    }
-
     fn set_origin(&mut self, origin: Point) {
         self.core.rectangle = self.core.rectangle.with_origin(origin);
 
@@ -285,17 +386,7 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for TextInput<APP_EVENT> {
         self.layout_child_widget();
     }
 
-    fn set_vertical_alignment(
-        &mut self,
-        _vertical_alignment: VerticalAlignment,
-    ) -> Result<(), WidgetError> {
-        // TODO
-        println!("`TextInput::set_vertical_alignment()`: TODO");
-
-        Ok(())
-    }
-
-    fn value(&self) -> Option<Box<dyn Any>> {
+    fn value(&self) -> Option<WidgetValue> {
         self.text_widget.value()
     }
 
@@ -304,7 +395,70 @@ impl<APP_EVENT: Clone> Widget<APP_EVENT> for TextInput<APP_EVENT> {
     }
 }
 
-impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
+impl<APP_EVENT: Clone + 'static> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
+    fn boxed_clone(&self) -> Box<dyn PietWidget<APP_EVENT>> {
+        Box::new(self.clone())
+    }
+
+    fn handle_command(
+        &mut self,
+        widget_command: WidgetCommand,
+        shared_state: &mut PietSharedState,
+        widget_id_provider: &mut WidgetIdProvider,
+        commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        match widget_command {
+            WidgetCommand::SetFill(fill) => {
+                self.set_fill(fill.map(|color| PaintBrush::Color(piet_color(color))))?;
+            }
+            WidgetCommand::SetFont(font) => {
+                self.set_font(font.into(), shared_state)?;
+            }
+            WidgetCommand::SetHorizontalAlignment(horizontal_alignment) => {
+                self.horizontal_alignment = horizontal_alignment;
+            }
+            WidgetCommand::SetIsDisabled(is_disabled) => {
+                self.core.is_disabled = is_disabled;
+            }
+            WidgetCommand::SetIsHidden(is_hidden) => {
+                self.core.is_hidden = is_hidden;
+            }
+            WidgetCommand::SetLayoutDirection(layout_direction) => {
+                self.core.layout_direction = layout_direction;
+
+                // Pass the layout direction on to the text widget.
+                self.propagate_layout_direction();
+            }
+            WidgetCommand::SetStroke(stroke) => {
+                self.set_stroke(stroke.map(Stroke::from))?;
+            }
+            WidgetCommand::SetValue(value) => {
+                self.text = match value {
+                    WidgetValue::Number(number) => number.to_string(),
+                    WidgetValue::Text(text) => text,
+                    value => {
+                        return Err(WidgetError::ValueKindNotAccepted {
+                            widget_id: self.core.widget_id,
+                            value_kind: value.kind(),
+                        });
+                    }
+                };
+                self.text_cursor.left_of_byte_index = self.text.len();
+                self.text_selection = None;
+                self.composition_range = None;
+
+                // Apply the text changes.
+                self.update_text_widget(shared_state, widget_id_provider, commands);
+            }
+            WidgetCommand::SetVerticalAlignment(_vertical_alignment) => {
+                // TODO
+                println!("`TextInput::handle_command(SetVerticalAlignment)`: TODO");
+            }
+        }
+
+        Ok(())
+    }
+
     fn handle_event(
         &mut self,
         event: &Event,
@@ -312,50 +466,151 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
         widget_id_provider: &mut WidgetIdProvider,
         widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
     ) {
+        // The text input widget is disabled.
+        if self.core.is_disabled() {
+            return;
+        }
+
         match event {
             Event::ClipboardPaste(string) => {
-                // TODO: error handling
-                self.set_selected_value(Box::new(string.clone()), shared_state, widget_id_provider)
-                    .unwrap();
+                self.replace_selected_text(string);
+
+                // Apply the text changes.
+                self.broadcast_modified_text(widget_id_provider, shared_state, widget_events);
             }
             Event::KeyDown(key_event) => match &key_event.key {
-                KbKey::Character(chracter_string) => {
-                    // Append the character to the text.
-                    self.text.push_str(chracter_string);
+                // Characters typed with Ctrl or Meta are unhandled shortcuts.
+                KbKey::Character(chracter_string)
+                    if !key_event.mods.ctrl() && !key_event.mods.meta() =>
+                {
+                    // Replace the selected text with the character or append it.
+                    self.replace_selected_text(chracter_string);
 
                     // Apply the text changes.
                     self.broadcast_modified_text(widget_id_provider, shared_state, widget_events);
                 }
                 KbKey::Backspace => {
-                    if !self.text.is_empty() {
-                        // Drop the last character from the text.
-                        let mut character_iterator = self.text.chars();
-                        character_iterator.next_back();
-                        self.text = character_iterator.as_str().to_string();
+                    // There is a text selection.
+                    if self.text_selection.is_some() {
+                        // Remove the selected text.
+                        self.replace_selected_text("");
+                    }
+                    // Remove left of the text cursor.
+                    else {
+                        // Ctrl or Alt remove the previous word, otherwise the previous grapheme
+                        // cluster, i.e. the previous character that users perceive.
+                        let text = if key_event.mods.ctrl() || key_event.mods.alt() {
+                            left_word_removed(self.text.clone(), &self.text_cursor)
+                        } else {
+                            left_character_removed(self.text.clone(), &self.text_cursor)
+                        };
+
+                        // The text cursor is at the beginning of the text, so that nothing was
+                        // removed.
+                        if text == self.text {
+                            return;
+                        }
+
+                        // Move the text cursor in front of the removed text.
+                        self.text_cursor.left_of_byte_index -= self.text.len() - text.len();
+
+                        // Remember the text for undo.
+                        self.undo_texts
+                            .push(std::mem::replace(&mut self.text, text));
                     }
 
                     // Apply the text changes.
                     self.broadcast_modified_text(widget_id_provider, shared_state, widget_events);
                 }
+                KbKey::Delete => {
+                    // There is a text selection.
+                    if self.text_selection.is_some() {
+                        // Remove the selected text.
+                        self.replace_selected_text("");
+                    }
+                    // Remove right of the text cursor.
+                    else {
+                        // Ctrl or Alt remove the next word, otherwise the next grapheme cluster.
+                        let text = if key_event.mods.ctrl() || key_event.mods.alt() {
+                            right_word_removed(self.text.clone(), &self.text_cursor)
+                        } else {
+                            right_character_removed(self.text.clone(), &self.text_cursor)
+                        };
+
+                        // The text cursor is at the end of the text, so that nothing was removed.
+                        if text == self.text {
+                            return;
+                        }
+
+                        // Remember the text for undo.
+                        self.undo_texts
+                            .push(std::mem::replace(&mut self.text, text));
+                    }
+
+                    // Apply the text changes.
+                    self.broadcast_modified_text(widget_id_provider, shared_state, widget_events);
+                }
+                KbKey::ArrowLeft => {
+                    self.text_selection = None;
+
+                    // Move the text cursor visually to the left, which is towards the end of the
+                    // text within right-to-left runs.
+                    self.text_cursor = text_cursor_moved_left(
+                        &self.text,
+                        &self.text_cursor,
+                        self.text_widget.paragraph_direction(),
+                    );
+                    self.update_caret_position();
+                }
+                KbKey::ArrowRight => {
+                    self.text_selection = None;
+
+                    // Move the text cursor visually to the right.
+                    self.text_cursor = text_cursor_moved_right(
+                        &self.text,
+                        &self.text_cursor,
+                        self.text_widget.paragraph_direction(),
+                    );
+                    self.update_caret_position();
+                }
+                KbKey::End => {
+                    self.text_selection = None;
+
+                    // Move the text cursor to the end of the text.
+                    self.text_cursor.left_of_byte_index = self.text.len();
+                    self.update_caret_position();
+                }
                 KbKey::Enter => {
                     // Enter on a (focused) text input submits the value.
-
-                    // There is a widget event observation.
-                    if let Some(widget_event) =
-                        self.core.event_observation(&WidgetEventType::Submitted)
+                    let widget_event_data = WidgetEventData {
+                        modifiers: modifiers(key_event.mods),
+                        value: Some(WidgetValue::Text(self.text.clone())),
+                        ..WidgetEventData::new(self.core.widget_id)
+                    };
+
+                    // Iterate over the widget event observations.
+                    for widget_event_observation in
+                        self.core.event_observations(&WidgetEventType::Submitted)
                     {
-                        widget_events.push(widget_event.clone());
+                        widget_events
+                            .push(widget_event_observation.widget_event(&widget_event_data));
                     }
                 }
+                KbKey::Home => {
+                    self.text_selection = None;
+
+                    // Move the text cursor to the beginning of the text.
+                    self.text_cursor.left_of_byte_index = 0;
+                    self.update_caret_position();
+                }
                 _ => {}
             },
-            Event::MouseDown(mouse_event) => {
+            Event::MouseDown(_) => {
+                // A click removes the text selection.
+                self.text_selection = None;
+
                 // The mouse is down within this text input.
-                if self
-                    .core
-                    .rectangle
-                    .contains(mouse_event.pos.x, mouse_event.pos.y)
-                {
+                if shared_state.is_under_pointer(self.core.widget_id) {
                     // This widget has no focus.
                     if !self.has_focus {
                         // Accept focus.
@@ -377,11 +632,69 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
                     }
                 }
             }
+            // There is text to select. A double click selects it as well.
+            Event::DoubleClicked(_) | Event::SelectAll if !self.text.is_empty() => {
+                self.text_selection = Some(TextSelection {
+                    left_of_byte_index_begin: 0,
+                    left_of_byte_index_end: self.text.len(),
+                });
+                self.text_cursor.left_of_byte_index = self.text.len();
+                self.update_caret_position();
+            }
+            Event::TextFieldEdited(text_field_state) => {
+                let text_was_changed = text_field_state.text != self.text;
+
+                // Remember the text for undo, unless the IME is still composing it.
+                if text_was_changed && self.composition_range.is_none() {
+                    self.undo_texts.push(self.text.clone());
+                }
+
+                self.text = text_field_state.text.clone();
+                self.text_cursor.left_of_byte_index = text_field_state.selection.active;
+                self.text_selection = if text_field_state.selection.is_caret() {
+                    None
+                } else {
+                    Some(TextSelection {
+                        left_of_byte_index_begin: text_field_state.selection.anchor,
+                        left_of_byte_index_end: text_field_state.selection.active,
+                    })
+                };
+                self.composition_range = text_field_state.composition_range.clone();
+
+                // The text was changed, but the IME is still composing it.
+                if text_was_changed && self.composition_range.is_some() {
+                    // Only show the preedit text.
+                    let mut commands = vec![];
+                    self.update_text_widget(shared_state, widget_id_provider, &mut commands);
+                    assert!(commands.is_empty());
+                }
+                // The text was changed and committed.
+                else if text_was_changed {
+                    // Apply the text changes.
+                    self.broadcast_modified_text(widget_id_provider, shared_state, widget_events);
+                }
+            }
+            Event::Undo => {
+                // There is a previous text.
+                if let Some(text) = self.undo_texts.pop() {
+                    self.text = text;
+                    self.text_cursor.left_of_byte_index = self.text.len();
+                    self.text_selection = None;
+
+                    // Apply the text changes.
+                    self.broadcast_modified_text(widget_id_provider, shared_state, widget_events);
+                }
+            }
             _ => {}
         }
     }
 
-    fn paint(&self, piet: &mut Piet, region: &Region) -> Result<(), Error> {
+    fn paint(
+        &self,
+        piet: &mut Piet,
+        shared_state: &PietSharedState,
+        region: &Region,
+    ) -> Result<(), Error> {
         // The text input widget is hidden.
         if self.core.is_hidden {
             return Ok(());
@@ -389,14 +702,17 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
 
         let stroke = self.stroke();
 
-        // Paint the frame.
+        // Paint the frame, snapped to device pixels to keep it sharp.
         {
             let shape = RoundedRect::from_rect(
-                kurbo::Rect::new(
-                    self.core.rectangle.x0,
-                    self.core.rectangle.y0,
-                    self.core.rectangle.x1,
-                    self.core.rectangle.y1,
+                shared_state.snap_stroke_rect(
+                    kurbo::Rect::new(
+                        self.core.rectangle.x0,
+                        self.core.rectangle.y0,
+                        self.core.rectangle.x1,
+                        self.core.rectangle.y1,
+                    ),
+                    stroke.stroke_width,
                 ),
                 self.corner_radius,
             );
@@ -407,18 +723,69 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
             }
 
             // Stroke the frame.
-            piet.stroke(shape, &stroke.stroke_brush, stroke.stroke_width);
+            piet.stroke(
+                shape,
+                &stroke.stroke_brush,
+                shared_state.snap_stroke_width(stroke.stroke_width),
+            );
+        }
+
+        // There is a text selection.
+        if let (true, Some(text_selection)) = (self.has_focus, &self.text_selection) {
+            let text_rectangle = self.text_widget.rectangle();
+            let selection = text_selection
+                .left_of_byte_index_begin
+                .min(text_selection.left_of_byte_index_end)
+                ..text_selection
+                    .left_of_byte_index_begin
+                    .max(text_selection.left_of_byte_index_end);
+
+            // Highlight each visual part of the selection, which is discontiguous in mixed
+            // left-to-right and right-to-left text.
+            for range in visual_selection_ranges(
+                &self.text,
+                selection,
+                self.text_widget.paragraph_direction(),
+            ) {
+                let x0 = self.text_widget.x_at_byte_index(range.start);
+                let x1 = self.text_widget.x_at_byte_index(range.end);
+
+                piet.fill(
+                    kurbo::Rect::new(x0.min(x1), text_rectangle.y0, x0.max(x1), text_rectangle.y1),
+                    &self.selection_color,
+                );
+            }
         }
 
         // Paint the text widget.
-        self.text_widget.paint(piet, region)?;
+        self.text_widget.paint(piet, shared_state, region)?;
+
+        // An IME is composing preedit text.
+        if let Some(composition_range) = &self.composition_range {
+            let y = self.text_widget.rectangle().y1 - 1.0;
+
+            // Underline the preedit text.
+            piet.stroke(
+                Line::new(
+                    (self.text_widget.x_at_byte_index(composition_range.start), y),
+                    (self.text_widget.x_at_byte_index(composition_range.end), y),
+                ),
+                &stroke.stroke_brush,
+                shared_state.snap_stroke_width(1.0),
+            );
+        }
 
-        // Draw the caret.
+        // Draw the caret, snapped to device pixels to keep it sharp.
         if self.has_focus {
+            let caret = shared_state.snap_stroke_rect(
+                kurbo::Rect::new(self.caret_x, self.caret_y1, self.caret_x, self.caret_y2),
+                1.0,
+            );
+
             piet.stroke_styled(
-                Line::new((self.caret_x, self.caret_y1), (self.caret_x, self.caret_y2)),
+                Line::new((caret.x0, caret.y0), (caret.x0, caret.y1)),
                 &stroke.stroke_brush,
-                1.0,
+                shared_state.snap_stroke_width(1.0),
                 &stroke.stroke_style,
             );
         }
@@ -445,12 +812,24 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
         shared_state: &mut PietSharedState,
         widget_id_provider: &mut WidgetIdProvider,
     ) -> Result<(), WidgetError> {
+        // There is a text selection.
+        if self.text_selection.is_some() {
+            // Remove the selected text.
+            self.replace_selected_text("");
+
+            let mut commands = vec![];
+            self.update_text_widget(shared_state, widget_id_provider, &mut commands);
+            assert!(commands.is_empty());
+
+            return Ok(());
+        }
+
         let mut commands = vec![];
 
         // TODO
         println!("`TextInput::remove_selected_value()`: TODO");
-        self.set_value(
-            Box::new("".to_string()),
+        self.handle_command(
+            WidgetCommand::SetValue(WidgetValue::Text("".to_string())),
             shared_state,
             widget_id_provider,
             &mut commands,
@@ -476,19 +855,26 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
 
     fn set_selected_value(
         &mut self,
-        value: Box<dyn Any>,
+        value: WidgetValue,
         shared_state: &mut PietSharedState,
         widget_id_provider: &mut WidgetIdProvider,
     ) -> Result<(), WidgetError> {
-        let mut commands = vec![];
-
-        // TODO
-        println!("`TextInput::set_selected_value()`: TODO");
-        self.set_value(value, shared_state, widget_id_provider, &mut commands)?;
+        match value {
+            WidgetValue::Text(string) => {
+                // Replace the selected text or append the string.
+                self.replace_selected_text(&string);
 
-        assert!(commands.is_empty());
+                let mut commands = vec![];
+                self.update_text_widget(shared_state, widget_id_provider, &mut commands);
+                assert!(commands.is_empty());
 
-        Ok(())
+                Ok(())
+            }
+            value => Err(WidgetError::ValueKindNotAccepted {
+                widget_id: self.core.widget_id,
+                value_kind: value.kind(),
+            }),
+        }
     }
 
     fn set_stroke(&mut self, _stroke: Option<Stroke>) -> Result<(), WidgetError> {
@@ -497,22 +883,41 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
         Ok(())
     }
 
-    fn set_value(
-        &mut self,
-        value: Box<dyn Any>,
-        shared_state: &mut PietSharedState,
-        widget_id_provider: &mut WidgetIdProvider,
-        commands: &mut Vec<Command<APP_EVENT>>,
-    ) -> Result<(), WidgetError> {
-        // The given value is a string.
-        if let Some(string) = value.downcast_ref::<String>() {
-            self.text = string.clone();
-
-            // Apply the text changes.
-            self.update_text_widget(shared_state, widget_id_provider, commands);
+    fn text_field_state(&self) -> Option<TextFieldState> {
+        // The text input widget takes no text input.
+        if !self.has_focus || self.core.is_disabled() {
+            return None;
         }
 
-        Ok(())
+        let selection = if let Some(text_selection) = &self.text_selection {
+            Selection::new(
+                text_selection.left_of_byte_index_begin,
+                text_selection.left_of_byte_index_end,
+            )
+        }
+        // There is no text selection. The caret is behind the text.
+        else {
+            Selection::caret(self.text_cursor.left_of_byte_index)
+        };
+
+        Some(TextFieldState {
+            bounding_box: kurbo::Rect::new(
+                self.core.rectangle.x0,
+                self.core.rectangle.y0,
+                self.core.rectangle.x1,
+                self.core.rectangle.y1,
+            ),
+            caret_rectangle: kurbo::Rect::new(
+                self.caret_x,
+                self.caret_y1,
+                self.caret_x,
+                self.caret_y2,
+            ),
+            caret_x_per_byte_index: self.text_widget.caret_x_per_byte_index(),
+            composition_range: self.composition_range.clone(),
+            selection,
+            text: self.text.clone(),
+        })
     }
 }
 
@@ -520,6 +925,18 @@ impl<APP_EVENT: Clone> PietWidget<APP_EVENT> for TextInput<APP_EVENT> {
 
 #[cfg(test)]
 mod tests {
+    use crate::shared_state::PietSharedState;
+    use crate::stroke::Stroke;
+    use crate::widget::TextInput;
+    use crate::{Color, Event, PietWidget, TextFieldState};
+    use druid_shell::text::Selection;
+    use druid_shell::{KbKey, KeyEvent, Modifiers};
+    use guiver::{
+        Widget, WidgetCommand, WidgetError, WidgetEvent, WidgetEventObservation, WidgetEventType,
+        WidgetIdProvider, WidgetValue, WidgetValueKind,
+    };
+    use std::ops::Range;
+    use std::rc::Rc;
 
     #[test]
     fn test_apply_size_constraints() {
@@ -532,11 +949,265 @@ mod tests {
 
     #[test]
     fn test_handle_command() {
-        // TODO
+        let mut shared_state = PietSharedState::new();
+        let mut widget_id_provider = WidgetIdProvider::new();
+        let mut commands = vec![];
+
+        let mut text_input: TextInput<()> = TextInput::new(
+            0,
+            Stroke::default(),
+            shared_state.piet_text(),
+            Default::default(),
+            "abc".to_string(),
+            100.0,
+            Color::rgb8(0, 0, 0),
+            Color::rgb8(0, 0, 0),
+        );
+
+        let mut set_value = |text_input: &mut TextInput<()>, value: WidgetValue| {
+            text_input.handle_command(
+                WidgetCommand::SetValue(value),
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut commands,
+            )
+        };
+
+        // Texts and numbers are accepted.
+        set_value(&mut text_input, WidgetValue::from("def")).unwrap();
+        assert_eq!(text_input.value().unwrap().as_text(), Some("def"));
+        set_value(&mut text_input, WidgetValue::Number(42.0)).unwrap();
+        assert_eq!(text_input.value().unwrap().as_text(), Some("42"));
+
+        // Other kinds of values are rejected.
+        assert!(matches!(
+            set_value(&mut text_input, WidgetValue::Bool(true)),
+            Err(WidgetError::ValueKindNotAccepted {
+                widget_id: 0,
+                value_kind: WidgetValueKind::Bool,
+            })
+        ));
+        assert_eq!(text_input.value().unwrap().as_text(), Some("42"));
     }
 
     #[test]
     fn test_handle_event() {
-        // TODO
+        let mut shared_state = PietSharedState::new();
+        let mut widget_id_provider = WidgetIdProvider::new();
+        let mut widget_events = vec![];
+
+        let mut text_input: TextInput<()> = TextInput::new(
+            0,
+            Stroke::default(),
+            shared_state.piet_text(),
+            Default::default(),
+            "abc".to_string(),
+            100.0,
+            Color::rgb8(0, 0, 0),
+            Color::rgb8(0, 0, 0),
+        );
+
+        let mut handle_event = |text_input: &mut TextInput<()>, event: Event| {
+            text_input.handle_event(
+                &event,
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut widget_events,
+            )
+        };
+        let text =
+            |text_input: &TextInput<()>| text_input.value().unwrap().as_text().unwrap().to_string();
+        let key_down = |key: KbKey| {
+            Event::KeyDown(KeyEvent {
+                key,
+                ..Default::default()
+            })
+        };
+
+        // Typing appends.
+        handle_event(&mut text_input, key_down(KbKey::Character("d".to_string())));
+        assert_eq!(text(&text_input), "abcd");
+
+        // Typing replaces the selection.
+        handle_event(&mut text_input, Event::SelectAll);
+        assert_eq!(
+            text_input.selected_value().unwrap().as_text().unwrap(),
+            "abcd"
+        );
+        handle_event(&mut text_input, key_down(KbKey::Character("x".to_string())));
+        assert_eq!(text(&text_input), "x");
+
+        // Backspace removes the selection.
+        handle_event(&mut text_input, Event::SelectAll);
+        handle_event(&mut text_input, key_down(KbKey::Backspace));
+        assert_eq!(text(&text_input), "");
+
+        // Undo restores the previous texts.
+        handle_event(&mut text_input, Event::Undo);
+        assert_eq!(text(&text_input), "x");
+        handle_event(&mut text_input, Event::Undo);
+        assert_eq!(text(&text_input), "abcd");
+        handle_event(&mut text_input, Event::Undo);
+        assert_eq!(text(&text_input), "abc");
+        handle_event(&mut text_input, Event::Undo);
+        assert_eq!(text(&text_input), "abc");
+
+        // Backspace removes whole grapheme clusters.
+        handle_event(
+            &mut text_input,
+            key_down(KbKey::Character("e\u{301}👩\u{200d}👧".to_string())),
+        );
+        handle_event(&mut text_input, key_down(KbKey::Backspace));
+        assert_eq!(text(&text_input), "abce\u{301}");
+        handle_event(&mut text_input, key_down(KbKey::Backspace));
+        assert_eq!(text(&text_input), "abc");
+
+        // Ctrl + Backspace removes the last word.
+        handle_event(
+            &mut text_input,
+            key_down(KbKey::Character(" def".to_string())),
+        );
+        handle_event(
+            &mut text_input,
+            Event::KeyDown(KeyEvent {
+                key: KbKey::Backspace,
+                mods: Modifiers::CONTROL,
+                ..Default::default()
+            }),
+        );
+        assert_eq!(text(&text_input), "abc ");
+
+        // Typing and Delete edit at the text cursor, which the arrow keys move.
+        handle_event(&mut text_input, key_down(KbKey::Home));
+        handle_event(&mut text_input, key_down(KbKey::ArrowRight));
+        handle_event(&mut text_input, key_down(KbKey::Character("x".to_string())));
+        assert_eq!(text(&text_input), "axbc ");
+        handle_event(&mut text_input, key_down(KbKey::Delete));
+        assert_eq!(text(&text_input), "axc ");
+        handle_event(&mut text_input, key_down(KbKey::ArrowLeft));
+        handle_event(&mut text_input, key_down(KbKey::Backspace));
+        assert_eq!(text(&text_input), "xc ");
+
+        // In right-to-left text, the right arrow key moves towards the beginning of the text.
+        handle_event(&mut text_input, Event::SelectAll);
+        handle_event(
+            &mut text_input,
+            key_down(KbKey::Character("אב".to_string())),
+        );
+        handle_event(&mut text_input, key_down(KbKey::ArrowRight));
+        handle_event(&mut text_input, key_down(KbKey::Character("ג".to_string())));
+        assert_eq!(text(&text_input), "אגב");
+    }
+
+    #[test]
+    fn test_text_field_edited() {
+        let mut shared_state = PietSharedState::new();
+        let mut widget_id_provider = WidgetIdProvider::new();
+        let mut text_input: TextInput<String> = TextInput::new(
+            0,
+            Stroke::default(),
+            shared_state.piet_text(),
+            Default::default(),
+            "ab".to_string(),
+            100.0,
+            Color::rgb8(0, 0, 0),
+            Color::rgb8(0, 0, 0),
+        );
+        text_input.add_event_observation(
+            1,
+            WidgetEventType::ValueChanged,
+            WidgetEventObservation::Mapper(Rc::new(|widget_event_data| {
+                widget_event_data.value.as_ref().unwrap().to_text().unwrap()
+            })),
+        );
+
+        // Only a focused text input takes text input.
+        assert_eq!(text_input.text_field_state(), None);
+        text_input.set_has_focus(true).unwrap();
+        let text_field_state = text_input.text_field_state().unwrap();
+        assert_eq!(text_field_state.selection, Selection::caret(2));
+
+        let mut edit = |text_input: &mut TextInput<String>,
+                        text: &str,
+                        composition_range: Option<Range<usize>>| {
+            let mut widget_events = vec![];
+            text_input.handle_event(
+                &Event::TextFieldEdited(TextFieldState {
+                    composition_range,
+                    selection: Selection::caret(text.len()),
+                    text: text.to_string(),
+                    ..text_field_state.clone()
+                }),
+                &mut shared_state,
+                &mut widget_id_provider,
+                &mut widget_events,
+            );
+            widget_events
+        };
+
+        // The preedit text is shown, but not reported as value.
+        let widget_events = edit(&mut text_input, "abに", Some(2..5));
+        assert_eq!(text_input.value().unwrap().as_text(), Some("abに"));
+        assert_eq!(
+            text_input.text_field_state().unwrap().composition_range,
+            Some(2..5)
+        );
+        assert!(widget_events.is_empty());
+
+        // The committed text is reported.
+        let widget_events = edit(&mut text_input, "ab日", None);
+        assert!(matches!(
+            widget_events.as_slice(),
+            [WidgetEvent::AppEvent(text)] if text == "ab日"
+        ));
+
+        // Undo skips the preedit text.
+        text_input.handle_event(
+            &Event::Undo,
+            &mut shared_state,
+            &mut widget_id_provider,
+            &mut vec![],
+        );
+        assert_eq!(text_input.value().unwrap().as_text(), Some("ab"));
+    }
+
+    #[test]
+    fn test_mapped_event_observation() {
+        let mut shared_state = PietSharedState::new();
+        let mut widget_id_provider = WidgetIdProvider::new();
+        let mut widget_events = vec![];
+
+        let mut text_input: TextInput<String> = TextInput::new(
+            0,
+            Stroke::default(),
+            shared_state.piet_text(),
+            Default::default(),
+            "abc".to_string(),
+            100.0,
+            Color::rgb8(0, 0, 0),
+            Color::rgb8(0, 0, 0),
+        );
+        text_input.add_event_observation(
+            1,
+            WidgetEventType::ValueChanged,
+            WidgetEventObservation::Mapper(Rc::new(|widget_event_data| {
+                widget_event_data.value.as_ref().unwrap().to_text().unwrap()
+            })),
+        );
+
+        // The mapper gets the new text.
+        text_input.handle_event(
+            &Event::KeyDown(KeyEvent {
+                key: KbKey::Character("d".to_string()),
+                ..Default::default()
+            }),
+            &mut shared_state,
+            &mut widget_id_provider,
+            &mut widget_events,
+        );
+        assert!(matches!(
+            widget_events.as_slice(),
+            [WidgetEvent::AppEvent(text)] if text == "abcd"
+        ));
     }
 }
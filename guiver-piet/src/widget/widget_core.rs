@@ -1,16 +1,31 @@
 use crate::stroke::Stroke;
-use guiver::{Rectangle, SizeConstraints, WidgetEvent, WidgetEventType, WidgetId};
+use guiver::{
+    EventObservationHandle, LayoutDirection, Rectangle, SizeConstraints, WidgetEventObservation,
+    WidgetEventType, WidgetId,
+};
 use std::collections::HashMap;
 
 /// Common data and functionality for widgets.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct WidgetCore<APP_EVENT: Clone> {
+    /// The layout direction of the nearest ancestor with a layout direction, or the window's. This
+    /// is set by the parent widget.
+    pub ancestor_layout_direction: LayoutDirection,
     pub debug_rendering: bool,
     pub debug_rendering_stroke: Stroke,
+    /// Whether one of the widget's ancestors is disabled. This is set by the parent widget.
+    pub has_disabled_ancestor: bool,
+    /// Whether the widget itself is disabled.
+    pub is_disabled: bool,
     pub is_hidden: bool,
+    /// The widget's own layout direction. `None` uses the ancestor's.
+    pub layout_direction: Option<LayoutDirection>,
     pub rectangle: Rectangle,
     pub size_constraints: SizeConstraints,
-    widget_event_observation: HashMap<WidgetEventType, WidgetEvent<APP_EVENT>>,
+    /// The event observations with their handles per widget event type, in the order they were
+    /// added.
+    widget_event_observations:
+        HashMap<WidgetEventType, Vec<(EventObservationHandle, WidgetEventObservation<APP_EVENT>)>>,
     pub widget_id: WidgetId,
 }
 
@@ -18,36 +33,145 @@ impl<APP_EVENT: Clone> WidgetCore<APP_EVENT> {
     ///
     pub fn new(widget_id: WidgetId, debug_rendering_stroke: Stroke) -> Self {
         WidgetCore {
+            ancestor_layout_direction: LayoutDirection::LeftToRight,
             debug_rendering: false,
             debug_rendering_stroke,
+            has_disabled_ancestor: false,
+            is_disabled: false,
             is_hidden: false,
+            layout_direction: None,
             rectangle: Rectangle::default(),
             size_constraints: SizeConstraints::unbounded(),
-            widget_event_observation: HashMap::new(),
+            widget_event_observations: HashMap::new(),
             widget_id,
         }
     }
 
-    ///
+    /// Adds an observation of the given widget event type. A widget event type can have several
+    /// observations. An observation with the same handle, even of another widget event type, is
+    /// replaced, so that the handle always refers to one observation.
     pub fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     ) {
-        self.widget_event_observation
-            .insert(widget_event_type, widget_event);
+        // An observation with the same handle is replaced.
+        self.remove_event_observation(event_observation_handle);
+
+        self.widget_event_observations
+            .entry(widget_event_type)
+            .or_default()
+            .push((event_observation_handle, widget_event_observation));
     }
 
     ///
-    pub fn event_observation(
+    pub fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>> {
-        self.widget_event_observation.get(widget_event_type)
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>> {
+        self.widget_event_observations
+            .get(widget_event_type)
+            .map(|event_observations| {
+                event_observations
+                    .iter()
+                    .map(|(_, widget_event_observation)| widget_event_observation)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the widget itself or one of its ancestors is disabled.
+    pub fn is_disabled(&self) -> bool {
+        self.is_disabled || self.has_disabled_ancestor
+    }
+
+    /// Returns the widget's own layout direction or, if it has none, the ancestor's.
+    pub fn layout_direction(&self) -> LayoutDirection {
+        self.layout_direction
+            .unwrap_or(self.ancestor_layout_direction)
     }
 
     ///
-    pub fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType) {
-        self.widget_event_observation.remove(widget_event_type);
+    pub fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+        // Iterate over the event observations per widget event type.
+        for event_observations in self.widget_event_observations.values_mut() {
+            event_observations.retain(|(handle, _)| *handle != event_observation_handle);
+        }
+
+        self.widget_event_observations
+            .retain(|_, event_observations| !event_observations.is_empty());
+    }
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the application events of the core's observations of the given widget event type.
+    fn app_events(
+        widget_core: &mut WidgetCore<usize>,
+        widget_event_type: &WidgetEventType,
+    ) -> Vec<usize> {
+        widget_core
+            .event_observations(widget_event_type)
+            .into_iter()
+            .map(|widget_event_observation| match widget_event_observation {
+                WidgetEventObservation::AppEvent(app_event) => *app_event,
+                WidgetEventObservation::Mapper(_) => panic!("The observation has no mapper"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_event_observations() {
+        let mut widget_core: WidgetCore<usize> = WidgetCore::new(0, Stroke::default());
+
+        // Two observations of the same widget event type.
+        widget_core.add_event_observation(
+            1,
+            WidgetEventType::Clicked,
+            WidgetEventObservation::AppEvent(10),
+        );
+        widget_core.add_event_observation(
+            2,
+            WidgetEventType::Clicked,
+            WidgetEventObservation::AppEvent(20),
+        );
+        assert_eq!(
+            app_events(&mut widget_core, &WidgetEventType::Clicked),
+            vec![10, 20]
+        );
+
+        // Removing one of them keeps the other.
+        widget_core.remove_event_observation(1);
+        assert_eq!(
+            app_events(&mut widget_core, &WidgetEventType::Clicked),
+            vec![20]
+        );
+
+        // Adding an observation with an existing handle replaces the observation, even of another
+        // widget event type.
+        widget_core.add_event_observation(
+            2,
+            WidgetEventType::Clicked,
+            WidgetEventObservation::AppEvent(30),
+        );
+        assert_eq!(
+            app_events(&mut widget_core, &WidgetEventType::Clicked),
+            vec![30]
+        );
+        widget_core.add_event_observation(
+            2,
+            WidgetEventType::Submitted,
+            WidgetEventObservation::AppEvent(40),
+        );
+        assert!(app_events(&mut widget_core, &WidgetEventType::Clicked).is_empty());
+        assert_eq!(
+            app_events(&mut widget_core, &WidgetEventType::Submitted),
+            vec![40]
+        );
     }
 }
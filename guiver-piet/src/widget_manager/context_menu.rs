@@ -0,0 +1,614 @@
+use crate::font::Font;
+use crate::shared_state::PietSharedState;
+use crate::style::Style;
+use crate::Event;
+use druid_shell::kurbo::{Line, Rect};
+use druid_shell::piet::{Color, Piet, PietTextLayout, RenderContext, TextLayout};
+use druid_shell::{piet, KbKey};
+use guiver::{MenuItem, Point, Rectangle, Size};
+
+/// The outcome of a context menu handling an event.
+pub(crate) enum ContextMenuEvent<APP_EVENT> {
+    /// The context menu was closed without a selection.
+    Closed,
+    /// The context menu handled the event and stays open.
+    Handled,
+    /// A menu item was selected. This closes the context menu.
+    Selected(APP_EVENT),
+}
+
+/// The text layouts of a menu item.
+#[derive(Clone)]
+struct MenuItemTextLayouts {
+    check_mark: Option<PietTextLayout>,
+    label: PietTextLayout,
+    /// The shortcut text or the submenu indicator.
+    suffix: Option<PietTextLayout>,
+}
+
+/// An open menu, i.e. the context menu itself or one of its submenus.
+#[derive(Clone)]
+struct MenuLevel<APP_EVENT> {
+    /// The width reserved for check marks left of the labels.
+    check_mark_width: f64,
+    highlighted_index: Option<usize>,
+    item_rectangles: Vec<Rectangle>,
+    item_text_layouts: Vec<Option<MenuItemTextLayouts>>,
+    menu_items: Vec<MenuItem<APP_EVENT>>,
+    rectangle: Rectangle,
+}
+
+impl<APP_EVENT> MenuLevel<APP_EVENT> {
+    /// Returns the index of the menu item at the given position.
+    fn item_index_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.item_rectangles
+            .iter()
+            .position(|item_rectangle| item_rectangle.contains(x, y))
+    }
+
+    /// Returns the index of the next selectable menu item after (or before) the highlighted one,
+    /// wrapping around at the ends.
+    fn next_selectable_index(&self, forward: bool) -> Option<usize> {
+        let number_of_menu_items = self.menu_items.len();
+        let mut index = self.highlighted_index;
+
+        for _ in 0..number_of_menu_items {
+            let next_index = match index {
+                None if forward => 0,
+                None => number_of_menu_items - 1,
+                Some(index) if forward => (index + 1) % number_of_menu_items,
+                Some(index) => (index + number_of_menu_items - 1) % number_of_menu_items,
+            };
+
+            // The menu item can be highlighted.
+            if self.menu_items[next_index].is_selectable() {
+                return Some(next_index);
+            }
+
+            index = Some(next_index);
+        }
+
+        None
+    }
+}
+
+/// A context menu, painted on top of the widgets. While it is open, it handles all events.
+#[derive(Clone)]
+pub(crate) struct ContextMenu<APP_EVENT> {
+    fill_color: Color,
+    font: Font,
+    font_dimmed: Font,
+    frame_color: Color,
+    highlight_color: Color,
+    /// The context menu itself, followed by its open submenus.
+    menu_levels: Vec<MenuLevel<APP_EVENT>>,
+    padding_horizontal: f64,
+    padding_vertical: f64,
+    separator_height: f64,
+    suffix_spacing: f64,
+    window_size: Size,
+}
+
+impl<APP_EVENT: Clone> ContextMenu<APP_EVENT> {
+    ///
+    pub(crate) fn new(
+        menu_items: Vec<MenuItem<APP_EVENT>>,
+        origin: Point,
+        window_size: Size,
+        style: &Style,
+        shared_state: &mut PietSharedState,
+    ) -> Self {
+        let mut font_dimmed = style.font.clone();
+        font_dimmed.font_color = style.frame_color;
+
+        let mut context_menu = ContextMenu {
+            fill_color: Color::rgb8(40, 40, 40),
+            font: style.font.clone(),
+            font_dimmed,
+            frame_color: style.frame_color,
+            highlight_color: Color::rgb8(70, 70, 70),
+            menu_levels: vec![],
+            padding_horizontal: 8.0,
+            padding_vertical: 4.0,
+            separator_height: 9.0,
+            suffix_spacing: 24.0,
+            window_size,
+        };
+
+        context_menu.open_menu_level(menu_items, origin, None, shared_state);
+
+        context_menu
+    }
+
+    ///
+    pub(crate) fn handle_event(
+        &mut self,
+        event: &Event,
+        shared_state: &mut PietSharedState,
+    ) -> ContextMenuEvent<APP_EVENT> {
+        match event {
+            Event::KeyDown(key_event) => match &key_event.key {
+                KbKey::ArrowDown => self.highlight_next_item(true),
+                KbKey::ArrowUp => self.highlight_next_item(false),
+                KbKey::ArrowLeft if self.menu_levels.len() > 1 => {
+                    // Close the innermost submenu.
+                    self.menu_levels.pop();
+                }
+                KbKey::ArrowRight => self.open_highlighted_submenu(true, shared_state),
+                KbKey::End => {
+                    let menu_level = self.menu_levels.last_mut().unwrap();
+                    menu_level.highlighted_index = None;
+                    menu_level.highlighted_index = menu_level.next_selectable_index(false);
+                }
+                KbKey::Enter => return self.select_highlighted_item(shared_state),
+                KbKey::Escape => {
+                    // Close the innermost submenu.
+                    if self.menu_levels.len() > 1 {
+                        self.menu_levels.pop();
+                    }
+                    // Close the context menu.
+                    else {
+                        return ContextMenuEvent::Closed;
+                    }
+                }
+                KbKey::Home => {
+                    let menu_level = self.menu_levels.last_mut().unwrap();
+                    menu_level.highlighted_index = None;
+                    menu_level.highlighted_index = menu_level.next_selectable_index(true);
+                }
+                KbKey::Character(string) if string == " " => {
+                    return self.select_highlighted_item(shared_state);
+                }
+                _ => {}
+            },
+            // The mouse is down outside of the context menu.
+            Event::MouseDown(mouse_event)
+                if self
+                    .menu_level_index_at(mouse_event.pos.x, mouse_event.pos.y)
+                    .is_none() =>
+            {
+                return ContextMenuEvent::Closed;
+            }
+            Event::MouseMove(mouse_event) => {
+                // The mouse moved within a menu.
+                if let Some(menu_level_index) =
+                    self.menu_level_index_at(mouse_event.pos.x, mouse_event.pos.y)
+                {
+                    let menu_level = &self.menu_levels[menu_level_index];
+                    let highlighted_index = menu_level
+                        .item_index_at(mouse_event.pos.x, mouse_event.pos.y)
+                        .filter(|index| menu_level.menu_items[*index].is_selectable());
+
+                    // The highlighted menu item changed.
+                    if highlighted_index != menu_level.highlighted_index {
+                        // Close the menu's submenus.
+                        self.menu_levels.truncate(menu_level_index + 1);
+                        self.menu_levels[menu_level_index].highlighted_index = highlighted_index;
+
+                        // Hovering over a submenu item opens the submenu.
+                        self.open_highlighted_submenu(false, shared_state);
+                    }
+                }
+            }
+            Event::MouseUp(mouse_event) => {
+                // Only the left mouse button selects menu items.
+                if !mouse_event.button.is_left() {
+                    return ContextMenuEvent::Handled;
+                }
+
+                // The mouse is up within a menu.
+                if let Some(menu_level_index) =
+                    self.menu_level_index_at(mouse_event.pos.x, mouse_event.pos.y)
+                {
+                    // The mouse is up over a menu item.
+                    if let Some(index) = self.menu_levels[menu_level_index]
+                        .item_index_at(mouse_event.pos.x, mouse_event.pos.y)
+                    {
+                        // The menu item is an enabled action.
+                        if let MenuItem::Action {
+                            app_event,
+                            is_enabled: true,
+                            ..
+                        } = &self.menu_levels[menu_level_index].menu_items[index]
+                        {
+                            return ContextMenuEvent::Selected(app_event.clone());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        ContextMenuEvent::Handled
+    }
+
+    /// Returns `true` if the context menu or one of its open submenus is at the given position.
+    pub(crate) fn contains(&self, x: f64, y: f64) -> bool {
+        self.menu_level_index_at(x, y).is_some()
+    }
+
+    /// Highlights the next (or previous) selectable item in the innermost menu.
+    fn highlight_next_item(&mut self, forward: bool) {
+        let menu_level = self.menu_levels.last_mut().unwrap();
+        menu_level.highlighted_index = menu_level.next_selectable_index(forward);
+    }
+
+    /// Returns the index of the innermost menu at the given position.
+    fn menu_level_index_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.menu_levels
+            .iter()
+            .rposition(|menu_level| menu_level.rectangle.contains(x, y))
+    }
+
+    /// Opens the submenu of the highlighted item in the innermost menu, if it is one.
+    fn open_highlighted_submenu(
+        &mut self,
+        highlight_first_item: bool,
+        shared_state: &mut PietSharedState,
+    ) {
+        let menu_level = self.menu_levels.last().unwrap();
+
+        // There is a highlighted menu item.
+        let highlighted_index = if let Some(highlighted_index) = menu_level.highlighted_index {
+            highlighted_index
+        }
+        // There is no highlighted menu item.
+        else {
+            return;
+        };
+
+        // The highlighted menu item is an enabled submenu.
+        if let MenuItem::Submenu {
+            is_enabled: true,
+            menu_items,
+            ..
+        } = &menu_level.menu_items[highlighted_index]
+        {
+            let menu_items = menu_items.clone();
+
+            // Open the submenu right of the menu, aligned with the submenu item.
+            let origin = Point::new(
+                menu_level.rectangle.x1,
+                menu_level.item_rectangles[highlighted_index].y0 - self.padding_vertical,
+            );
+            let x1_alternative = menu_level.rectangle.x0;
+
+            self.open_menu_level(menu_items, origin, Some(x1_alternative), shared_state);
+
+            if highlight_first_item {
+                self.highlight_next_item(true);
+            }
+        }
+    }
+
+    /// Lays out the given menu items and opens them as innermost menu. If the menu does not fit
+    /// right of `origin`, it is placed left of `x1_alternative`, if given.
+    fn open_menu_level(
+        &mut self,
+        menu_items: Vec<MenuItem<APP_EVENT>>,
+        origin: Point,
+        x1_alternative: Option<f64>,
+        shared_state: &mut PietSharedState,
+    ) {
+        let mut check_mark_width: f64 = 0.0;
+        let mut item_text_layouts = vec![];
+        let mut label_width: f64 = 0.0;
+        let mut suffix_width: f64 = 0.0;
+        let mut text_height: f64 = 0.0;
+
+        // Create the text layouts.
+        for menu_item in &menu_items {
+            let text_layouts = match menu_item {
+                MenuItem::Action {
+                    is_checked,
+                    is_enabled,
+                    label,
+                    shortcut,
+                    ..
+                } => {
+                    let font = if *is_enabled {
+                        &self.font
+                    } else {
+                        &self.font_dimmed
+                    };
+
+                    Some(MenuItemTextLayouts {
+                        check_mark: if *is_checked {
+                            Some(font.text_layout(shared_state.piet_text(), "✓"))
+                        } else {
+                            None
+                        },
+                        label: font.text_layout(shared_state.piet_text(), label.clone()),
+                        suffix: shortcut.as_ref().map(|shortcut| {
+                            self.font_dimmed
+                                .text_layout(shared_state.piet_text(), shortcut.to_string())
+                        }),
+                    })
+                }
+                MenuItem::Separator => None,
+                MenuItem::Submenu {
+                    is_enabled, label, ..
+                } => {
+                    let font = if *is_enabled {
+                        &self.font
+                    } else {
+                        &self.font_dimmed
+                    };
+
+                    Some(MenuItemTextLayouts {
+                        check_mark: None,
+                        label: font.text_layout(shared_state.piet_text(), label.clone()),
+                        suffix: Some(font.text_layout(shared_state.piet_text(), "▸")),
+                    })
+                }
+            };
+
+            if let Some(text_layouts) = &text_layouts {
+                if let Some(check_mark) = &text_layouts.check_mark {
+                    check_mark_width =
+                        check_mark_width.max(check_mark.size().width + self.padding_horizontal);
+                }
+
+                label_width = label_width.max(text_layouts.label.size().width);
+                text_height = text_height.max(text_layouts.label.size().height);
+
+                if let Some(suffix) = &text_layouts.suffix {
+                    suffix_width = suffix_width.max(suffix.size().width);
+                }
+            }
+
+            item_text_layouts.push(text_layouts);
+        }
+
+        let item_height = text_height + 2.0 * self.padding_vertical;
+
+        // Determine the menu size.
+        let mut width = check_mark_width + label_width + 2.0 * self.padding_horizontal;
+
+        if suffix_width > 0.0 {
+            width += self.suffix_spacing + suffix_width;
+        }
+
+        let mut height = 2.0 * self.padding_vertical;
+
+        for menu_item in &menu_items {
+            height += match menu_item {
+                MenuItem::Separator => self.separator_height,
+                _ => item_height,
+            };
+        }
+
+        // Keep the menu within the window.
+        let mut x = origin.x;
+
+        if x + width > self.window_size.width {
+            x = x1_alternative.unwrap_or(self.window_size.width) - width;
+        }
+
+        let x = x.max(0.0);
+        let y = origin.y.min(self.window_size.height - height).max(0.0);
+
+        // Determine the menu item rectangles.
+        let mut item_rectangles = vec![];
+        let mut item_y = y + self.padding_vertical;
+
+        for menu_item in &menu_items {
+            let height = match menu_item {
+                MenuItem::Separator => self.separator_height,
+                _ => item_height,
+            };
+
+            item_rectangles.push(Rectangle::new(x, item_y, x + width, item_y + height));
+            item_y += height;
+        }
+
+        self.menu_levels.push(MenuLevel {
+            check_mark_width,
+            highlighted_index: None,
+            item_rectangles,
+            item_text_layouts,
+            menu_items,
+            rectangle: Rectangle::new(x, y, x + width, y + height),
+        });
+    }
+
+    ///
+    pub(crate) fn paint(&self, piet: &mut Piet) -> Result<(), piet::Error> {
+        // Iterate over the context menu and its open submenus.
+        for menu_level in &self.menu_levels {
+            let menu_shape = Rect::new(
+                menu_level.rectangle.x0,
+                menu_level.rectangle.y0,
+                menu_level.rectangle.x1,
+                menu_level.rectangle.y1,
+            );
+
+            piet.fill(menu_shape, &self.fill_color);
+            piet.stroke(menu_shape, &self.frame_color, 1.0);
+
+            // Iterate over the menu items.
+            for (index, item_rectangle) in menu_level.item_rectangles.iter().enumerate() {
+                // The menu item is highlighted.
+                if menu_level.highlighted_index == Some(index) {
+                    piet.fill(
+                        Rect::new(
+                            item_rectangle.x0,
+                            item_rectangle.y0,
+                            item_rectangle.x1,
+                            item_rectangle.y1,
+                        ),
+                        &self.highlight_color,
+                    );
+                }
+
+                // The menu item has text.
+                if let Some(text_layouts) = &menu_level.item_text_layouts[index] {
+                    // The menu item is checked.
+                    if let Some(check_mark) = &text_layouts.check_mark {
+                        piet.draw_text(
+                            check_mark,
+                            (
+                                item_rectangle.x0 + self.padding_horizontal,
+                                item_rectangle.y0 + self.padding_vertical,
+                            ),
+                        );
+                    }
+
+                    piet.draw_text(
+                        &text_layouts.label,
+                        (
+                            item_rectangle.x0
+                                + self.padding_horizontal
+                                + menu_level.check_mark_width,
+                            item_rectangle.y0 + self.padding_vertical,
+                        ),
+                    );
+
+                    // Right-align the shortcut text or submenu indicator.
+                    if let Some(suffix) = &text_layouts.suffix {
+                        piet.draw_text(
+                            suffix,
+                            (
+                                item_rectangle.x1 - self.padding_horizontal - suffix.size().width,
+                                item_rectangle.y0 + self.padding_vertical,
+                            ),
+                        );
+                    }
+                }
+                // The menu item is a separator.
+                else {
+                    let y = 0.5 * (item_rectangle.y0 + item_rectangle.y1);
+
+                    piet.stroke(
+                        Line::new(
+                            (item_rectangle.x0 + self.padding_horizontal, y),
+                            (item_rectangle.x1 - self.padding_horizontal, y),
+                        ),
+                        &self.frame_color,
+                        1.0,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Selects the highlighted item in the innermost menu.
+    fn select_highlighted_item(
+        &mut self,
+        shared_state: &mut PietSharedState,
+    ) -> ContextMenuEvent<APP_EVENT> {
+        let menu_level = self.menu_levels.last().unwrap();
+
+        match menu_level
+            .highlighted_index
+            .map(|index| &menu_level.menu_items[index])
+        {
+            Some(MenuItem::Action {
+                app_event,
+                is_enabled: true,
+                ..
+            }) => ContextMenuEvent::Selected(app_event.clone()),
+            Some(MenuItem::Submenu { .. }) => {
+                self.open_highlighted_submenu(true, shared_state);
+                ContextMenuEvent::Handled
+            }
+            _ => ContextMenuEvent::Handled,
+        }
+    }
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::shared_state::PietSharedState;
+    use crate::style::Style;
+    use crate::widget_manager::context_menu::{ContextMenu, ContextMenuEvent};
+    use crate::Event;
+    use druid_shell::{KbKey, KeyEvent};
+    use guiver::{MenuItem, Point, Shortcut, Size};
+
+    fn key_down(key: KbKey) -> Event {
+        Event::KeyDown(KeyEvent {
+            key,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_handle_event() {
+        let mut shared_state = PietSharedState::new();
+
+        let mut context_menu = ContextMenu::new(
+            vec![
+                MenuItem::Action {
+                    app_event: 1,
+                    is_checked: false,
+                    is_enabled: true,
+                    label: "Copy".to_string(),
+                    shortcut: Some(Shortcut::primary("c")),
+                },
+                MenuItem::Separator,
+                MenuItem::Action {
+                    app_event: 2,
+                    is_checked: false,
+                    is_enabled: false,
+                    label: "Paste".to_string(),
+                    shortcut: None,
+                },
+                MenuItem::Submenu {
+                    is_enabled: true,
+                    label: "More".to_string(),
+                    menu_items: vec![MenuItem::Action {
+                        app_event: 3,
+                        is_checked: false,
+                        is_enabled: true,
+                        label: "Inspect".to_string(),
+                        shortcut: None,
+                    }],
+                },
+            ],
+            Point::new(10.0, 10.0),
+            Size::new(400.0, 300.0),
+            &Style::default(),
+            &mut shared_state,
+        );
+
+        // Nothing is highlighted yet.
+        assert!(matches!(
+            context_menu.handle_event(&key_down(KbKey::Enter), &mut shared_state),
+            ContextMenuEvent::Handled
+        ));
+
+        // The separator and the disabled item are skipped.
+        context_menu.handle_event(&key_down(KbKey::ArrowDown), &mut shared_state);
+        context_menu.handle_event(&key_down(KbKey::ArrowDown), &mut shared_state);
+        assert_eq!(context_menu.menu_levels[0].highlighted_index, Some(3));
+
+        // Arrow down wraps around.
+        context_menu.handle_event(&key_down(KbKey::ArrowDown), &mut shared_state);
+        assert_eq!(context_menu.menu_levels[0].highlighted_index, Some(0));
+
+        // Arrow up wraps around.
+        context_menu.handle_event(&key_down(KbKey::ArrowUp), &mut shared_state);
+        assert_eq!(context_menu.menu_levels[0].highlighted_index, Some(3));
+
+        // Open the submenu and select its item.
+        context_menu.handle_event(&key_down(KbKey::ArrowRight), &mut shared_state);
+        assert_eq!(context_menu.menu_levels.len(), 2);
+        assert!(matches!(
+            context_menu.handle_event(&key_down(KbKey::Enter), &mut shared_state),
+            ContextMenuEvent::Selected(3)
+        ));
+
+        // Escape closes the submenu first, then the context menu.
+        context_menu.handle_event(&key_down(KbKey::Escape), &mut shared_state);
+        assert_eq!(context_menu.menu_levels.len(), 1);
+        assert!(matches!(
+            context_menu.handle_event(&key_down(KbKey::Escape), &mut shared_state),
+            ContextMenuEvent::Closed
+        ));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::shared_state::PietSharedState;
+use crate::style::Style;
+use druid_shell::kurbo::{Rect, RoundedRect};
+use druid_shell::piet;
+use druid_shell::piet::{Color, Piet, PietTextLayout, RenderContext, TextLayout};
+use guiver::{DragPayload, Point, Rectangle, WidgetId};
+
+/// A payload being dragged from a widget. Its preview is painted next to the pointer, on top of the
+/// widgets, and the drop target under the pointer that accepts it is highlighted.
+#[derive(Clone)]
+pub(crate) struct Drag {
+    drag_payload: DragPayload,
+    /// The ID and rectangle of the drop target under the pointer that accepts the payload.
+    drop_target: Option<(WidgetId, Rectangle)>,
+    fill_color: Color,
+    frame_color: Color,
+    highlight_color: Color,
+    label_text_layout: PietTextLayout,
+    padding: f64,
+    /// The position of the pointer.
+    position: Point,
+    source_widget_id: WidgetId,
+}
+
+impl Drag {
+    ///
+    pub(crate) fn new(
+        drag_payload: DragPayload,
+        source_widget_id: WidgetId,
+        position: Point,
+        style: &Style,
+        shared_state: &mut PietSharedState,
+    ) -> Self {
+        let label_text_layout = style
+            .font
+            .text_layout(shared_state.piet_text(), drag_payload.label().to_string());
+
+        Drag {
+            drag_payload,
+            drop_target: None,
+            fill_color: Color::rgba8(40, 40, 40, 200),
+            frame_color: style.frame_color,
+            highlight_color: style.accent_color,
+            label_text_layout,
+            padding: 4.0,
+            position,
+            source_widget_id,
+        }
+    }
+
+    ///
+    pub(crate) fn drag_payload(&self) -> &DragPayload {
+        &self.drag_payload
+    }
+
+    ///
+    pub(crate) fn drop_target_widget_id(&self) -> Option<WidgetId> {
+        self.drop_target.map(|(widget_id, _)| widget_id)
+    }
+
+    /// Paints the drop target highlight and the drag preview.
+    pub(crate) fn paint(&self, piet: &mut Piet) -> Result<(), piet::Error> {
+        // A drop target accepts the payload.
+        if let Some((_, rectangle)) = &self.drop_target {
+            piet.stroke(
+                Rect::new(rectangle.x0, rectangle.y0, rectangle.x1, rectangle.y1),
+                &self.highlight_color,
+                2.0,
+            );
+        }
+
+        // Paint the preview below and to the right of the pointer, so that it does not hide the
+        // drop position.
+        let label_size = self.label_text_layout.size();
+        let preview_shape = RoundedRect::new(
+            self.position.x + 12.0,
+            self.position.y + 12.0,
+            self.position.x + 12.0 + label_size.width + 2.0 * self.padding,
+            self.position.y + 12.0 + label_size.height + 2.0 * self.padding,
+            3.0,
+        );
+
+        piet.fill(preview_shape, &self.fill_color);
+        piet.stroke(preview_shape, &self.frame_color, 1.0);
+        piet.draw_text(
+            &self.label_text_layout,
+            (
+                preview_shape.rect().x0 + self.padding,
+                preview_shape.rect().y0 + self.padding,
+            ),
+        );
+
+        Ok(())
+    }
+
+    ///
+    pub(crate) fn set_drop_target(&mut self, drop_target: Option<(WidgetId, Rectangle)>) {
+        self.drop_target = drop_target;
+    }
+
+    ///
+    pub(crate) fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    ///
+    pub(crate) fn source_widget_id(&self) -> WidgetId {
+        self.source_widget_id
+    }
+}
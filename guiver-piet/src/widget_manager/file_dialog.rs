@@ -0,0 +1,56 @@
+use druid_shell::FileSpec;
+
+/// Converts the given backend-neutral file dialog options to druid-shell ones.
+pub(crate) fn file_dialog_options(
+    file_dialog_options: guiver::FileDialogOptions,
+) -> druid_shell::FileDialogOptions {
+    let mut druid_shell_file_dialog_options = druid_shell::FileDialogOptions::new();
+
+    // File types are given.
+    if !file_dialog_options.file_types.is_empty() {
+        let file_specs = file_dialog_options
+            .file_types
+            .into_iter()
+            .map(|file_type| {
+                // druid-shell's file specs need static strings. Leaking them is acceptable, since
+                // there are few file types and they are small.
+                let extensions: Vec<&'static str> = file_type
+                    .extensions
+                    .into_iter()
+                    .map(|extension| &*Box::leak(extension.into_boxed_str()))
+                    .collect();
+
+                FileSpec::new(
+                    Box::leak(file_type.name.into_boxed_str()),
+                    Box::leak(extensions.into_boxed_slice()),
+                )
+            })
+            .collect();
+
+        druid_shell_file_dialog_options = druid_shell_file_dialog_options.allowed_types(file_specs);
+    }
+
+    // A default file name is given.
+    if let Some(default_file_name) = file_dialog_options.default_file_name {
+        druid_shell_file_dialog_options =
+            druid_shell_file_dialog_options.default_name(default_file_name);
+    }
+
+    // Several files can be chosen.
+    if file_dialog_options.has_multiple_selection {
+        druid_shell_file_dialog_options = druid_shell_file_dialog_options.multi_selection();
+    }
+
+    // A starting directory is given.
+    if let Some(starting_directory) = file_dialog_options.starting_directory {
+        druid_shell_file_dialog_options =
+            druid_shell_file_dialog_options.starting_directory(starting_directory);
+    }
+
+    // A title is given.
+    if let Some(title) = file_dialog_options.title {
+        druid_shell_file_dialog_options = druid_shell_file_dialog_options.title(title);
+    }
+
+    druid_shell_file_dialog_options
+}
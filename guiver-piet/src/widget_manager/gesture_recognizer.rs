@@ -0,0 +1,234 @@
+use crate::Event;
+use druid_shell::kurbo::Point;
+use druid_shell::MouseEvent;
+use guiver::{GestureThresholds, WidgetEventType};
+use std::time::Instant;
+
+/// A pointer press in progress.
+struct Press {
+    /// Whether the press became a drag.
+    is_dragging: bool,
+    /// Whether the press was recognized as a long press already.
+    is_long_press: bool,
+    /// The mouse event that started the press.
+    mouse_event: MouseEvent,
+    /// When the press started.
+    time: Instant,
+}
+
+/// Recognizes gestures, like double clicks, long presses and drags, from pointer events.
+pub(crate) struct GestureRecognizer {
+    ///
+    gesture_thresholds: GestureThresholds,
+    /// The time and position of the last click that could start a double click.
+    last_click: Option<(Instant, Point)>,
+    /// The pointer press in progress.
+    press: Option<Press>,
+}
+
+impl GestureRecognizer {
+    ///
+    pub(crate) fn new() -> Self {
+        GestureRecognizer {
+            gesture_thresholds: GestureThresholds::default(),
+            last_click: None,
+            press: None,
+        }
+    }
+
+    ///
+    pub(crate) fn gesture_thresholds(&self) -> &GestureThresholds {
+        &self.gesture_thresholds
+    }
+
+    /// Returns the gesture events that the given event at the given time completes. A left mouse
+    /// down can complete a double click, a mouse move can start and continue a drag, a left mouse up
+    /// can end a drag and a timer event can complete a long press.
+    pub(crate) fn handle_event(&mut self, event: &Event, now: Instant) -> Vec<Event> {
+        let mut gesture_events = vec![];
+
+        match event {
+            Event::MouseDown(mouse_event) if mouse_event.button.is_left() => {
+                match self.last_click {
+                    // The click is close enough to the last one in time and space.
+                    Some((time, position))
+                        if now.duration_since(time)
+                            <= self.gesture_thresholds.double_click_interval
+                            && position.distance(mouse_event.pos)
+                                <= self.gesture_thresholds.double_click_distance =>
+                    {
+                        gesture_events.push(Event::DoubleClicked(mouse_event.clone()));
+
+                        // A third click starts a new double click.
+                        self.last_click = None;
+                    }
+                    _ => self.last_click = Some((now, mouse_event.pos)),
+                }
+
+                self.press = Some(Press {
+                    is_dragging: false,
+                    is_long_press: false,
+                    mouse_event: mouse_event.clone(),
+                    time: now,
+                });
+            }
+            Event::MouseMove(mouse_event) => {
+                // The pointer is pressed.
+                if let Some(press) = &mut self.press {
+                    // The pointer moved far enough to start a drag.
+                    if !press.is_dragging
+                        && press.mouse_event.pos.distance(mouse_event.pos)
+                            >= self.gesture_thresholds.drag_distance
+                    {
+                        press.is_dragging = true;
+
+                        // A drag is no click.
+                        self.last_click = None;
+
+                        gesture_events.push(Event::DragStarted(press.mouse_event.clone()));
+                    }
+
+                    // The press is a drag.
+                    if press.is_dragging {
+                        gesture_events.push(Event::Dragged(mouse_event.clone()));
+                    }
+                }
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button.is_left() => {
+                // The press was a drag.
+                if let Some(Press {
+                    is_dragging: true, ..
+                }) = self.press.take()
+                {
+                    gesture_events.push(Event::DragEnded(mouse_event.clone()));
+                }
+            }
+            Event::Timer => {
+                // The pointer is pressed.
+                if let Some(press) = &mut self.press {
+                    // The pointer stayed pressed long enough without dragging.
+                    if !press.is_dragging
+                        && !press.is_long_press
+                        && now.duration_since(press.time)
+                            >= self.gesture_thresholds.long_press_duration
+                    {
+                        press.is_long_press = true;
+
+                        // A long press is no click.
+                        self.last_click = None;
+
+                        gesture_events.push(Event::LongPressed(press.mouse_event.clone()));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        gesture_events
+    }
+
+    ///
+    pub(crate) fn set_gesture_thresholds(&mut self, gesture_thresholds: GestureThresholds) {
+        self.gesture_thresholds = gesture_thresholds;
+    }
+}
+
+/// Returns the widget event type and the mouse event of the given gesture event.
+pub(crate) fn gesture(event: &Event) -> Option<(WidgetEventType, &MouseEvent)> {
+    match event {
+        Event::DoubleClicked(mouse_event) => Some((WidgetEventType::DoubleClicked, mouse_event)),
+        Event::DragEnded(mouse_event) => Some((WidgetEventType::DragEnded, mouse_event)),
+        Event::DragStarted(mouse_event) => Some((WidgetEventType::DragStarted, mouse_event)),
+        Event::Dragged(mouse_event) => Some((WidgetEventType::Dragged, mouse_event)),
+        Event::LongPressed(mouse_event) => Some((WidgetEventType::LongPressed, mouse_event)),
+        _ => None,
+    }
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::widget_manager::gesture_recognizer::GestureRecognizer;
+    use crate::Event;
+    use druid_shell::kurbo::{Point, Vec2};
+    use druid_shell::{MouseButton, MouseEvent};
+    use std::time::{Duration, Instant};
+
+    fn mouse_event(x: f64, y: f64) -> MouseEvent {
+        MouseEvent {
+            pos: Point::new(x, y),
+            buttons: Default::default(),
+            mods: Default::default(),
+            count: 1,
+            focus: false,
+            button: MouseButton::Left,
+            wheel_delta: Vec2::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_handle_event() {
+        let mut gesture_recognizer = GestureRecognizer::new();
+        let start = Instant::now();
+        let at = |milliseconds| start + Duration::from_millis(milliseconds);
+
+        // Two quick clicks at the same position are a double click.
+        let click = |gesture_recognizer: &mut GestureRecognizer, milliseconds| {
+            let mut gesture_events = gesture_recognizer
+                .handle_event(&Event::MouseDown(mouse_event(10.0, 10.0)), at(milliseconds));
+            gesture_events.extend(
+                gesture_recognizer
+                    .handle_event(&Event::MouseUp(mouse_event(10.0, 10.0)), at(milliseconds)),
+            );
+            gesture_events
+        };
+        assert!(click(&mut gesture_recognizer, 0).is_empty());
+        assert!(matches!(
+            click(&mut gesture_recognizer, 100).as_slice(),
+            [Event::DoubleClicked(_)]
+        ));
+
+        // Clicks too far apart in time are no double click.
+        assert!(click(&mut gesture_recognizer, 1000).is_empty());
+        assert!(click(&mut gesture_recognizer, 2000).is_empty());
+
+        // Moving the pressed pointer far enough starts a drag.
+        gesture_recognizer.handle_event(&Event::MouseDown(mouse_event(10.0, 10.0)), at(3000));
+        assert!(gesture_recognizer
+            .handle_event(&Event::MouseMove(mouse_event(11.0, 10.0)), at(3000))
+            .is_empty());
+        assert!(matches!(
+            gesture_recognizer
+                .handle_event(&Event::MouseMove(mouse_event(20.0, 10.0)), at(3000))
+                .as_slice(),
+            [Event::DragStarted(_), Event::Dragged(_)]
+        ));
+
+        // A drag is no long press.
+        assert!(gesture_recognizer
+            .handle_event(&Event::Timer, at(5000))
+            .is_empty());
+        assert!(matches!(
+            gesture_recognizer
+                .handle_event(&Event::MouseUp(mouse_event(20.0, 10.0)), at(5000))
+                .as_slice(),
+            [Event::DragEnded(_)]
+        ));
+
+        // Keeping the pointer pressed is a long press, but only once.
+        gesture_recognizer.handle_event(&Event::MouseDown(mouse_event(10.0, 10.0)), at(6000));
+        assert!(gesture_recognizer
+            .handle_event(&Event::Timer, at(6100))
+            .is_empty());
+        assert!(matches!(
+            gesture_recognizer
+                .handle_event(&Event::Timer, at(7000))
+                .as_slice(),
+            [Event::LongPressed(_)]
+        ));
+        assert!(gesture_recognizer
+            .handle_event(&Event::Timer, at(8000))
+            .is_empty());
+    }
+}
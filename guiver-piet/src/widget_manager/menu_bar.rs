@@ -0,0 +1,144 @@
+use druid_shell::{HotKey, KbKey, Menu, RawMods};
+use guiver::{MenuItem, Shortcut};
+use std::str::FromStr;
+
+/// Returns the hot key for the given shortcut, if its key is known.
+pub(crate) fn hot_key(shortcut: &Shortcut) -> Option<HotKey> {
+    let raw_mods = match (
+        shortcut.has_alt,
+        shortcut.has_ctrl,
+        shortcut.has_meta,
+        shortcut.has_shift,
+    ) {
+        (false, false, false, false) => RawMods::None,
+        (true, false, false, false) => RawMods::Alt,
+        (false, true, false, false) => RawMods::Ctrl,
+        (false, false, true, false) => RawMods::Meta,
+        (false, false, false, true) => RawMods::Shift,
+        (true, true, false, false) => RawMods::AltCtrl,
+        (true, false, true, false) => RawMods::AltMeta,
+        (true, false, false, true) => RawMods::AltShift,
+        (false, true, false, true) => RawMods::CtrlShift,
+        (false, true, true, false) => RawMods::CtrlMeta,
+        (false, false, true, true) => RawMods::MetaShift,
+        (true, true, true, false) => RawMods::AltCtrlMeta,
+        (true, true, false, true) => RawMods::AltCtrlShift,
+        (true, false, true, true) => RawMods::AltMetaShift,
+        (false, true, true, true) => RawMods::CtrlMetaShift,
+        (true, true, true, true) => RawMods::AltCtrlMetaShift,
+    };
+
+    // The key is a character.
+    let key = if shortcut.key.chars().count() == 1 {
+        KbKey::Character(shortcut.key.clone())
+    }
+    // The key is a key name like "F1" or "Enter".
+    else {
+        KbKey::from_str(&shortcut.key).ok()?
+    };
+
+    Some(HotKey::new(raw_mods, key))
+}
+
+/// Creates a native menu from the given menu items. The application events of the menu items are
+/// appended to `app_events`. Their indices are used as menu item IDs.
+pub(crate) fn menu<APP_EVENT: Clone>(
+    menu_items: &[MenuItem<APP_EVENT>],
+    app_events: &mut Vec<APP_EVENT>,
+) -> Menu {
+    let mut menu = Menu::new();
+
+    // Iterate over the menu items.
+    for menu_item in menu_items {
+        match menu_item {
+            MenuItem::Action {
+                app_event,
+                is_checked,
+                is_enabled,
+                label,
+                shortcut,
+            } => {
+                let hot_key = shortcut.as_ref().and_then(hot_key);
+
+                menu.add_item(
+                    app_events.len() as u32,
+                    label,
+                    hot_key.as_ref(),
+                    Some(*is_checked),
+                    *is_enabled,
+                );
+
+                app_events.push(app_event.clone());
+            }
+            MenuItem::Separator => menu.add_separator(),
+            MenuItem::Submenu {
+                is_enabled,
+                label,
+                menu_items,
+            } => {
+                menu.add_dropdown(self::menu(menu_items, app_events), label, *is_enabled);
+            }
+        }
+    }
+
+    menu
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::widget_manager::menu_bar::hot_key;
+    use druid_shell::{HotKey, KbKey, RawMods};
+    use guiver::Shortcut;
+
+    #[test]
+    fn test_hot_key() {
+        let hot_key_for = |shortcut: &str| hot_key(&shortcut.parse::<Shortcut>().unwrap());
+
+        assert_eq!(
+            hot_key_for("Ctrl+C"),
+            Some(HotKey::new(
+                RawMods::Ctrl,
+                KbKey::Character("c".to_string())
+            ))
+        );
+        assert_eq!(
+            hot_key_for("Alt+Shift+S"),
+            Some(HotKey::new(
+                RawMods::AltShift,
+                KbKey::Character("s".to_string())
+            ))
+        );
+        assert_eq!(
+            hot_key_for("Ctrl++"),
+            Some(HotKey::new(
+                RawMods::Ctrl,
+                KbKey::Character("+".to_string())
+            ))
+        );
+        assert_eq!(
+            hot_key_for("F5"),
+            Some(HotKey::new(RawMods::None, KbKey::F5))
+        );
+        assert_eq!(
+            hot_key_for("Meta+Enter"),
+            Some(HotKey::new(RawMods::Meta, KbKey::Enter))
+        );
+
+        // The primary modifier key depends on the platform.
+        let primary = if cfg!(target_os = "macos") {
+            RawMods::Meta
+        } else {
+            RawMods::Ctrl
+        };
+        assert_eq!(
+            hot_key_for("Primary+Q"),
+            Some(HotKey::new(primary, KbKey::Character("q".to_string())))
+        );
+
+        // Unknown modifier keys and keys.
+        assert!("Hyper+C".parse::<Shortcut>().is_err());
+        assert_eq!(hot_key_for("Ctrl+NoSuchKey"), None);
+    }
+}
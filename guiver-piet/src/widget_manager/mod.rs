@@ -1,46 +1,112 @@
+mod context_menu;
+mod drag_and_drop;
+mod file_dialog;
+mod gesture_recognizer;
+mod menu_bar;
+mod snapshot;
 mod widget_focus_order;
 
+use crate::event::modifiers;
+use crate::localization::{localized_text, LocaleFormat, MessageCatalogs};
 use crate::shared_state::PietSharedState;
 use crate::style::Style;
 use crate::widget::layout::{Center, Column, Expanded, Grid, Padding, Row, SizedBox};
 use crate::widget::{Button, Hyperlink, Placeholder, Text, TextInput};
+use crate::widget_manager::context_menu::{ContextMenu, ContextMenuEvent};
+use crate::widget_manager::drag_and_drop::Drag;
+use crate::widget_manager::gesture_recognizer::GestureRecognizer;
+use crate::widget_manager::snapshot::Snapshot;
 use crate::widget_manager::widget_focus_order::WidgetFocusOrder;
-use crate::{Event, PietWidget};
-use druid_shell::kurbo::Size;
-use druid_shell::piet::{Color, Piet};
-use druid_shell::{piet, Clipboard, KbKey, Modifiers, Region};
+use crate::{
+    Event, EventPhase, EventPropagation, FileDialogId, PietWidget, TextFieldState, WindowId,
+    WindowRequest, MAIN_WINDOW_ID,
+};
+use druid_shell::kurbo::{Affine, Size};
+use druid_shell::piet::{Color, Piet, RenderContext};
+use druid_shell::{kurbo, piet, Clipboard, KbKey, KeyEvent, MouseEvent, Region};
 pub use guiver::widget::r#type::WidgetType;
 pub use guiver::widget_manager::command::Command;
 use guiver::{
-    HorizontalAlignment, Point, Rectangle, SizeConstraints, WidgetError, WidgetEvent, WidgetId,
-    WidgetIdProvider,
+    AccessibilityNode, AccessibilityRole, DragPayload, DropData, DropTarget, FileDialogMapper,
+    HorizontalAlignment, LayoutDirection, MenuItem, Message, Point, Rectangle, Shortcut,
+    ShortcutAction, SizeConstraints, WidgetCommand, WidgetError, WidgetEvent, WidgetEventData,
+    WidgetEventObservation, WidgetEventType, WidgetId, WidgetIdProvider, WidgetValue,
+    WidgetValueKind,
 };
 use piet::PaintBrush;
-use std::any::Any;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::time::Instant;
+
+/// The largest zoom factor.
+const MAXIMUM_ZOOM_FACTOR: f64 = 4.0;
+
+/// The smallest zoom factor.
+const MINIMUM_ZOOM_FACTOR: f64 = 0.25;
+
+/// The factor by which the zoom factor changes per zoom shortcut.
+const ZOOM_STEP: f64 = 1.1;
 
 ///
 pub type WidgetBox<APP_EVENT> = Rc<RefCell<Box<dyn PietWidget<APP_EVENT>>>>;
 
 /// A widget manager that uses `Piet` and `druid-shell`.
 pub struct PietWidgetManager<APP_EVENT> {
-    /// The IDs of each widget's child widgets.
-    child_widget_ids_per_widget_id: HashMap<WidgetId, HashSet<WidgetId>>,
+    /// The accessibility names set with `Command::SetAccessibilityName`, per widget ID.
+    accessibility_name_per_widget_id: HashMap<WidgetId, String>,
+    /// The IDs of each widget's child widgets, in the order they were added. Container widgets paint
+    /// their child widgets in this order, so later ones are on top.
+    child_widget_ids_per_widget_id: HashMap<WidgetId, Vec<WidgetId>>,
+    /// The open context menu.
+    context_menu: Option<ContextMenu<APP_EVENT>>,
+    /// The context menu items per widget ID.
+    context_menu_items_per_widget_id: HashMap<WidgetId, Vec<MenuItem<APP_EVENT>>>,
+    /// The payload being dragged.
+    drag: Option<Drag>,
+    ///
+    drag_payload_per_widget_id: HashMap<WidgetId, DragPayload>,
+    ///
+    drop_target_per_widget_id: HashMap<WidgetId, DropTarget<APP_EVENT>>,
+    /// The mappers of the open file dialogs.
+    file_dialog_mappers: HashMap<FileDialogId, FileDialogMapper<APP_EVENT>>,
+    /// The IDs of the widgets whose widget trees are focus scopes.
+    focus_scope_widget_ids: HashSet<WidgetId>,
     /// The widget that has the focus.
     focused_widget: Option<WidgetBox<APP_EVENT>>,
+    /// Recognizes gestures like double clicks from pointer events.
+    gesture_recognizer: GestureRecognizer,
+    /// The window's layout direction, set with `Command::SetLayoutDirection`. Widgets inherit it
+    /// unless they or an ancestor override it.
+    layout_direction: LayoutDirection,
     /// The main widget that fills the whole window.
     main_widget: Option<WidgetBox<APP_EVENT>>,
+    /// The application events of the menu bar items. The indices are the menu item IDs.
+    menu_bar_app_events: Vec<APP_EVENT>,
+    /// The message catalogs and the current locale.
+    message_catalogs: MessageCatalogs,
+    /// The messages set with `Command::SetMessage` or created with localized widgets, per ID of the
+    /// widget whose value is the message's translation.
+    message_per_widget_id: HashMap<WidgetId, Message>,
+    /// The ID for the next file dialog.
+    next_file_dialog_id: FileDialogId,
     /// The IDs of each widget's parent widget.
     parent_widget_id_per_widget_id: HashMap<WidgetId, WidgetId>,
+    /// The ID of the widget that captured the pointer.
+    pointer_capture_widget_id: Option<WidgetId>,
     ///
     shared_state: PietSharedState,
+    /// The shortcut actions per scope. The scope `None` is global, the scope `Some(widget_id)` is
+    /// the widget tree with the given root widget.
+    shortcut_actions_per_scope:
+        HashMap<Option<WidgetId>, HashMap<Shortcut, ShortcutAction<APP_EVENT>>>,
     /// The size constraints. It is set in `resize()`, called by the window event handler for every
     /// window resize event so that the main widget fills the whole window.
     size_constraints: SizeConstraints,
     /// The widget style.
     style: Style,
+    /// The focused text widget's state that the window's text input was last told about.
+    text_field_state: Option<TextFieldState>,
     /// The widgets' tab/focus order.
     widget_focus_order: WidgetFocusOrder,
     /// The provide for widget IDs.
@@ -49,23 +115,147 @@ pub struct PietWidgetManager<APP_EVENT> {
     /// * to determine whether a widget with a given ID exists
     /// * to pass commands to widgets directly
     widgets: HashMap<WidgetId, WidgetBox<APP_EVENT>>,
+    /// The ID of the widget manager's window, set with `set_window_id()`.
+    window_id: WindowId,
+    /// The requests to the window, collected until `window_requests()` is called.
+    window_requests: Vec<WindowRequest>,
+    /// The window size, in window coordinates, i.e. without zoom.
+    window_size: Size,
 }
 
 impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
     ///
     pub fn new() -> Self {
+        // The built-in global shortcuts.
+        let global_shortcut_actions = HashMap::from([
+            (Shortcut::primary("a"), ShortcutAction::SelectAll),
+            (Shortcut::primary("c"), ShortcutAction::Copy),
+            (Shortcut::primary("v"), ShortcutAction::Paste),
+            (Shortcut::primary("x"), ShortcutAction::Cut),
+            (Shortcut::primary("z"), ShortcutAction::Undo),
+            (Shortcut::primary("+"), ShortcutAction::ZoomIn),
+            (
+                Shortcut {
+                    has_shift: true,
+                    ..Shortcut::primary("+")
+                },
+                ShortcutAction::ZoomIn,
+            ),
+            (Shortcut::primary("="), ShortcutAction::ZoomIn),
+            (Shortcut::primary("-"), ShortcutAction::ZoomOut),
+            (Shortcut::primary("0"), ShortcutAction::ResetZoom),
+        ]);
+
         PietWidgetManager {
+            accessibility_name_per_widget_id: HashMap::new(),
             child_widget_ids_per_widget_id: HashMap::new(),
+            context_menu: None,
+            context_menu_items_per_widget_id: HashMap::new(),
+            drag: None,
+            drag_payload_per_widget_id: HashMap::new(),
+            drop_target_per_widget_id: HashMap::new(),
+            file_dialog_mappers: HashMap::new(),
+            focus_scope_widget_ids: HashSet::new(),
             focused_widget: None,
+            gesture_recognizer: GestureRecognizer::new(),
+            layout_direction: LayoutDirection::default(),
             main_widget: None,
+            menu_bar_app_events: vec![],
+            message_catalogs: MessageCatalogs::new(),
+            message_per_widget_id: HashMap::new(),
+            next_file_dialog_id: 0,
             parent_widget_id_per_widget_id: HashMap::new(),
+            pointer_capture_widget_id: None,
             shared_state: PietSharedState::new(),
+            shortcut_actions_per_scope: HashMap::from([(None, global_shortcut_actions)]),
             size_constraints: SizeConstraints::default(),
             style: Style::default(),
+            text_field_state: None,
             widget_focus_order: WidgetFocusOrder::new(),
             widget_id_provider: WidgetIdProvider::new(),
             widgets: HashMap::new(),
+            window_id: MAIN_WINDOW_ID,
+            window_requests: vec![],
+            window_size: Size::ZERO,
+        }
+    }
+
+    /// Returns the accessibility node of the widget with the given ID and of its descendants.
+    fn accessibility_node(
+        &self,
+        widget_box: &WidgetBox<APP_EVENT>,
+        focused_widget_id: Option<WidgetId>,
+        has_hidden_ancestor: bool,
+    ) -> AccessibilityNode {
+        let widget = widget_box.borrow();
+        let widget_id = *widget.widget_id();
+        let is_hidden = has_hidden_ancestor || widget.is_hidden();
+
+        // Collect the nodes of the child widgets.
+        let children: Vec<AccessibilityNode> = self
+            .child_widget_ids_per_widget_id
+            .get(&widget_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|child_widget_id| self.widgets.get(child_widget_id))
+            .map(|child_widget_box| {
+                self.accessibility_node(child_widget_box, focused_widget_id, is_hidden)
+            })
+            .collect();
+
+        // A name set by the application takes precedence over the widget's own name.
+        let mut name = self
+            .accessibility_name_per_widget_id
+            .get(&widget_id)
+            .cloned()
+            .or_else(|| widget.accessibility_name());
+
+        // A button without a name is named by its content, e.g. its text.
+        if name.is_none() && widget.accessibility_role() == AccessibilityRole::Button {
+            let child_names: Vec<String> = children
+                .iter()
+                .filter_map(|child| child.name.clone())
+                .collect();
+
+            // The content has a name.
+            if !child_names.is_empty() {
+                name = Some(child_names.join(" "));
+            }
         }
+
+        // The bounds are in window coordinates, i.e. zoomed.
+        let zoom_factor = self.shared_state.zoom_factor();
+        let rectangle = widget.rectangle();
+
+        AccessibilityNode {
+            bounds: Rectangle::new(
+                rectangle.x0 * zoom_factor,
+                rectangle.y0 * zoom_factor,
+                rectangle.x1 * zoom_factor,
+                rectangle.y1 * zoom_factor,
+            ),
+            children,
+            is_disabled: widget.is_disabled(),
+            is_focused: focused_widget_id == Some(widget_id),
+            is_hidden,
+            name,
+            role: widget.accessibility_role(),
+            value: widget.accessibility_value(),
+            widget_id,
+        }
+    }
+
+    /// Returns the accessibility tree of the main widget's widget tree, e.g. for a screen reader or
+    /// to assert on the window's semantics in tests. It is `None` if there is no main widget.
+    pub fn accessibility_tree(&self) -> Option<AccessibilityNode> {
+        let focused_widget_id = self
+            .focused_widget
+            .as_ref()
+            .map(|focused_widget| *focused_widget.borrow().widget_id());
+
+        self.main_widget
+            .as_ref()
+            .map(|main_widget| self.accessibility_node(main_widget, focused_widget_id, false))
     }
 
     ///
@@ -74,7 +264,7 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         &mut self,
         parent_widget_id: WidgetId,
         child_widget_id: WidgetId,
-    ) {
+    ) -> Result<(), WidgetError> {
         // The child widget has a parent already already.
         if let Some(previous_parent_widget_id) =
             self.parent_widget_id_per_widget_id.get(&child_widget_id)
@@ -82,11 +272,11 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
             // Get the previous parent widget.
             let previous_parent_widget = self.widgets.get(previous_parent_widget_id).unwrap();
 
-            // Tell the previous parent widget to remove the child widget.
+            // Tell the previous parent widget to remove the child widget. This fails if the
+            // previous parent widget owns the child widget internally, e.g. a text button's text.
             previous_parent_widget
                 .borrow_mut()
-                .remove_child(child_widget_id)
-                .unwrap();
+                .remove_child(child_widget_id)?;
 
             // Remove the previous parent child widget connection.
             self.remove_parent_child_widget_connection(*previous_parent_widget_id, child_widget_id);
@@ -96,11 +286,13 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         self.child_widget_ids_per_widget_id
             .entry(parent_widget_id)
             .or_default()
-            .insert(child_widget_id);
+            .push(child_widget_id);
 
         // Add the parent to the child.
         self.parent_widget_id_per_widget_id
             .insert(child_widget_id, parent_widget_id);
+
+        Ok(())
     }
 
     /// Puts the given widget box under widget management.
@@ -122,31 +314,759 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         self.widgets.insert(widget_id, widget_box);
     }
 
-    ///
-    fn copy_selected_value_to_clipboard(&self, clipboard: &mut Clipboard) {
-        // A widget has focus.
-        if let Some(focused_widget) = &self.focused_widget {
-            // The focused widget has a selected value.
-            if let Some(selected_value) = focused_widget.borrow().selected_value() {
-                // Get a string representation of the widget's selected value.
-                let string_value =
-                // The widget's selected value is a string.
-                if let Some(string) =
-                    selected_value.downcast_ref::<String>()
+    /// Applies the given command. Commands that result from it are appended to `next_commands`.
+    fn apply_command(
+        &mut self,
+        command: Command<APP_EVENT>,
+        next_commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        match command {
+            Command::AddChild {
+                parent_widget_id,
+                widget_placement,
+                child_widget_id,
+                ..
+            } => {
+                let widget_box = self.widget(parent_widget_id)?.clone();
+
+                // There is a widget with the child widget ID from the command.
+                let child_widget_box =
+                    if let Some(child_widget_box) = self.widgets.get(&child_widget_id) {
+                        child_widget_box.clone()
+                    }
+                    // There is no widget with the given child ID.
+                    else {
+                        return Err(WidgetError::NoSuchWidget(child_widget_id));
+                    };
+
+                // Update the connections first, so that a previous parent widget removes
+                // the child widget before the new parent widget adds it.
+                self.add_parent_child_widget_connection(parent_widget_id, child_widget_id)?;
+
+                widget_box
+                    .borrow_mut()
+                    .add_child(widget_placement, child_widget_box)?;
+            }
+            Command::AddChildren {
+                parent_widget_id,
+                child_widgets,
+                ..
+            } => {
+                // Iterate over the child widgets. This additional loop is in order to escape
+                // the borrow checker.
+                for (_, child_widget_id) in &child_widgets {
+                    self.add_parent_child_widget_connection(parent_widget_id, *child_widget_id)?;
+                }
+
+                let widget_box = self.widget(parent_widget_id)?;
+
+                // Iterate over the child widgets.
+                for (widget_placement, child_widget_id) in child_widgets {
+                    // There is a widget with the child widget ID from the command.
+                    let child_widget_box =
+                        if let Some(child_widget_box) = self.widgets.get(&child_widget_id) {
+                            child_widget_box
+                        }
+                        // There is no widget with the given child ID.
+                        else {
+                            return Err(WidgetError::NoSuchWidget(child_widget_id));
+                        };
+
+                    widget_box
+                        .borrow_mut()
+                        .add_child(widget_placement, child_widget_box.clone())?;
+                }
+            }
+            Command::AddEventObservation(
+                widget_id,
+                event_observation_handle,
+                widget_event_type,
+                custom_value,
+            ) => {
+                let widget_box = self.widget(widget_id)?;
+
+                widget_box.borrow_mut().add_event_observation(
+                    event_observation_handle,
+                    widget_event_type.clone(),
+                    WidgetEventObservation::AppEvent(custom_value),
+                );
+            }
+            Command::AddMappedEventObservation(
+                widget_id,
+                event_observation_handle,
+                widget_event_type,
+                event_mapper,
+            ) => {
+                let widget_box = self.widget(widget_id)?;
+
+                widget_box.borrow_mut().add_event_observation(
+                    event_observation_handle,
+                    widget_event_type.clone(),
+                    WidgetEventObservation::Mapper(event_mapper),
+                );
+            }
+            Command::AddMessageCatalog {
+                locale,
+                fluent_source,
+            } => {
+                self.message_catalogs.add_catalog(&locale, fluent_source)?;
+
+                // The catalog might have translations of the localized widgets' messages.
+                self.update_localized_texts(next_commands)?;
+            }
+            Command::AddShortcut {
+                scope_widget_id,
+                shortcut,
+                shortcut_action,
+            } => {
+                // There is a scope widget.
+                if let Some(scope_widget_id) = scope_widget_id {
+                    self.widget(scope_widget_id)?;
+                }
+
+                let shortcut_actions = self
+                    .shortcut_actions_per_scope
+                    .entry(scope_widget_id)
+                    .or_default();
+
+                // The shortcut exists already in the scope.
+                if shortcut_actions.contains_key(&shortcut) {
+                    return Err(WidgetError::ShortcutExistsAlready {
+                        scope_widget_id,
+                        shortcut,
+                    });
+                }
+
+                shortcut_actions.insert(shortcut, shortcut_action);
+            }
+            Command::CloseWindow => {
+                self.window_requests
+                    .push(WindowRequest::CloseWindow(self.window_id));
+            }
+            Command::CreateWidget(widget_id, widget_type) => {
+                // A widget with the given ID exists already.
+                if self.widgets.contains_key(&widget_id) {
+                    return Err(WidgetError::WidgetExistsAlready(widget_id));
+                }
+
+                let widget_box: Box<dyn PietWidget<APP_EVENT>> = match widget_type {
+                    // Localized widgets are created like their unlocalized counterparts, with the
+                    // message's translation as text.
+                    WidgetType::LocalizedHyperlink(message) => {
+                        return self.create_localized_widget(
+                            widget_id,
+                            WidgetType::Hyperlink(String::new()),
+                            message,
+                            next_commands,
+                        );
+                    }
+                    WidgetType::LocalizedText(message) => {
+                        return self.create_localized_widget(
+                            widget_id,
+                            WidgetType::Text(String::new()),
+                            message,
+                            next_commands,
+                        );
+                    }
+                    WidgetType::LocalizedTextButton(message) => {
+                        return self.create_localized_widget(
+                            widget_id,
+                            WidgetType::TextButton(String::new()),
+                            message,
+                            next_commands,
+                        );
+                    }
+                    WidgetType::Hyperlink(text) => {
+                        let mut font_unvisited = self.style.font.clone();
+                        font_unvisited.font_color = Color::rgb8(100, 100, 255);
+
+                        let mut font_being_clicked = self.style.font.clone();
+                        font_being_clicked.font_color = self.style.accent_color;
+
+                        let mut font_visited = self.style.font.clone();
+                        font_visited.font_color = Color::rgb8(50, 50, 100);
+
+                        Box::new(Hyperlink::new(
+                            widget_id,
+                            self.style.debug_rendering_stroke.clone(),
+                            self.shared_state.piet_text(),
+                            font_unvisited,
+                            font_being_clicked,
+                            font_visited,
+                            text,
+                        ))
+                    }
+                    WidgetType::LayoutCenter => Box::new(Center::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                    )),
+                    WidgetType::LayoutColumn => Box::new(Column::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        HorizontalAlignment::Center,
+                        self.style.spacing,
+                    )),
+                    WidgetType::LayoutExpanded { flex_factor } => Box::new(Expanded::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        flex_factor,
+                    )),
+                    WidgetType::LayoutGrid {
+                        column_properties,
+                        row_properties,
+                    } => Box::new(Grid::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        column_properties,
+                        row_properties,
+                    )),
+                    WidgetType::LayoutPadding => Box::new(Padding::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        self.style.padding,
+                        self.style.padding,
+                        self.style.padding,
+                        self.style.padding,
+                    )),
+                    WidgetType::LayoutRow => Box::new(Row::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        self.style.vertical_alignment,
+                        self.style.spacing,
+                    )),
+                    WidgetType::LayoutSizedBox { desired_size } => Box::new(SizedBox::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        desired_size,
+                    )),
+                    WidgetType::Placeholder { maximum_size } => Box::new(Placeholder::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        maximum_size,
+                    )),
+                    WidgetType::Text(text) => Box::new(Text::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        self.shared_state.piet_text(),
+                        self.style.font.clone(),
+                        text,
+                    )),
+                    WidgetType::TextButton(text) => {
+                        let child_widget_id = self.widget_id_provider.next_widget_id();
+                        let child_widget_box: WidgetBox<APP_EVENT> =
+                            Rc::new(RefCell::new(Box::new(Text::new(
+                                child_widget_id,
+                                self.style.debug_rendering_stroke.clone(),
+                                self.shared_state.piet_text(),
+                                self.style.font.clone(),
+                                text,
+                            ))));
+
+                        // Manage the text widget like any other child widget, e.g. so that a
+                        // failed command batch restores it as well.
+                        self.add_widget(child_widget_box.clone());
+                        self.add_parent_child_widget_connection(widget_id, child_widget_id)?;
+
+                        Box::new(Button::new(
+                            widget_id,
+                            self.style.debug_rendering_stroke.clone(),
+                            child_widget_box,
+                            Some(PaintBrush::Color(self.style.accent_color)),
+                            Some(self.style.frame_color),
+                            Some(self.style.accent_color),
+                        ))
+                    }
+                    WidgetType::TextInput { text, width } => Box::new(TextInput::new(
+                        widget_id,
+                        self.style.debug_rendering_stroke.clone(),
+                        self.shared_state.piet_text(),
+                        self.style.font.clone(),
+                        text,
+                        width,
+                        self.style.frame_color,
+                        self.style.accent_color,
+                    )),
+                };
+
+                self.add_widget(Rc::new(RefCell::new(widget_box)));
+            }
+            Command::Destroy(widget_id) => self.destroy_widget(widget_id),
+            Command::RemoveChild {
+                parent_widget_id,
+                child_widget_id,
+                destroy_child_widget: destroy,
+            } => {
+                let widget_box = self.widget(parent_widget_id)?;
+                widget_box.borrow_mut().remove_child(child_widget_id)?;
+
+                // Destroy the child widget.
+                if destroy {
+                    self.destroy_widget(child_widget_id);
+                }
+                // Remove the child widget.
+                else {
+                    self.remove_parent_child_widget_connection(parent_widget_id, child_widget_id);
+                }
+            }
+            Command::RemoveChildren {
+                parent_widget_id,
+                destroy_child_widgets,
+                ..
+            } => {
+                let widget_box = self.widget(parent_widget_id)?;
+                widget_box.borrow_mut().remove_children()?;
+
+                // Destroy the child widgets.
+                if destroy_child_widgets {
+                    // Get the widget's the child widget IDs.
+                    let child_widget_ids = self
+                        .child_widget_ids_per_widget_id
+                        .entry(parent_widget_id)
+                        .or_default()
+                        .clone();
+
+                    // Iterate over the child widget IDs.
+                    for child_widget_id in child_widget_ids {
+                        // Destroy the child widget.
+                        self.destroy_widget(child_widget_id);
+                    }
+                }
+                // Remove the child widgets.
+                else {
+                    self.remove_parent_child_widget_connections(parent_widget_id);
+                }
+            }
+            Command::RemoveEventObservation(widget_id, event_observation_handle) => {
+                let widget_box = self.widget(widget_id)?;
+
+                widget_box
+                    .borrow_mut()
+                    .remove_event_observation(event_observation_handle);
+            }
+            Command::RemoveShortcut {
+                scope_widget_id,
+                shortcut,
+            } => {
+                // The scope has shortcuts.
+                if let Some(shortcut_actions) =
+                    self.shortcut_actions_per_scope.get_mut(&scope_widget_id)
                 {
-                    string.clone()
+                    shortcut_actions.remove(&shortcut);
+                }
+            }
+            Command::SetContextMenu(widget_id, menu_items) => {
+                // There is no widget with the given ID.
+                if !self.widgets.contains_key(&widget_id) {
+                    return Err(WidgetError::NoSuchWidget(widget_id));
+                }
+
+                // Remove the context menu.
+                if menu_items.is_empty() {
+                    self.context_menu_items_per_widget_id.remove(&widget_id);
+                }
+                // Set the context menu.
+                else {
+                    self.context_menu_items_per_widget_id
+                        .insert(widget_id, menu_items);
+                }
+            }
+            Command::SetAccessibilityName(widget_id, name) => {
+                // There is no widget with the given ID.
+                if !self.widgets.contains_key(&widget_id) {
+                    return Err(WidgetError::NoSuchWidget(widget_id));
+                }
+
+                // Set the name.
+                if let Some(name) = name {
+                    self.accessibility_name_per_widget_id
+                        .insert(widget_id, name);
+                }
+                // Remove the name.
+                else {
+                    self.accessibility_name_per_widget_id.remove(&widget_id);
+                }
+            }
+            Command::SetDebugRendering(widget_id, debug_rendering) => {
+                let widget_box = self.widget(widget_id)?;
+                widget_box.borrow_mut().set_debug_rendering(debug_rendering);
+            }
+            Command::SetDragPayload(widget_id, drag_payload) => {
+                // There is no widget with the given ID.
+                if !self.widgets.contains_key(&widget_id) {
+                    return Err(WidgetError::NoSuchWidget(widget_id));
+                }
+
+                // Make the widget draggable.
+                if let Some(drag_payload) = drag_payload {
+                    self.drag_payload_per_widget_id
+                        .insert(widget_id, drag_payload);
+                }
+                // Make the widget undraggable.
+                else {
+                    self.drag_payload_per_widget_id.remove(&widget_id);
+                }
+            }
+            Command::SetDropTarget(widget_id, drop_target) => {
+                // There is no widget with the given ID.
+                if !self.widgets.contains_key(&widget_id) {
+                    return Err(WidgetError::NoSuchWidget(widget_id));
+                }
+
+                // Make the widget a drop target.
+                if let Some(drop_target) = drop_target {
+                    self.drop_target_per_widget_id
+                        .insert(widget_id, drop_target);
+                }
+                // Make the widget no drop target.
+                else {
+                    self.drop_target_per_widget_id.remove(&widget_id);
+
+                    // Stop highlighting the widget if the payload being dragged is over it.
+                    self.remove_drag_drop_target(widget_id);
+                }
+            }
+            Command::SetFocusOrder(focus_order) => {
+                self.widget_focus_order.set_focus_order(focus_order);
+            }
+            Command::SetGestureThresholds(gesture_thresholds) => {
+                self.gesture_recognizer
+                    .set_gesture_thresholds(gesture_thresholds);
+            }
+            Command::SetHasFocus(widget_id, has_focus) => {
+                // There is a widget with the given ID.
+                let widget_box = if let Some(widget_box) = self.widgets.get(&widget_id) {
+                    widget_box
+                }
+                // There is no widget with the given ID.
+                else {
+                    return Err(WidgetError::NoSuchWidget(widget_id));
+                };
+
+                let mut widget_had_focus_already = false;
+
+                // A widget had focus.
+                if let Some(focused_widget) = &mut self.focused_widget {
+                    // The widgets are different.
+                    if *focused_widget.borrow().widget_id() != widget_id {
+                        // Unfocus that widget.
+                        focused_widget.borrow_mut().set_has_focus(false)?;
+                    }
+                    // The widgets are the same.
+                    else {
+                        widget_had_focus_already = true;
+                    }
+                }
+
+                if !widget_had_focus_already {
+                    // Tell the widget it has focus.
+                    widget_box.borrow_mut().set_has_focus(has_focus)?;
+
+                    // Remember the current widget as focused.
+                    self.focused_widget = Some(widget_box.clone());
+                }
+            }
+            Command::SetIsFocusScope(widget_id, is_focus_scope) => {
+                self.widget(widget_id)?;
+
+                // Make the widget a focus scope.
+                if is_focus_scope {
+                    self.focus_scope_widget_ids.insert(widget_id);
+                }
+                // Make the widget a regular widget.
+                else {
+                    self.focus_scope_widget_ids.remove(&widget_id);
+                }
+            }
+            Command::SetLayoutDirection(layout_direction) => {
+                self.layout_direction = layout_direction;
+
+                // There is a main widget.
+                if let Some(main_widget) = &self.main_widget {
+                    // Pass the layout direction on to the widgets.
+                    main_widget
+                        .borrow_mut()
+                        .set_ancestor_layout_direction(layout_direction);
+                }
+
+                // Lay out the widgets again.
+                self.resize(self.window_size);
+            }
+            Command::SetLocale(locale) => {
+                self.message_catalogs.set_locale(&locale)?;
+
+                // Look up the localized widgets' messages in the new locale.
+                self.update_localized_texts(next_commands)?;
+            }
+            Command::SetMainWidget(widget_id) => {
+                let widget_box = self.widget(widget_id)?;
+                widget_box
+                    .borrow_mut()
+                    .set_ancestor_layout_direction(self.layout_direction);
+                widget_box.borrow_mut().set_origin(Point::new(1.0, 1.0));
+                self.main_widget = Some(widget_box.clone());
+            }
+            Command::SetMenuBar(menu_items) => {
+                let mut menu_bar_app_events = vec![];
+                let menu = menu_bar::menu(&menu_items, &mut menu_bar_app_events);
+
+                self.menu_bar_app_events = menu_bar_app_events;
+                self.window_requests.push(WindowRequest::SetMenu(menu));
+            }
+            Command::SetMessage(widget_id, message) => {
+                let text_widget_id = self.text_widget_id(widget_id)?;
+
+                // Localize the widget's text.
+                if let Some(message) = message {
+                    let text = localized_text(&self.message_catalogs.bundle(), &message);
+
+                    self.set_widget_text(text_widget_id, text, next_commands)?;
+                    self.message_per_widget_id.insert(text_widget_id, message);
+                }
+                // Keep the widget's current text.
+                else {
+                    self.message_per_widget_id.remove(&text_widget_id);
+                }
+            }
+            Command::SetTabIndex(widget_id, tab_index) => {
+                self.widget(widget_id)?;
+                self.widget_focus_order.set_tab_index(widget_id, tab_index);
+            }
+            Command::SetWindowSize(size) => {
+                self.window_requests
+                    .push(WindowRequest::SetSize(Size::new(size.width, size.height)));
+            }
+            Command::SetWindowTitle(title) => {
+                self.window_requests.push(WindowRequest::SetTitle(title));
+            }
+            Command::SetZoomFactor(zoom_factor) => {
+                self.set_zoom_factor(zoom_factor);
+            }
+            Command::ShowOpenDialog(file_dialog_options, file_dialog_mapper) => {
+                let file_dialog_id = self.next_file_dialog_id;
+                self.next_file_dialog_id += 1;
+
+                self.file_dialog_mappers
+                    .insert(file_dialog_id, file_dialog_mapper);
+                self.window_requests.push(WindowRequest::ShowOpenDialog(
+                    file_dialog_id,
+                    file_dialog::file_dialog_options(file_dialog_options),
+                ));
+            }
+            Command::ShowSaveDialog(file_dialog_options, file_dialog_mapper) => {
+                let file_dialog_id = self.next_file_dialog_id;
+                self.next_file_dialog_id += 1;
+
+                self.file_dialog_mappers
+                    .insert(file_dialog_id, file_dialog_mapper);
+                self.window_requests.push(WindowRequest::ShowSaveDialog(
+                    file_dialog_id,
+                    file_dialog::file_dialog_options(file_dialog_options),
+                ));
+            }
+            Command::ToWidget(widget_id, widget_commands) => {
+                // There is a widget with the given ID.
+                let widget_box = if let Some(widget_box) = self.widgets.get(&widget_id) {
+                    widget_box
                 }
-                // The widget selected value is not a string.
+                // There is no widget with the given ID.
                 else {
-                    format!("{:?}", selected_value)
+                    return Err(WidgetError::NoSuchWidget(widget_id));
                 };
 
-                // Put the string value in the clipboard.
-                clipboard.put_string(string_value);
+                // Iterate over the widget commands.
+                for widget_command in widget_commands {
+                    widget_box.borrow_mut().handle_command(
+                        widget_command,
+                        &mut self.shared_state,
+                        &mut self.widget_id_provider,
+                        next_commands,
+                    )?;
+                }
+
+                // A widget has focus.
+                if let Some(focused_widget) = &self.focused_widget {
+                    // The focused widget is disabled now, possibly via an ancestor.
+                    if focused_widget.borrow().is_disabled() {
+                        focused_widget.borrow_mut().set_has_focus(false)?;
+                        self.focused_widget = None;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the widget with the given ID can receive focus via Tab: it and its ancestors
+    /// are neither hidden nor disabled, it is part of the main widget's widget tree and, if a focus
+    /// scope is given, part of the focus scope's widget tree.
+    fn can_receive_focus(
+        &self,
+        widget_id: WidgetId,
+        focus_scope_widget_id: Option<WidgetId>,
+    ) -> bool {
+        let main_widget_id = if let Some(main_widget) = &self.main_widget {
+            *main_widget.borrow().widget_id()
+        }
+        // There is no main widget.
+        else {
+            return false;
+        };
+
+        let mut is_in_focus_scope = focus_scope_widget_id.is_none();
+        let mut current_widget_id = widget_id;
+
+        loop {
+            // There is a widget with the current ID.
+            let widget_box = if let Some(widget_box) = self.widgets.get(&current_widget_id) {
+                widget_box
+            }
+            // There is no widget with the current ID.
+            else {
+                return false;
+            };
+
+            // The current widget is hidden or disabled.
+            if widget_box.borrow().is_hidden() || widget_box.borrow().is_disabled() {
+                return false;
+            }
+
+            // The current widget is the focus scope.
+            if Some(current_widget_id) == focus_scope_widget_id {
+                is_in_focus_scope = true;
+            }
+
+            // The main widget was reached.
+            if current_widget_id == main_widget_id {
+                return is_in_focus_scope;
+            }
+
+            // Continue with the parent widget.
+            if let Some(parent_widget_id) =
+                self.parent_widget_id_per_widget_id.get(&current_widget_id)
+            {
+                current_widget_id = *parent_widget_id;
+            }
+            // The widget is not part of the main widget's widget tree.
+            else {
+                return false;
+            }
+        }
+    }
+
+    ///
+    fn copy_selected_value_to_clipboard(&self, clipboard: &mut Clipboard) {
+        // A widget has focus.
+        if let Some(focused_widget) = &self.focused_widget {
+            // The focused widget has a selected value with a text representation.
+            if let Some(text) = focused_widget
+                .borrow()
+                .selected_value()
+                .and_then(|selected_value| selected_value.to_text())
+            {
+                // Put the text in the clipboard.
+                clipboard.put_string(text);
+            }
+        }
+    }
+
+    /// Copies the widgets that the given command changes into the snapshot, so that they can be
+    /// restored if the command batch fails.
+    fn copy_widgets_changed_by(
+        &self,
+        command: &Command<APP_EVENT>,
+        snapshot: &mut Snapshot<APP_EVENT>,
+    ) {
+        let mut widget_ids = vec![];
+
+        match command {
+            Command::AddChild {
+                parent_widget_id,
+                child_widget_id,
+                ..
+            } => {
+                widget_ids.push(*parent_widget_id);
+                widget_ids.push(*child_widget_id);
+
+                // Adding a child widget removes it from its previous parent widget.
+                widget_ids.extend(self.parent_widget_id_per_widget_id.get(child_widget_id));
+            }
+            Command::AddChildren {
+                parent_widget_id,
+                child_widgets,
+            } => {
+                widget_ids.push(*parent_widget_id);
+
+                // Iterate over the child widgets.
+                for (_, child_widget_id) in child_widgets {
+                    widget_ids.push(*child_widget_id);
+
+                    // Adding a child widget removes it from its previous parent widget.
+                    widget_ids.extend(self.parent_widget_id_per_widget_id.get(child_widget_id));
+                }
+            }
+            Command::AddEventObservation(widget_id, _, _, _)
+            | Command::AddMappedEventObservation(widget_id, _, _, _)
+            | Command::RemoveEventObservation(widget_id, _)
+            | Command::SetDebugRendering(widget_id, _)
+            | Command::SetHasFocus(widget_id, _)
+            | Command::SetMainWidget(widget_id)
+            | Command::SetMessage(widget_id, _)
+            | Command::ToWidget(widget_id, _) => {
+                widget_ids.push(*widget_id);
+            }
+            Command::RemoveChild {
+                parent_widget_id, ..
+            }
+            | Command::RemoveChildren {
+                parent_widget_id, ..
+            } => {
+                widget_ids.push(*parent_widget_id);
             }
+            // Message catalogs and the locale change the texts of all localized widgets.
+            Command::AddMessageCatalog { .. } | Command::SetLocale(_) => {
+                widget_ids.extend(self.message_per_widget_id.keys());
+            }
+            // The layout direction changes all widgets of the main widget's tree.
+            Command::SetLayoutDirection(_) => {
+                widget_ids.extend(
+                    self.main_widget
+                        .as_ref()
+                        .map(|main_widget| *main_widget.borrow().widget_id()),
+                );
+            }
+            // The other commands only change the widget manager's state.
+            _ => {}
+        }
+
+        // Changing the focus or disabling a widget changes the focused widget as well.
+        if let Command::SetHasFocus(..) | Command::ToWidget(..) = command {
+            widget_ids.extend(
+                self.focused_widget
+                    .as_ref()
+                    .map(|focused_widget| *focused_widget.borrow().widget_id()),
+            );
+        }
+
+        // Iterate over the IDs of the changed widgets.
+        for widget_id in widget_ids {
+            // Copy the widget and its descendants, since e.g. layout changes affect them too.
+            snapshot.copy_widget_tree(self, widget_id);
         }
     }
 
+    /// Creates a widget of the given type whose text is the given message's translation.
+    fn create_localized_widget(
+        &mut self,
+        widget_id: WidgetId,
+        widget_type: WidgetType,
+        message: Message,
+        next_commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        self.apply_command(Command::CreateWidget(widget_id, widget_type), next_commands)?;
+        self.apply_command(Command::SetMessage(widget_id, Some(message)), next_commands)
+    }
+
     /// Destroys the widget with the given ID and its child widget tree.
     fn destroy_widget(&mut self, widget_id: WidgetId) {
         let mut ids_of_widgets_to_destroy: HashSet<WidgetId> = HashSet::new();
@@ -186,6 +1106,14 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
             }
         }
 
+        // There is a drag.
+        if let Some(drag) = &self.drag {
+            // The dragged widget is to be destroyed.
+            if ids_of_widgets_to_destroy.contains(&drag.source_widget_id()) {
+                self.drag = None;
+            }
+        }
+
         // There is a main widget.
         if let Some(main_widget) = &self.main_widget {
             // The main widget is to be destroyed.
@@ -200,7 +1128,7 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
                 .child_widget_ids_per_widget_id
                 .get(&id_of_widget_to_destroy)
                 .cloned()
-                .unwrap_or(HashSet::new());
+                .unwrap_or_default();
             let parent_widget_id = self
                 .parent_widget_id_per_widget_id
                 .get(&id_of_widget_to_destroy)
@@ -210,6 +1138,31 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
             self.widget_focus_order
                 .remove_widget(id_of_widget_to_destroy);
 
+            // Remove the widget's focus scope.
+            self.focus_scope_widget_ids.remove(&id_of_widget_to_destroy);
+
+            // Remove the widget's accessibility name.
+            self.accessibility_name_per_widget_id
+                .remove(&id_of_widget_to_destroy);
+
+            // Remove the widget's context menu.
+            self.context_menu_items_per_widget_id
+                .remove(&id_of_widget_to_destroy);
+
+            // Remove the widget's message.
+            self.message_per_widget_id.remove(&id_of_widget_to_destroy);
+
+            // Remove the widget's drag payload and drop target.
+            self.drag_payload_per_widget_id
+                .remove(&id_of_widget_to_destroy);
+            self.drop_target_per_widget_id
+                .remove(&id_of_widget_to_destroy);
+            self.remove_drag_drop_target(id_of_widget_to_destroy);
+
+            // Remove the shortcuts scoped to the widget.
+            self.shortcut_actions_per_scope
+                .remove(&Some(id_of_widget_to_destroy));
+
             // Remove the widget from child/parent connections.
             {
                 self.child_widget_ids_per_widget_id
@@ -221,7 +1174,7 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
                         .child_widget_ids_per_widget_id
                         .get_mut(&parent_widget_id)
                     {
-                        parents_child_widget_ids.remove(&id_of_widget_to_destroy);
+                        parents_child_widget_ids.retain(|&i| i != id_of_widget_to_destroy);
                     }
                 }
             }
@@ -241,29 +1194,306 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         }
     }
 
-    ///
-    fn give_next_widget_focus(&mut self) -> Result<(), WidgetError> {
-        // There is a next widget in the focus order.
-        if let Some(widget_id_to_give_focus) = self.widget_focus_order.focus_next_widget() {
-            let widget_to_give_focus = self.widgets.get(&widget_id_to_give_focus).unwrap();
-
-            // A widget has focus already.
-            if let Some(focused_widget) = &self.focused_widget {
-                // The focused widget is already the next widget in the focus order.
-                if focused_widget.borrow().widget_id() == &widget_id_to_give_focus {
-                    return Ok(());
-                }
-
-                // Remove the focus from the previously focused widget.
-                focused_widget.borrow_mut().set_has_focus(false)?;
+    /// Dispatches the given event along the given event path: in the capture phase from the main
+    /// widget down to the target widget's parent, in the target phase to the target widget, which is
+    /// the last one of the path, and in the bubble phase back up to the main widget. Every widget of
+    /// the path can stop the propagation.
+    fn dispatch_event(
+        &mut self,
+        event: &Event,
+        event_path: &[WidgetId],
+        widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
+    ) {
+        let widget_boxes: Vec<WidgetBox<APP_EVENT>> = event_path
+            .iter()
+            .filter_map(|widget_id| self.widgets.get(widget_id).cloned())
+            .collect();
+
+        let (target_widget, ancestor_widgets) =
+            if let Some((target_widget, ancestor_widgets)) = widget_boxes.split_last() {
+                (target_widget, ancestor_widgets)
+            }
+            // The event path is empty.
+            else {
+                return;
+            };
+
+        // Capture phase.
+        for ancestor_widget in ancestor_widgets {
+            // The ancestor widget stops the propagation.
+            if ancestor_widget.borrow_mut().handle_dispatched_event(
+                event,
+                EventPhase::Capture,
+                &mut self.shared_state,
+                &mut self.widget_id_provider,
+                widget_events,
+            ) == EventPropagation::Stop
+            {
+                return;
             }
+        }
 
-            // Give the widget focus.
-            widget_to_give_focus.borrow_mut().set_has_focus(true)?;
-            self.focused_widget = Some(widget_to_give_focus.clone());
+        // Target phase. The target widget stops the propagation.
+        if target_widget.borrow_mut().handle_dispatched_event(
+            event,
+            EventPhase::Target,
+            &mut self.shared_state,
+            &mut self.widget_id_provider,
+            widget_events,
+        ) == EventPropagation::Stop
+        {
+            return;
         }
 
-        Ok(())
+        // Bubble phase.
+        for ancestor_widget in ancestor_widgets.iter().rev() {
+            // The ancestor widget stops the propagation.
+            if ancestor_widget.borrow_mut().handle_dispatched_event(
+                event,
+                EventPhase::Bubble,
+                &mut self.shared_state,
+                &mut self.widget_id_provider,
+                widget_events,
+            ) == EventPropagation::Stop
+            {
+                return;
+            }
+        }
+    }
+
+    /// Dispatches the given pointer event along the path to the widget that captured the pointer or
+    /// else to the topmost widget under the pointer. Returns that path.
+    fn dispatch_pointer_event(
+        &mut self,
+        event: &Event,
+        mouse_event: &MouseEvent,
+        widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
+    ) -> Vec<WidgetId> {
+        let pointer_widget_path =
+            self.widget_path_at(Point::new(mouse_event.pos.x, mouse_event.pos.y));
+
+        // The widget that captured the pointer is no longer shown.
+        if let Some(pointer_capture_widget_id) = self.pointer_capture_widget_id {
+            if !self.is_shown(pointer_capture_widget_id) {
+                self.pointer_capture_widget_id = None;
+            }
+        }
+
+        let event_path = if let Some(pointer_capture_widget_id) = self.pointer_capture_widget_id {
+            self.widget_path(pointer_capture_widget_id)
+        } else {
+            pointer_widget_path.clone()
+        };
+
+        self.shared_state
+            .set_pointer_widget_path(pointer_widget_path);
+        self.dispatch_event(event, &event_path, widget_events);
+
+        event_path
+    }
+
+    /// Returns the ID of the innermost enabled drop target at the given position that accepts
+    /// payloads of the given kind. The dragged widget is no drop target for its own payload.
+    fn drop_target_at(
+        &self,
+        point: Point,
+        kind: &str,
+        source_widget_id: WidgetId,
+    ) -> Option<WidgetId> {
+        self.widget_path_at(point)
+            .into_iter()
+            .rev()
+            .find(|widget_id| {
+                *widget_id != source_widget_id
+                    && self
+                        .drop_target_per_widget_id
+                        .get(widget_id)
+                        .map(|drop_target| drop_target.accepts(kind))
+                        .unwrap_or(false)
+                    && self
+                        .widgets
+                        .get(widget_id)
+                        .map(|widget_box| !widget_box.borrow().is_disabled())
+                        .unwrap_or(false)
+            })
+    }
+
+    /// Returns the IDs of the widgets in the focus order that can currently receive focus. While the
+    /// focused widget is in a focus scope, only widgets in that scope are returned.
+    fn focusable_widget_ids(&self) -> Vec<WidgetId> {
+        let focus_scope_widget_id = self.focused_widget.as_ref().and_then(|focused_widget| {
+            self.focus_scope_widget_id(*focused_widget.borrow().widget_id())
+        });
+
+        self.widget_focus_order
+            .ordered_widget_ids(
+                |widget_id| {
+                    self.widgets
+                        .get(&widget_id)
+                        .map(|widget_box| *widget_box.borrow().rectangle())
+                        .unwrap_or(Rectangle::ZERO)
+                },
+                || self.widget_tree_order(),
+            )
+            .into_iter()
+            .filter(|widget_id| self.can_receive_focus(*widget_id, focus_scope_widget_id))
+            .collect()
+    }
+
+    /// Returns the ID of the innermost focus scope widget that contains the widget with the given ID.
+    fn focus_scope_widget_id(&self, widget_id: WidgetId) -> Option<WidgetId> {
+        let mut current_widget_id = widget_id;
+
+        loop {
+            // The current widget is a focus scope.
+            if self.focus_scope_widget_ids.contains(&current_widget_id) {
+                return Some(current_widget_id);
+            }
+
+            current_widget_id = *self
+                .parent_widget_id_per_widget_id
+                .get(&current_widget_id)?;
+        }
+    }
+
+    /// Returns the focused widget's text field state in window coordinates, i.e. zoomed.
+    fn focused_text_field_state(&self) -> Option<TextFieldState> {
+        let zoom_factor = self.shared_state.zoom_factor();
+        let mut text_field_state = self.focused_widget.as_ref()?.borrow().text_field_state()?;
+
+        text_field_state.bounding_box =
+            text_field_state.bounding_box.scale_from_origin(zoom_factor);
+        text_field_state.caret_rectangle = text_field_state
+            .caret_rectangle
+            .scale_from_origin(zoom_factor);
+
+        // Iterate over the caret positions.
+        for (_, caret_x) in &mut text_field_state.caret_x_per_byte_index {
+            *caret_x *= zoom_factor;
+        }
+
+        Some(text_field_state)
+    }
+
+    /// Gives focus to the next widget in the focus order or, if `forward` is `false`, to the
+    /// previous one.
+    fn give_next_widget_focus(&mut self, forward: bool) -> Result<(), WidgetError> {
+        let focused_widget_id = self
+            .focused_widget
+            .as_ref()
+            .map(|focused_widget| *focused_widget.borrow().widget_id());
+
+        // There is a next widget in the focus order.
+        if let Some(widget_id_to_give_focus) = WidgetFocusOrder::next_widget_id(
+            &self.focusable_widget_ids(),
+            focused_widget_id,
+            forward,
+        ) {
+            let widget_to_give_focus = self.widgets.get(&widget_id_to_give_focus).unwrap();
+
+            // A widget has focus already.
+            if let Some(focused_widget) = &self.focused_widget {
+                // The focused widget is already the next widget in the focus order.
+                if focused_widget.borrow().widget_id() == &widget_id_to_give_focus {
+                    return Ok(());
+                }
+
+                // Remove the focus from the previously focused widget.
+                focused_widget.borrow_mut().set_has_focus(false)?;
+            }
+
+            // Give the widget focus.
+            widget_to_give_focus.borrow_mut().set_has_focus(true)?;
+            self.focused_widget = Some(widget_to_give_focus.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Starts, moves or drops a drag for the given drag gesture event.
+    fn handle_drag_and_drop(
+        &mut self,
+        gesture_event: &Event,
+        mouse_event: &MouseEvent,
+        widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
+    ) {
+        let position = Point::new(mouse_event.pos.x, mouse_event.pos.y);
+
+        match gesture_event {
+            Event::DragStarted(_) => {
+                // The innermost widget at the drag's start position that is draggable and enabled.
+                let source_widget_id =
+                    self.widget_path_at(position)
+                        .into_iter()
+                        .rev()
+                        .find(|widget_id| {
+                            self.drag_payload_per_widget_id.contains_key(widget_id)
+                                && self
+                                    .widgets
+                                    .get(widget_id)
+                                    .map(|widget_box| !widget_box.borrow().is_disabled())
+                                    .unwrap_or(false)
+                        });
+
+                // A draggable widget was hit.
+                if let Some(source_widget_id) = source_widget_id {
+                    self.drag = Some(Drag::new(
+                        self.drag_payload_per_widget_id[&source_widget_id].clone(),
+                        source_widget_id,
+                        position,
+                        &self.style,
+                        &mut self.shared_state,
+                    ));
+                }
+            }
+            Event::Dragged(_) => {
+                // There is a drag.
+                if let Some(drag) = &self.drag {
+                    let drop_target = self
+                        .drop_target_at(
+                            position,
+                            drag.drag_payload().kind(),
+                            drag.source_widget_id(),
+                        )
+                        .and_then(|widget_id| {
+                            self.widgets
+                                .get(&widget_id)
+                                .map(|widget_box| (widget_id, *widget_box.borrow().rectangle()))
+                        });
+
+                    // Move the drag preview and highlight the drop target.
+                    if let Some(drag) = &mut self.drag {
+                        drag.set_position(position);
+                        drag.set_drop_target(drop_target);
+                    }
+                }
+            }
+            Event::DragEnded(_) => {
+                // There is a drag.
+                if let Some(drag) = self.drag.take() {
+                    // The drag ended over the highlighted drop target.
+                    if let Some(target_widget_id) = drag.drop_target_widget_id() {
+                        let drop_data = DropData {
+                            modifiers: modifiers(mouse_event.mods),
+                            payload: drag.drag_payload().clone(),
+                            position,
+                            source_widget_id: drag.source_widget_id(),
+                            target_widget_id,
+                        };
+
+                        // The drop target still exists. It might have been destroyed or unregistered
+                        // during the drag.
+                        if let Some(drop_target) =
+                            self.drop_target_per_widget_id.get(&target_widget_id)
+                        {
+                            widget_events
+                                .push(WidgetEvent::AppEvent(drop_target.app_event(&drop_data)));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
     }
 
     ///
@@ -272,95 +1502,192 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         event: &Event,
         clipboard: Option<&mut Clipboard>,
     ) -> Result<Vec<APP_EVENT>, WidgetError> {
+        // A menu bar item was selected.
+        if let Event::MenuCommand(menu_item_id) = event {
+            // Close an open context menu.
+            self.context_menu = None;
+
+            return Ok(self
+                .menu_bar_app_events
+                .get(*menu_item_id as usize)
+                .cloned()
+                .into_iter()
+                .collect());
+        }
+
+        // Convert pointer positions from window coordinates to zoomed coordinates.
+        let zoomed_event = self.zoomed_event(event);
+        let event = &zoomed_event;
+
+        // A file dialog was closed.
+        if let Event::FileChosen(file_dialog_id, paths) = event {
+            return Ok(self
+                .file_dialog_mappers
+                .remove(file_dialog_id)
+                .map(|file_dialog_mapper| file_dialog_mapper(paths))
+                .into_iter()
+                .collect());
+        }
+
+        // The window lost the keyboard focus.
+        if let Event::WindowUnfocused = event {
+            // Close an open context menu and cancel a drag.
+            self.context_menu = None;
+            self.drag = None;
+        }
+
+        // A context menu is open.
+        if let Some(context_menu) = &mut self.context_menu {
+            // The context menu handles all events while it is open.
+            match context_menu.handle_event(event, &mut self.shared_state) {
+                ContextMenuEvent::Closed => {
+                    self.context_menu = None;
+                }
+                ContextMenuEvent::Handled => {}
+                ContextMenuEvent::Selected(app_event) => {
+                    self.context_menu = None;
+                    return Ok(vec![app_event]);
+                }
+            }
+
+            return Ok(vec![]);
+        }
+
+        // Open a context menu.
+        match event {
+            Event::KeyDown(key_event) if key_event.key == KbKey::ContextMenu => {
+                // A widget has focus.
+                if let Some(focused_widget) = &self.focused_widget {
+                    let widget_id = *focused_widget.borrow().widget_id();
+                    let rectangle = *focused_widget.borrow().rectangle();
+
+                    // The focused widget has a context menu.
+                    if self
+                        .context_menu_items_per_widget_id
+                        .contains_key(&widget_id)
+                    {
+                        // Open the context menu below the focused widget.
+                        self.open_context_menu(widget_id, Point::new(rectangle.x0, rectangle.y1));
+                        return Ok(vec![]);
+                    }
+                }
+            }
+            Event::MouseDown(mouse_event) if mouse_event.button.is_right() => {
+                // A widget with a context menu was right-clicked.
+                if let Some(widget_id) =
+                    self.widget_with_context_menu_at(mouse_event.pos.x, mouse_event.pos.y)
+                {
+                    self.open_context_menu(
+                        widget_id,
+                        Point::new(mouse_event.pos.x, mouse_event.pos.y),
+                    );
+                    return Ok(vec![]);
+                }
+            }
+            _ => {}
+        }
+
         let mut widget_events = vec![];
 
         let mut event_was_handled = false;
 
         // Handle key events.
         match event {
+            Event::KeyDown(key_event) if key_event.key == KbKey::Escape && self.drag.is_some() => {
+                event_was_handled = true;
+
+                // Cancel the drag.
+                self.drag = None;
+            }
             Event::KeyDown(key_event) => {
-                // The Meta key is pressed.
-                if key_event.mods.contains(Modifiers::META) {
-                    // A clipboard is given.
-                    if let Some(clipboard) = clipboard {
-                        // Handle paste from clipboard.
-                        if key_event.key == KbKey::Character("v".to_string()) {
-                            event_was_handled = true;
-
-                            // Could get a string from the clipboard.
-                            if let Some(string) = clipboard.get_string() {
-                                // A widget has focus.
-                                if let Some(focused_widget) = &mut self.focused_widget {
-                                    // Let the focused widget handle a clipboard paste event.
-                                    focused_widget.borrow_mut().handle_event(
-                                        &Event::ClipboardPaste(string),
-                                        &mut self.shared_state,
-                                        &mut self.widget_id_provider,
-                                        &mut widget_events,
-                                    );
-                                }
-                            }
-                        }
-                        // Handle copy to clipboard.
-                        else if key_event.key == KbKey::Character("c".to_string()) {
-                            event_was_handled = true;
+                // The key event matches a shortcut.
+                if let Some(shortcut_action) = self.shortcut_action(key_event) {
+                    event_was_handled = true;
 
-                            self.copy_selected_value_to_clipboard(clipboard);
-                        }
-                        // Handle cut to clipboard.
-                        else if key_event.key == KbKey::Character("x".to_string()) {
-                            event_was_handled = true;
-
-                            self.copy_selected_value_to_clipboard(clipboard);
-
-                            // A widget has focus.
-                            if let Some(focused_widget) = &mut self.focused_widget {
-                                // Remove the selected value.
-                                focused_widget.borrow_mut().remove_selected_value(
-                                    &mut self.shared_state,
-                                    &mut self.widget_id_provider,
-                                )?;
-                            }
-                        }
-                    }
+                    self.handle_shortcut_action(shortcut_action, clipboard, &mut widget_events)?;
+                }
+                // Tab was pressed.
+                else if key_event.key == KbKey::Tab {
+                    event_was_handled = true;
+
+                    // Give the next widget focus or, with Shift, the previous one.
+                    self.give_next_widget_focus(!key_event.mods.shift())?;
                 }
-                // The Meta key is not pressed.
+                // Any other key was pressed.
                 else {
-                    // Tab was pressed.
-                    if key_event.key == KbKey::Tab {
+                    // A widget has focus.
+                    if let Some(focused_widget) = &self.focused_widget {
                         event_was_handled = true;
 
-                        // Give the next widget focus.
-                        self.give_next_widget_focus()?;
-                    }
-                    // Tab was not pressed.
-                    else {
-                        // A widget has focus.
-                        if let Some(focused_widget) = &mut self.focused_widget {
-                            event_was_handled = true;
-
-                            // Let the focused widget handle the key event.
-                            focused_widget.borrow_mut().handle_event(
-                                event,
-                                &mut self.shared_state,
-                                &mut self.widget_id_provider,
-                                &mut widget_events,
-                            );
-                        }
+                        // Dispatch the key event along the path to the focused widget.
+                        let event_path = self.widget_path(*focused_widget.borrow().widget_id());
+                        self.dispatch_event(event, &event_path, &mut widget_events);
                     }
                 }
             }
             Event::KeyUp(_key_event) => {
                 // A widget has focus.
-                if let Some(focused_widget) = &mut self.focused_widget {
-                    // Let the focused widget handle the key event.
-                    focused_widget.borrow_mut().handle_event(
-                        event,
-                        &mut self.shared_state,
-                        &mut self.widget_id_provider,
-                        &mut widget_events,
-                    );
+                if let Some(focused_widget) = &self.focused_widget {
+                    // Dispatch the key event along the path to the focused widget.
+                    let event_path = self.widget_path(*focused_widget.borrow().widget_id());
+                    self.dispatch_event(event, &event_path, &mut widget_events);
+                }
+            }
+            Event::TextFieldEdited(_) => {
+                event_was_handled = true;
+
+                // A widget has focus.
+                if let Some(focused_widget) = &self.focused_widget {
+                    // Dispatch the edit along the path to the focused widget.
+                    let event_path = self.widget_path(*focused_widget.borrow().widget_id());
+                    self.dispatch_event(event, &event_path, &mut widget_events);
+                }
+            }
+            Event::MouseDown(mouse_event)
+            | Event::MouseMove(mouse_event)
+            | Event::MouseUp(mouse_event)
+            | Event::MouseWheel(mouse_event) => {
+                event_was_handled = true;
+
+                let event_path =
+                    self.dispatch_pointer_event(event, mouse_event, &mut widget_events);
+
+                // A left mouse down can become a long press.
+                if let Event::MouseDown(mouse_event) = event {
+                    if mouse_event.button.is_left() {
+                        self.window_requests.push(WindowRequest::RequestTimer(
+                            self.gesture_recognizer
+                                .gesture_thresholds()
+                                .long_press_duration,
+                        ));
+
+                        // The focused widget was not hit, so it did not get the event and loses
+                        // focus here. A widget that was hit may gain focus in its place.
+                        if let Some(focused_widget) = self.focused_widget.clone() {
+                            if !event_path.contains(focused_widget.borrow().widget_id()) {
+                                focused_widget.borrow_mut().set_has_focus(false)?;
+                                self.focused_widget = None;
+                            }
+                        }
+                    }
                 }
+
+                self.handle_gestures(event, &mut widget_events);
+            }
+            Event::ScaleChanged(scale) => {
+                event_was_handled = true;
+
+                self.shared_state.set_scale(*scale);
+            }
+            Event::Timer => {
+                event_was_handled = true;
+
+                self.handle_gestures(event, &mut widget_events);
+            }
+            Event::Zoom(delta) => {
+                event_was_handled = true;
+
+                self.set_zoom_factor(self.shared_state.zoom_factor() * (1.0 + delta));
             }
             _ => {}
         }
@@ -390,6 +1717,9 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
                     WidgetEvent::AppEvent(custom_widget_event) => {
                         custom_widget_events.push(custom_widget_event);
                     }
+                    WidgetEvent::CapturedPointer(widget_id) => {
+                        self.pointer_capture_widget_id = Some(widget_id);
+                    }
                     WidgetEvent::GainedFocus(widget_id) => {
                         // A widget gained focus.
                         id_of_the_last_widget_that_gained_focus = Some(widget_id);
@@ -403,6 +1733,12 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
                             }
                         }
                     }
+                    WidgetEvent::ReleasedPointer(widget_id) => {
+                        // The widget that released the pointer had captured it.
+                        if self.pointer_capture_widget_id == Some(widget_id) {
+                            self.pointer_capture_widget_id = None;
+                        }
+                    }
                 }
             }
 
@@ -438,37 +1774,333 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         Ok(custom_widget_events)
     }
 
+    /// Lets the gesture recognizer handle the given event and dispatches the recognized gesture
+    /// events like pointer events. The innermost widget of a gesture event's path that observes the
+    /// gesture produces the widget events.
+    fn handle_gestures(&mut self, event: &Event, widget_events: &mut Vec<WidgetEvent<APP_EVENT>>) {
+        // Iterate over the recognized gesture events.
+        for gesture_event in self.gesture_recognizer.handle_event(event, Instant::now()) {
+            let (widget_event_type, mouse_event) =
+                if let Some(gesture) = gesture_recognizer::gesture(&gesture_event) {
+                    gesture
+                }
+                // The event is no gesture event.
+                else {
+                    continue;
+                };
+
+            self.handle_drag_and_drop(&gesture_event, mouse_event, widget_events);
+
+            let event_path =
+                self.dispatch_pointer_event(&gesture_event, mouse_event, widget_events);
+
+            // Iterate over the path's widgets, from the innermost one.
+            for widget_id in event_path.iter().rev() {
+                let widget_box = if let Some(widget_box) = self.widgets.get(widget_id) {
+                    widget_box
+                }
+                // There is no widget with the given ID.
+                else {
+                    continue;
+                };
+
+                let mut widget = widget_box.borrow_mut();
+                let event_observations = widget.event_observations(&widget_event_type);
+
+                // The widget observes the gesture.
+                if !event_observations.is_empty() {
+                    let widget_event_data = WidgetEventData {
+                        click_count: if widget_event_type == WidgetEventType::DoubleClicked {
+                            2
+                        } else {
+                            0
+                        },
+                        modifiers: modifiers(mouse_event.mods),
+                        ..WidgetEventData::new(*widget_id)
+                    };
+
+                    // Iterate over the widget event observations.
+                    for widget_event_observation in event_observations {
+                        widget_events
+                            .push(widget_event_observation.widget_event(&widget_event_data));
+                    }
+
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Performs the given shortcut action.
+    fn handle_shortcut_action(
+        &mut self,
+        shortcut_action: ShortcutAction<APP_EVENT>,
+        clipboard: Option<&mut Clipboard>,
+        widget_events: &mut Vec<WidgetEvent<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        // Zooming does not need a focused widget.
+        match shortcut_action {
+            ShortcutAction::ResetZoom => {
+                self.set_zoom_factor(1.0);
+                return Ok(());
+            }
+            ShortcutAction::ZoomIn => {
+                self.set_zoom_factor(self.shared_state.zoom_factor() * ZOOM_STEP);
+                return Ok(());
+            }
+            ShortcutAction::ZoomOut => {
+                self.set_zoom_factor(self.shared_state.zoom_factor() / ZOOM_STEP);
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        // A widget has focus.
+        let focused_widget = if let Some(focused_widget) = &self.focused_widget {
+            focused_widget.clone()
+        }
+        // No widget has focus.
+        else {
+            // Only application events do not need a focused widget.
+            if let ShortcutAction::AppEvent(app_event) = shortcut_action {
+                widget_events.push(WidgetEvent::AppEvent(app_event));
+            }
+
+            return Ok(());
+        };
+
+        match shortcut_action {
+            ShortcutAction::AppEvent(app_event) => {
+                widget_events.push(WidgetEvent::AppEvent(app_event));
+            }
+            ShortcutAction::Copy => {
+                // A clipboard is given.
+                if let Some(clipboard) = clipboard {
+                    self.copy_selected_value_to_clipboard(clipboard);
+                }
+            }
+            ShortcutAction::Cut => {
+                // A clipboard is given.
+                if let Some(clipboard) = clipboard {
+                    self.copy_selected_value_to_clipboard(clipboard);
+
+                    // Remove the selected value.
+                    focused_widget.borrow_mut().remove_selected_value(
+                        &mut self.shared_state,
+                        &mut self.widget_id_provider,
+                    )?;
+                }
+            }
+            ShortcutAction::Paste => {
+                // A clipboard is given and it contains a string.
+                if let Some(string) = clipboard.and_then(|clipboard| clipboard.get_string()) {
+                    // Let the focused widget handle a clipboard paste event.
+                    focused_widget.borrow_mut().handle_event(
+                        &Event::ClipboardPaste(string),
+                        &mut self.shared_state,
+                        &mut self.widget_id_provider,
+                        widget_events,
+                    );
+                }
+            }
+            // Zooming was handled above.
+            ShortcutAction::ResetZoom | ShortcutAction::ZoomIn | ShortcutAction::ZoomOut => {}
+            ShortcutAction::SelectAll => {
+                focused_widget.borrow_mut().handle_event(
+                    &Event::SelectAll,
+                    &mut self.shared_state,
+                    &mut self.widget_id_provider,
+                    widget_events,
+                );
+            }
+            ShortcutAction::Undo => {
+                focused_widget.borrow_mut().handle_event(
+                    &Event::Undo,
+                    &mut self.shared_state,
+                    &mut self.widget_id_provider,
+                    widget_events,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the ID of the topmost widget at the given position. Hidden widgets, widgets outside of
+    /// their ancestors' rectangles and widgets below an open context menu are not hit.
+    pub fn hit_test(&self, point: Point) -> Option<WidgetId> {
+        // An open context menu covers the position.
+        if let Some(context_menu) = &self.context_menu {
+            if context_menu.contains(point.x, point.y) {
+                return None;
+            }
+        }
+
+        self.widget_path_at(point).last().cloned()
+    }
+
+    /// Returns whether the widget with the given ID is part of the main widget's widget tree and
+    /// neither it nor one of its ancestors is hidden.
+    fn is_shown(&self, widget_id: WidgetId) -> bool {
+        let main_widget_id = if let Some(main_widget) = &self.main_widget {
+            *main_widget.borrow().widget_id()
+        }
+        // There is no main widget.
+        else {
+            return false;
+        };
+
+        let mut current_widget_id = widget_id;
+
+        loop {
+            // The current widget is hidden or does not exist.
+            match self.widgets.get(&current_widget_id) {
+                Some(widget_box) if !widget_box.borrow().is_hidden() => {}
+                _ => return false,
+            }
+
+            // The main widget was reached.
+            if current_widget_id == main_widget_id {
+                return true;
+            }
+
+            // Continue with the parent widget.
+            if let Some(parent_widget_id) =
+                self.parent_widget_id_per_widget_id.get(&current_widget_id)
+            {
+                current_widget_id = *parent_widget_id;
+            }
+            // The widget is not part of the main widget's widget tree.
+            else {
+                return false;
+            }
+        }
+    }
+
+    /// Returns the number and date format of the current locale, e.g. to validate a `TextInput`'s
+    /// text.
+    pub fn locale_format(&self) -> LocaleFormat {
+        LocaleFormat::new(&self.message_catalogs.locale().to_string())
+    }
+
     ///
     pub fn paint(&self, piet: &mut Piet, region: &Region) -> Result<(), piet::Error> {
+        // Zoom everything that is painted.
+        piet.save()?;
+        piet.transform(Affine::scale(self.shared_state.zoom_factor()));
+
         // There is a main widget.
         if let Some(main_widget) = &self.main_widget {
             // Paint the main widget.
-            main_widget.borrow().paint(piet, region)?;
+            main_widget
+                .borrow()
+                .paint(piet, &self.shared_state, region)?;
+
+            // Dim the disabled widgets.
+            self.paint_disabled_overlays(piet, main_widget)?;
+        }
+
+        // There is a drag.
+        if let Some(drag) = &self.drag {
+            // Paint the drag preview on top of the widgets.
+            drag.paint(piet)?;
+        }
+
+        // There is an open context menu.
+        if let Some(context_menu) = &self.context_menu {
+            // Paint the context menu on top of the widgets.
+            context_menu.paint(piet)?;
+        }
+
+        piet.restore()
+    }
+
+    /// Dims the given widget if it is disabled or, if it is enabled, its disabled descendant
+    /// widgets. Hidden widgets and the descendant widgets of dimmed widgets are skipped.
+    fn paint_disabled_overlays(
+        &self,
+        piet: &mut Piet,
+        widget_box: &WidgetBox<APP_EVENT>,
+    ) -> Result<(), piet::Error> {
+        let widget = widget_box.borrow();
+
+        // The widget and its descendant widgets are not painted.
+        if widget.is_hidden() {
+            return Ok(());
+        }
+
+        // Dim the disabled widget, which dims its descendant widgets as well.
+        if widget.is_disabled() {
+            let rectangle = widget.rectangle();
+            piet.fill(
+                kurbo::Rect::new(rectangle.x0, rectangle.y0, rectangle.x1, rectangle.y1),
+                &self.style.disabled_overlay_color,
+            );
+        }
+        // The widget is enabled.
+        else if let Some(child_widget_ids) =
+            self.child_widget_ids_per_widget_id.get(widget.widget_id())
+        {
+            // Iterate over the child widgets.
+            for child_widget_id in child_widget_ids {
+                // The child widget exists.
+                if let Some(child_widget_box) = self.widgets.get(child_widget_id) {
+                    self.paint_disabled_overlays(piet, child_widget_box)?;
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Opens the context menu of the widget with the given ID at the given position.
+    fn open_context_menu(&mut self, widget_id: WidgetId, origin: Point) {
+        // The widget has a context menu.
+        if let Some(menu_items) = self.context_menu_items_per_widget_id.get(&widget_id) {
+            self.context_menu = Some(ContextMenu::new(
+                menu_items.clone(),
+                origin,
+                *self.size_constraints.maximum(),
+                &self.style,
+                &mut self.shared_state,
+            ));
+        }
+    }
+
     /// Returns a widget's rectangle.
     pub fn rectangle(&self, widget_id: WidgetId) -> Result<Rectangle, WidgetError> {
         Ok(*self.widget(widget_id)?.borrow().rectangle())
     }
 
+    /// Removes the drag's drop target if it is the widget with the given ID, so that the payload is
+    /// not dropped there.
+    fn remove_drag_drop_target(&mut self, widget_id: WidgetId) {
+        // There is a drag.
+        if let Some(drag) = &mut self.drag {
+            // The widget is the drag's drop target.
+            if drag.drop_target_widget_id() == Some(widget_id) {
+                drag.set_drop_target(None);
+            }
+        }
+    }
+
     ///
     fn remove_parent_child_widget_connection(
         &mut self,
         parent_widget_id: WidgetId,
         child_widget_id: WidgetId,
     ) {
-        // Remove the child widget from the focus order.
-        self.widget_focus_order.remove_widget(child_widget_id);
-
         self.child_widget_ids_per_widget_id
             .entry(parent_widget_id)
             .or_default()
-            .remove(&child_widget_id);
+            .retain(|&i| i != child_widget_id);
 
         self.parent_widget_id_per_widget_id.remove(&child_widget_id);
+
+        // The child widget no longer has a disabled ancestor.
+        if let Some(child_widget) = self.widgets.get(&child_widget_id) {
+            child_widget.borrow_mut().set_has_disabled_ancestor(false);
+        }
     }
 
     ///
@@ -478,18 +2110,23 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
             .child_widget_ids_per_widget_id
             .entry(parent_widget_id)
             .or_default()
-            .drain()
+            .drain(..)
         {
-            // Remove the child widget from the focus order.
-            self.widget_focus_order.remove_widget(child_widget_id);
-
             // Remove the parent widget ID for the current child widget ID.
             self.parent_widget_id_per_widget_id.remove(&child_widget_id);
+
+            // The child widget no longer has a disabled ancestor.
+            if let Some(child_widget) = self.widgets.get(&child_widget_id) {
+                child_widget.borrow_mut().set_has_disabled_ancestor(false);
+            }
         }
     }
 
     pub fn resize(&mut self, size: Size) {
-        let adjusted_size = size - Size::new(2.0, 2.0);
+        self.window_size = size;
+
+        // The widgets are laid out in zoomed coordinates.
+        let adjusted_size = size / self.shared_state.zoom_factor() - Size::new(2.0, 2.0);
 
         // Create a new size constraint from the given window size.
         let size_constraints =
@@ -498,6 +2135,9 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         // Use the new size constraint.
         self.size_constraints = size_constraints;
 
+        // Close an open context menu, since it was laid out for the previous window size.
+        self.context_menu = None;
+
         // There is a main widget.
         if let Some(main_widget) = &mut self.main_widget {
             // Resize the main widget.
@@ -507,7 +2147,38 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         }
     }
 
-    pub fn selected_value(&self, widget_id: WidgetId) -> Result<Option<Box<dyn Any>>, WidgetError> {
+    /// Sets the given text as the value of the widget with the given ID.
+    fn set_widget_text(
+        &mut self,
+        widget_id: WidgetId,
+        text: String,
+        next_commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        let widget_box = self.widget(widget_id)?.clone();
+        let mut widget = widget_box.borrow_mut();
+
+        widget.handle_command(
+            WidgetCommand::SetValue(WidgetValue::Text(text)),
+            &mut self.shared_state,
+            &mut self.widget_id_provider,
+            next_commands,
+        )
+    }
+
+    /// Sets the ID of the widget manager's window, which `Command::CloseWindow` closes. It is
+    /// `MAIN_WINDOW_ID` by default, so applications with more windows set it for the others.
+    pub fn set_window_id(&mut self, window_id: WindowId) {
+        self.window_id = window_id;
+    }
+
+    /// Sets the zoom factor, limited to the allowed range, and lays out the widgets again.
+    pub(crate) fn set_zoom_factor(&mut self, zoom_factor: f64) {
+        self.shared_state
+            .set_zoom_factor(zoom_factor.clamp(MINIMUM_ZOOM_FACTOR, MAXIMUM_ZOOM_FACTOR));
+        self.resize(self.window_size);
+    }
+
+    pub fn selected_value(&self, widget_id: WidgetId) -> Result<Option<WidgetValue>, WidgetError> {
         Ok(self.widget(widget_id)?.borrow().selected_value())
     }
 
@@ -516,12 +2187,94 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         &mut self.shared_state
     }
 
+    /// Returns the action of the shortcut that matches the given key event. Shortcuts scoped to the
+    /// focused widget or its ancestors take precedence over outer and global shortcuts.
+    fn shortcut_action(&self, key_event: &KeyEvent) -> Option<ShortcutAction<APP_EVENT>> {
+        let shortcut = Shortcut {
+            has_alt: key_event.mods.alt(),
+            has_ctrl: key_event.mods.ctrl(),
+            has_meta: key_event.mods.meta(),
+            has_shift: key_event.mods.shift(),
+            key: match &key_event.key {
+                KbKey::Character(character) => character.to_lowercase(),
+                key => key.to_string(),
+            },
+        };
+
+        // Start with the focused widget's scope.
+        let mut scope_widget_id = self
+            .focused_widget
+            .as_ref()
+            .map(|focused_widget| *focused_widget.borrow().widget_id());
+
+        loop {
+            // The shortcut exists in the current scope.
+            if let Some(shortcut_action) = self
+                .shortcut_actions_per_scope
+                .get(&scope_widget_id)
+                .and_then(|shortcut_actions| shortcut_actions.get(&shortcut))
+            {
+                return Some(shortcut_action.clone());
+            }
+
+            match scope_widget_id {
+                // Continue with the parent widget's scope or, for the root widget, the global scope.
+                Some(widget_id) => {
+                    scope_widget_id = self.parent_widget_id_per_widget_id.get(&widget_id).cloned();
+                }
+                // The global scope was searched.
+                None => return None,
+            }
+        }
+    }
+
     ///
     pub fn style(&self) -> &Style {
         &self.style
     }
 
-    pub fn value(&self, widget_id: WidgetId) -> Result<Option<Box<dyn Any>>, WidgetError> {
+    /// Returns the ID of the widget whose value is the text of the widget with the given ID: the
+    /// widget itself or, e.g. for a text button, its only child widget.
+    fn text_widget_id(&self, widget_id: WidgetId) -> Result<WidgetId, WidgetError> {
+        let accepts_text = self
+            .widget(widget_id)?
+            .borrow()
+            .accepted_value_kinds()
+            .contains(&WidgetValueKind::Text);
+
+        match self
+            .child_widget_ids_per_widget_id
+            .get(&widget_id)
+            .map(Vec::as_slice)
+        {
+            // The widget's text is its only child widget's value.
+            Some([child_widget_id]) if !accepts_text => self.text_widget_id(*child_widget_id),
+            // The widget's text is its own value or, if it does not accept texts, setting it fails.
+            _ => Ok(widget_id),
+        }
+    }
+
+    /// Looks up the messages of all localized widgets again, e.g. in a new locale.
+    fn update_localized_texts(
+        &mut self,
+        next_commands: &mut Vec<Command<APP_EVENT>>,
+    ) -> Result<(), WidgetError> {
+        let bundle = self.message_catalogs.bundle();
+        let texts = self
+            .message_per_widget_id
+            .iter()
+            .map(|(widget_id, message)| (*widget_id, localized_text(&bundle, message)))
+            .collect::<Vec<_>>();
+
+        // Iterate over the localized widgets' texts.
+        for (widget_id, text) in texts {
+            self.set_widget_text(widget_id, text, next_commands)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn value(&self, widget_id: WidgetId) -> Result<Option<WidgetValue>, WidgetError> {
         Ok(self.widget(widget_id)?.borrow().value())
     }
 
@@ -542,6 +2295,17 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
         &mut self.widget_id_provider
     }
 
+    /// Returns the ID of the innermost widget with a context menu at the given position.
+    fn widget_with_context_menu_at(&self, x: f64, y: f64) -> Option<WidgetId> {
+        self.widget_path_at(Point::new(x, y))
+            .into_iter()
+            .rev()
+            .find(|widget_id| {
+                self.context_menu_items_per_widget_id
+                    .contains_key(widget_id)
+            })
+    }
+
     ///
     fn widget_mut(
         &mut self,
@@ -556,339 +2320,173 @@ impl<APP_EVENT: Clone + 'static> PietWidgetManager<APP_EVENT> {
             Err(WidgetError::NoSuchWidget(widget_id))
         }
     }
-}
 
-impl<APP_EVENT: Clone + 'static> guiver::widget_manager::WidgetManager<APP_EVENT>
-    for PietWidgetManager<APP_EVENT>
-{
-    fn handle_commands(&mut self, commands: Vec<Command<APP_EVENT>>) -> Result<(), WidgetError> {
-        let mut commands = commands;
+    /// Returns the IDs of the given widget's ancestors and of the widget itself, from the outermost
+    /// ancestor down to the widget.
+    fn widget_path(&self, widget_id: WidgetId) -> Vec<WidgetId> {
+        let mut widget_path = vec![widget_id];
+        let mut current_widget_id = widget_id;
+
+        // Continue with the parent widget.
+        while let Some(parent_widget_id) =
+            self.parent_widget_id_per_widget_id.get(&current_widget_id)
+        {
+            widget_path.push(*parent_widget_id);
+            current_widget_id = *parent_widget_id;
+        }
+
+        widget_path.reverse();
+        widget_path
+    }
+
+    /// Returns the IDs of the widgets at the given position, from the main widget down to the topmost
+    /// widget. Hidden widgets are skipped. A widget is only hit within its ancestors' rectangles,
+    /// which clip it. Of overlapping child widgets, the last one is on top.
+    fn widget_path_at(&self, point: Point) -> Vec<WidgetId> {
+        let is_hit = |widget_id: &WidgetId| {
+            self.widgets
+                .get(widget_id)
+                .map(|widget_box| {
+                    let widget = widget_box.borrow();
+                    !widget.is_hidden() && widget.rectangle().contains(point.x, point.y)
+                })
+                .unwrap_or(false)
+        };
+
+        let mut widget_path = vec![];
+
+        let mut current_widget_id = match &self.main_widget {
+            // The main widget is at the given position.
+            Some(main_widget) if is_hit(main_widget.borrow().widget_id()) => {
+                *main_widget.borrow().widget_id()
+            }
+            // There is no main widget or it is not hit.
+            _ => return widget_path,
+        };
 
         loop {
-            let mut next_commands = vec![];
+            widget_path.push(current_widget_id);
 
-            // Iterate over the given commands.
-            for command in commands {
-                match command {
-                    Command::AddChild {
-                        parent_widget_id,
-                        widget_placement,
-                        child_widget_id,
-                        ..
-                    } => {
-                        let widget_box = self.widget(parent_widget_id)?;
+            // A child widget of the current widget is at the given position.
+            if let Some(child_widget_id) = self
+                .child_widget_ids_per_widget_id
+                .get(&current_widget_id)
+                .and_then(|child_widget_ids| child_widget_ids.iter().rev().find(|id| is_hit(id)))
+            {
+                current_widget_id = *child_widget_id;
+            }
+            // The current widget is the innermost widget at the given position.
+            else {
+                return widget_path;
+            }
+        }
+    }
 
-                        // There is a widget with the child widget ID from the command.
-                        let child_widget_box =
-                            if let Some(child_widget_box) = self.widgets.get(&child_widget_id) {
-                                child_widget_box
-                            }
-                            // There is no widget with the given child ID.
-                            else {
-                                return Err(WidgetError::NoSuchWidget(child_widget_id));
-                            };
+    /// Returns the IDs of the main widget's widget tree in depth-first order. Siblings are in visual
+    /// order, since the child widget IDs are not ordered.
+    fn widget_tree_order(&self) -> Vec<WidgetId> {
+        let mut widget_ids = vec![];
 
-                        widget_box
-                            .borrow_mut()
-                            .add_child(widget_placement, child_widget_box.clone())?;
+        // There is a main widget.
+        if let Some(main_widget) = &self.main_widget {
+            let mut widget_ids_to_visit = vec![*main_widget.borrow().widget_id()];
 
-                        self.add_parent_child_widget_connection(parent_widget_id, child_widget_id);
-                    }
-                    Command::AddChildren {
-                        parent_widget_id,
-                        child_widgets,
-                        ..
-                    } => {
-                        // Iterate over the child widgets. This additional loop is in order to escape
-                        // the borrow checker.
-                        for (_, child_widget_id) in &child_widgets {
-                            self.add_parent_child_widget_connection(
-                                parent_widget_id,
-                                *child_widget_id,
-                            );
-                        }
+            while let Some(widget_id) = widget_ids_to_visit.pop() {
+                widget_ids.push(widget_id);
 
-                        let widget_box = self.widget(parent_widget_id)?;
+                // The widget has child widgets.
+                if let Some(child_widget_ids) = self.child_widget_ids_per_widget_id.get(&widget_id)
+                {
+                    let child_widget_ids = widget_focus_order::geometric_order(
+                        child_widget_ids
+                            .iter()
+                            .filter_map(|child_widget_id| {
+                                self.widgets.get(child_widget_id).map(|widget_box| {
+                                    (*child_widget_id, *widget_box.borrow().rectangle())
+                                })
+                            })
+                            .collect(),
+                    );
 
-                        // Iterate over the child widgets.
-                        for (widget_placement, child_widget_id) in child_widgets {
-                            // There is a widget with the child widget ID from the command.
-                            let child_widget_box = if let Some(child_widget_box) =
-                                self.widgets.get(&child_widget_id)
-                            {
-                                child_widget_box
-                            }
-                            // There is no widget with the given child ID.
-                            else {
-                                return Err(WidgetError::NoSuchWidget(child_widget_id));
-                            };
-
-                            widget_box
-                                .borrow_mut()
-                                .add_child(widget_placement, child_widget_box.clone())?;
-                        }
-                    }
-                    Command::AddEventObservation(widget_id, widget_event_type, custom_value) => {
-                        let widget_box = self.widget(widget_id)?;
+                    // Visit the first child widget next.
+                    widget_ids_to_visit.extend(child_widget_ids.into_iter().rev());
+                }
+            }
+        }
 
-                        widget_box.borrow_mut().add_event_observation(
-                            widget_event_type.clone(),
-                            WidgetEvent::AppEvent(custom_value),
-                        );
-                    }
-                    Command::CreateWidget(widget_id, widget_type) => {
-                        // A widget with the given ID exists already.
-                        if self.widgets.contains_key(&widget_id) {
-                            return Err(WidgetError::WidgetExistsAlready(widget_id));
-                        }
+        widget_ids
+    }
 
-                        let widget_box: Box<dyn PietWidget<APP_EVENT>> = match widget_type {
-                            WidgetType::Hyperlink(text) => {
-                                let mut font_unvisited = self.style.font.clone();
-                                font_unvisited.font_color = Color::rgb8(100, 100, 255);
-
-                                let mut font_being_clicked = self.style.font.clone();
-                                font_being_clicked.font_color = self.style.accent_color;
-
-                                let mut font_visited = self.style.font.clone();
-                                font_visited.font_color = Color::rgb8(50, 50, 100);
-
-                                Box::new(Hyperlink::new(
-                                    widget_id,
-                                    self.style.debug_rendering_stroke.clone(),
-                                    self.shared_state.piet_text(),
-                                    font_unvisited,
-                                    font_being_clicked,
-                                    font_visited,
-                                    text,
-                                ))
-                            }
-                            WidgetType::LayoutCenter => Box::new(Center::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                            )),
-                            WidgetType::LayoutColumn => Box::new(Column::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                HorizontalAlignment::Center,
-                                self.style.spacing,
-                            )),
-                            WidgetType::LayoutExpanded { flex_factor } => Box::new(Expanded::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                flex_factor,
-                            )),
-                            WidgetType::LayoutGrid {
-                                column_properties,
-                                row_properties,
-                            } => Box::new(Grid::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                column_properties,
-                                row_properties,
-                            )),
-                            WidgetType::LayoutPadding => Box::new(Padding::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                self.style.padding,
-                                self.style.padding,
-                                self.style.padding,
-                                self.style.padding,
-                            )),
-                            WidgetType::LayoutRow => Box::new(Row::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                self.style.vertical_alignment,
-                                self.style.spacing,
-                            )),
-                            WidgetType::LayoutSizedBox { desired_size } => Box::new(SizedBox::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                desired_size,
-                            )),
-                            WidgetType::Placeholder { maximum_size } => Box::new(Placeholder::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                maximum_size,
-                            )),
-                            WidgetType::Text(text) => Box::new(Text::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                self.shared_state.piet_text(),
-                                self.style.font.clone(),
-                                text,
-                            )),
-                            WidgetType::TextButton(text) => {
-                                let child_widget = Text::new(
-                                    self.widget_id_provider.next_widget_id(),
-                                    self.style.debug_rendering_stroke.clone(),
-                                    self.shared_state.piet_text(),
-                                    self.style.font.clone(),
-                                    text,
-                                );
-
-                                Box::new(Button::new(
-                                    widget_id,
-                                    self.style.debug_rendering_stroke.clone(),
-                                    Rc::new(RefCell::new(Box::new(child_widget))),
-                                    Some(PaintBrush::Color(self.style.accent_color)),
-                                    Some(self.style.frame_color),
-                                    Some(self.style.accent_color),
-                                ))
-                            }
-                            WidgetType::TextInput { text, width } => Box::new(TextInput::new(
-                                widget_id,
-                                self.style.debug_rendering_stroke.clone(),
-                                self.shared_state.piet_text(),
-                                self.style.font.clone(),
-                                text,
-                                width,
-                                self.style.frame_color,
-                                self.style.accent_color,
-                            )),
-                        };
+    /// Returns a copy of the given event in which pointer positions are converted from window
+    /// coordinates to zoomed coordinates, in which the widgets are laid out.
+    fn zoomed_event(&self, event: &Event) -> Event {
+        let zoom_factor = self.shared_state.zoom_factor();
+        let mut zoomed_event = event.clone();
+
+        match &mut zoomed_event {
+            Event::DoubleClicked(mouse_event)
+            | Event::DragEnded(mouse_event)
+            | Event::DragStarted(mouse_event)
+            | Event::Dragged(mouse_event)
+            | Event::LongPressed(mouse_event)
+            | Event::MouseDown(mouse_event)
+            | Event::MouseMove(mouse_event)
+            | Event::MouseUp(mouse_event)
+            | Event::MouseWheel(mouse_event) => {
+                mouse_event.pos = (mouse_event.pos.to_vec2() / zoom_factor).to_point();
+            }
+            _ => {}
+        }
 
-                        self.add_widget(Rc::new(RefCell::new(widget_box)));
-                    }
-                    Command::Destroy(widget_id) => self.destroy_widget(widget_id),
-                    Command::RemoveChild {
-                        parent_widget_id,
-                        child_widget_id,
-                        destroy_child_widget: destroy,
-                    } => {
-                        let widget_box = self.widget(parent_widget_id)?;
-                        widget_box.borrow_mut().remove_child(child_widget_id)?;
+        zoomed_event
+    }
 
-                        // Destroy the child widget.
-                        if destroy {
-                            self.destroy_widget(parent_widget_id);
-                        }
-                        // Remove the child widget.
-                        else {
-                            self.remove_parent_child_widget_connection(
-                                parent_widget_id,
-                                child_widget_id,
-                            );
-                        }
-                    }
-                    Command::RemoveChildren {
-                        parent_widget_id,
-                        destroy_child_widgets,
-                        ..
-                    } => {
-                        let widget_box = self.widget(parent_widget_id)?;
-                        widget_box.borrow_mut().remove_children()?;
-
-                        // Destroy the child widgets.
-                        if destroy_child_widgets {
-                            // Get the widget's the child widget IDs.
-                            let child_widget_ids = self
-                                .child_widget_ids_per_widget_id
-                                .entry(parent_widget_id)
-                                .or_default()
-                                .clone();
-
-                            // Iterate over the child widget IDs.
-                            for child_widget_id in child_widget_ids {
-                                // Destroy the child widget.
-                                self.destroy_widget(child_widget_id);
-                            }
-                        }
-                        // Remove the child widgets.
-                        else {
-                            self.remove_parent_child_widget_connections(parent_widget_id);
-                        }
-                    }
-                    Command::RemoveEventObservation(widget_id, widget_event_type) => {
-                        let widget_box = self.widget(widget_id)?;
+    /// Returns and clears the requests to the window, e.g. to set the menu bar. Applications pass
+    /// them on in `PietApplication::window_requests()`.
+    pub fn window_requests(&mut self) -> Vec<WindowRequest> {
+        let text_field_state = self.focused_text_field_state();
 
-                        widget_box
-                            .borrow_mut()
-                            .remove_event_observation(&widget_event_type);
-                    }
-                    Command::SetDebugRendering(widget_id, debug_rendering) => {
-                        let widget_box = self.widget(widget_id)?;
-                        widget_box.borrow_mut().set_debug_rendering(debug_rendering);
-                    }
-                    Command::SetHasFocus(widget_id, has_focus) => {
-                        // There is a widget with the given ID.
-                        let widget_box = if let Some(widget_box) = self.widgets.get(&widget_id) {
-                            widget_box
-                        }
-                        // There is no widget with the given ID.
-                        else {
-                            return Err(WidgetError::NoSuchWidget(widget_id));
-                        };
+        // The focused text widget's state changed, e.g. it gained focus or its text changed.
+        if text_field_state != self.text_field_state {
+            self.text_field_state = text_field_state.clone();
+            self.window_requests
+                .push(WindowRequest::SetTextField(text_field_state));
+        }
 
-                        let mut widget_had_focus_already = false;
+        std::mem::take(&mut self.window_requests)
+    }
+}
 
-                        // A widget had focus.
-                        if let Some(focused_widget) = &mut self.focused_widget {
-                            // The widgets are different.
-                            if *focused_widget.borrow().widget_id() != widget_id {
-                                // Unfocus that widget.
-                                focused_widget.borrow_mut().set_has_focus(false)?;
-                            }
-                            // The widgets are the same.
-                            else {
-                                widget_had_focus_already = true;
-                            }
-                        }
+impl<APP_EVENT: Clone + 'static> guiver::widget_manager::WidgetManager<APP_EVENT>
+    for PietWidgetManager<APP_EVENT>
+{
+    fn handle_commands(&mut self, commands: Vec<Command<APP_EVENT>>) -> Result<(), WidgetError> {
+        // Remember the current state, in order to restore it if a command fails.
+        let mut snapshot = Snapshot::new(self);
 
-                        if !widget_had_focus_already {
-                            // Tell the widget it has focus.
-                            widget_box.borrow_mut().set_has_focus(has_focus)?;
+        let mut commands = commands;
+        let mut command_index = 0;
 
-                            // Select the widget in the focus order.
-                            self.widget_focus_order.focus_widget(widget_id);
+        loop {
+            let mut next_commands = vec![];
 
-                            // Remember the current widget as focused.
-                            self.focused_widget = Some(widget_box.clone());
-                        }
-                    }
-                    Command::SetHorizontalAlignment(widget_id, horizontal_alignment) => {
-                        let widget_box = self.widget(widget_id)?;
-                        widget_box
-                            .borrow_mut()
-                            .set_horizontal_alignment(horizontal_alignment)?;
-                    }
-                    Command::SetIsDisabled(widget_id, is_disabled) => {
-                        let widget_box = self.widget(widget_id)?;
-                        widget_box.borrow_mut().set_is_disabled(is_disabled);
-                    }
-                    Command::SetIsHidden(widget_id, is_hidden) => {
-                        let widget_box = self.widget(widget_id)?;
-                        widget_box.borrow_mut().set_is_hidden(is_hidden);
-                    }
-                    Command::SetMainWidget(widget_id) => {
-                        let widget_box = self.widget(widget_id)?;
-                        widget_box.borrow_mut().set_origin(Point::new(1.0, 1.0));
-                        self.main_widget = Some(widget_box.clone());
-                    }
-                    Command::SetValue(widget_id, value) => {
-                        // There is a widget with the given ID.
-                        let widget_box = if let Some(widget_box) = self.widgets.get(&widget_id) {
-                            widget_box
-                        }
-                        // There is no widget with the given ID.
-                        else {
-                            return Err(WidgetError::NoSuchWidget(widget_id));
-                        };
+            // Iterate over the given commands.
+            for command in commands {
+                // Copy the widgets that the command changes.
+                self.copy_widgets_changed_by(&command, &mut snapshot);
+
+                // The command failed.
+                if let Err(error) = self.apply_command(command, &mut next_commands) {
+                    // Undo the batch's previous commands.
+                    snapshot.restore(self);
+
+                    return Err(WidgetError::CommandFailed {
+                        command_index,
+                        error: Box::new(error),
+                    });
+                }
 
-                        widget_box.borrow_mut().set_value(
-                            value,
-                            &mut self.shared_state,
-                            &mut self.widget_id_provider,
-                            &mut next_commands,
-                        )?;
-                    }
-                    Command::SetVerticalAlignment(widget_id, vertical_alignment) => {
-                        let widget_box = self.widget(widget_id)?;
-                        widget_box
-                            .borrow_mut()
-                            .set_vertical_alignment(vertical_alignment)?;
-                    }
-                };
+                command_index += 1;
             }
 
             if next_commands.is_empty() {
@@ -910,3 +2508,1260 @@ impl<APP_EVENT: Clone + 'static> guiver::widget_manager::WidgetManager<APP_EVENT
         Ok(())
     }
 }
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::shared_state::PietSharedState;
+    use crate::widget_manager::{Command, PietWidgetManager, WidgetType};
+    use crate::{Event, EventPhase, EventPropagation, PietWidget, WindowRequest, MAIN_WINDOW_ID};
+    use druid_shell::piet::Piet;
+    use druid_shell::{kurbo, piet, KbKey, KeyEvent, Modifiers, MouseButton, MouseEvent, Region};
+    use guiver::widget_manager::WidgetManager;
+    use guiver::{
+        Color, DragPayload, DropTarget, EventObservationHandle, FileDialogOptions, Font,
+        LayoutDirection, Message, Point, Rectangle, Shortcut, ShortcutAction, Size,
+        SizeConstraints, Widget, WidgetCommand, WidgetError, WidgetEvent, WidgetEventObservation,
+        WidgetEventType, WidgetId, WidgetIdProvider, WidgetValue, WidgetValueKind,
+    };
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Wraps a widget and records the mouse down and wheel events that it is given. It stops the
+    /// propagation in the given phase.
+    struct EventRecorder {
+        /// The recorded events' widget IDs and phases. Events given to `handle_event()` are
+        /// recorded with the target phase.
+        recorded_events: Rc<RefCell<Vec<(WidgetId, EventPhase)>>>,
+        stop_event_phase: Option<EventPhase>,
+        widget: Box<dyn PietWidget<()>>,
+    }
+
+    impl EventRecorder {
+        /// Wraps the managed widget with the given ID in place, so that its parent widget keeps it.
+        fn wrap(
+            widget_manager: &PietWidgetManager<()>,
+            widget_id: WidgetId,
+            recorded_events: &Rc<RefCell<Vec<(WidgetId, EventPhase)>>>,
+            stop_event_phase: Option<EventPhase>,
+        ) {
+            let widget_box = widget_manager.widgets[&widget_id].clone();
+            let widget = widget_box.borrow().boxed_clone();
+
+            *widget_box.borrow_mut() = Box::new(EventRecorder {
+                recorded_events: recorded_events.clone(),
+                stop_event_phase,
+                widget,
+            });
+        }
+    }
+
+    impl Widget<()> for EventRecorder {
+        fn add_event_observation(
+            &mut self,
+            event_observation_handle: EventObservationHandle,
+            widget_event_type: WidgetEventType,
+            widget_event_observation: WidgetEventObservation<()>,
+        ) {
+            self.widget.add_event_observation(
+                event_observation_handle,
+                widget_event_type,
+                widget_event_observation,
+            );
+        }
+
+        fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size {
+            self.widget.apply_size_constraints(size_constraints)
+        }
+
+        fn event_observations(
+            &mut self,
+            widget_event_type: &WidgetEventType,
+        ) -> Vec<&WidgetEventObservation<()>> {
+            self.widget.event_observations(widget_event_type)
+        }
+
+        fn is_disabled(&self) -> bool {
+            self.widget.is_disabled()
+        }
+
+        fn is_hidden(&self) -> bool {
+            self.widget.is_hidden()
+        }
+
+        fn rectangle(&self) -> &Rectangle {
+            self.widget.rectangle()
+        }
+
+        fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle) {
+            self.widget
+                .remove_event_observation(event_observation_handle);
+        }
+
+        fn set_debug_rendering(&mut self, debug_rendering: bool) {
+            self.widget.set_debug_rendering(debug_rendering);
+        }
+
+        fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool) {
+            self.widget.set_has_disabled_ancestor(has_disabled_ancestor);
+        }
+
+        fn set_origin(&mut self, origin: Point) {
+            self.widget.set_origin(origin);
+        }
+
+        fn widget_id(&self) -> &WidgetId {
+            self.widget.widget_id()
+        }
+    }
+
+    impl PietWidget<()> for EventRecorder {
+        fn boxed_clone(&self) -> Box<dyn PietWidget<()>> {
+            Box::new(EventRecorder {
+                recorded_events: self.recorded_events.clone(),
+                stop_event_phase: self.stop_event_phase,
+                widget: self.widget.boxed_clone(),
+            })
+        }
+
+        fn handle_command(
+            &mut self,
+            widget_command: WidgetCommand,
+            shared_state: &mut PietSharedState,
+            widget_id_provider: &mut WidgetIdProvider,
+            commands: &mut Vec<Command<()>>,
+        ) -> Result<(), WidgetError> {
+            self.widget
+                .handle_command(widget_command, shared_state, widget_id_provider, commands)
+        }
+
+        fn handle_event(
+            &mut self,
+            event: &Event,
+            shared_state: &mut PietSharedState,
+            widget_id_provider: &mut WidgetIdProvider,
+            widget_events: &mut Vec<WidgetEvent<()>>,
+        ) {
+            // The event is a mouse down or wheel event.
+            if let Event::MouseDown(_) | Event::MouseWheel(_) = event {
+                self.recorded_events
+                    .borrow_mut()
+                    .push((*self.widget_id(), EventPhase::Target));
+            }
+
+            self.widget
+                .handle_event(event, shared_state, widget_id_provider, widget_events);
+        }
+
+        fn handle_dispatched_event(
+            &mut self,
+            event: &Event,
+            event_phase: EventPhase,
+            _shared_state: &mut PietSharedState,
+            _widget_id_provider: &mut WidgetIdProvider,
+            _widget_events: &mut Vec<WidgetEvent<()>>,
+        ) -> EventPropagation {
+            // The event is a mouse down or wheel event.
+            if let Event::MouseDown(_) | Event::MouseWheel(_) = event {
+                self.recorded_events
+                    .borrow_mut()
+                    .push((*self.widget_id(), event_phase));
+            }
+
+            // The widget stops the propagation in this phase.
+            if self.stop_event_phase == Some(event_phase) {
+                EventPropagation::Stop
+            }
+            // The widget lets the event propagate.
+            else {
+                EventPropagation::Continue
+            }
+        }
+
+        fn paint(
+            &self,
+            piet: &mut Piet,
+            shared_state: &PietSharedState,
+            region: &Region,
+        ) -> Result<(), piet::Error> {
+            self.widget.paint(piet, shared_state, region)
+        }
+    }
+
+    /// Returns a left mouse button event at the given position.
+    fn mouse_event(x: f64, y: f64) -> MouseEvent {
+        MouseEvent {
+            pos: kurbo::Point::new(x, y),
+            buttons: Default::default(),
+            mods: Default::default(),
+            count: 1,
+            focus: false,
+            button: MouseButton::Left,
+            wheel_delta: kurbo::Vec2::ZERO,
+        }
+    }
+
+    /// Dispatches a pointer event, created by the given function, to the innermost of three nested
+    /// widgets, with the given widgets stopping the propagation in the given phases. Returns the
+    /// events that the widgets recorded.
+    fn recorded_pointer_events(
+        event: fn(MouseEvent) -> Event,
+        stop_event_phases: [Option<EventPhase>; 3],
+    ) -> Vec<(WidgetId, EventPhase)> {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(0, WidgetType::LayoutColumn),
+                Command::CreateWidget(1, WidgetType::LayoutColumn),
+                Command::CreateWidget(
+                    2,
+                    WidgetType::Placeholder {
+                        maximum_size: Size::new(10.0, 10.0),
+                    },
+                ),
+                Command::CreateWidget(
+                    3,
+                    WidgetType::Placeholder {
+                        maximum_size: Size::new(10.0, 10.0),
+                    },
+                ),
+                Command::AddChildren {
+                    parent_widget_id: 0,
+                    child_widgets: vec![(None, 1), (None, 3)],
+                },
+                Command::AddChild {
+                    parent_widget_id: 1,
+                    widget_placement: None,
+                    child_widget_id: 2,
+                },
+                Command::SetMainWidget(0),
+            ])
+            .unwrap();
+        widget_manager.resize(kurbo::Size::new(100.0, 100.0));
+
+        let recorded_events = Rc::new(RefCell::new(vec![]));
+
+        // Iterate over the nested widgets.
+        for (widget_id, stop_event_phase) in [0, 1, 2].into_iter().zip(stop_event_phases) {
+            EventRecorder::wrap(
+                &widget_manager,
+                widget_id,
+                &recorded_events,
+                stop_event_phase,
+            );
+        }
+        EventRecorder::wrap(&widget_manager, 3, &recorded_events, None);
+
+        let rectangle = widget_manager.rectangle(2).unwrap();
+        widget_manager
+            .handle_event(
+                &event(mouse_event(rectangle.x0 + 1.0, rectangle.y0 + 1.0)),
+                None,
+            )
+            .unwrap();
+
+        let recorded_events = recorded_events.borrow().clone();
+        recorded_events
+    }
+
+    #[test]
+    fn test_handle_commands() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let placeholder = |widget_id| {
+            Command::CreateWidget(
+                widget_id,
+                WidgetType::Placeholder {
+                    maximum_size: Size::new(10.0, 10.0),
+                },
+            )
+        };
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(0, WidgetType::LayoutColumn),
+                placeholder(1),
+                Command::AddChild {
+                    parent_widget_id: 0,
+                    widget_placement: None,
+                    child_widget_id: 1,
+                },
+                Command::SetMainWidget(0),
+            ])
+            .unwrap();
+
+        // The last command of the batch fails, since there is no widget with ID 3.
+        let result = widget_manager.handle_commands(vec![
+            placeholder(2),
+            Command::AddChild {
+                parent_widget_id: 0,
+                widget_placement: None,
+                child_widget_id: 2,
+            },
+            Command::ToWidget(1, vec![WidgetCommand::SetIsHidden(true)]),
+            Command::ToWidget(3, vec![WidgetCommand::SetIsDisabled(true)]),
+        ]);
+
+        match result {
+            Err(WidgetError::CommandFailed {
+                command_index,
+                error,
+            }) => {
+                assert_eq!(command_index, 3);
+                assert!(matches!(*error, WidgetError::NoSuchWidget(3)));
+            }
+            _ => panic!("The batch should fail"),
+        }
+
+        // None of the batch's commands was applied.
+        assert!(!widget_manager.widgets.contains_key(&2));
+        assert!(!widget_manager
+            .parent_widget_id_per_widget_id
+            .contains_key(&2));
+        assert_eq!(
+            widget_manager.child_widget_ids_per_widget_id.get(&0),
+            Some(&[1].into())
+        );
+        assert!(!widget_manager.widgets.get(&1).unwrap().borrow().is_hidden());
+    }
+
+    #[test]
+    fn test_add_internally_owned_child() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let column = widget_manager.widget_id_provider().next_widget_id();
+        let button = widget_manager.widget_id_provider().next_widget_id();
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(column, WidgetType::LayoutColumn),
+                Command::CreateWidget(button, WidgetType::TextButton("Button".to_string())),
+            ])
+            .unwrap();
+        let text = widget_manager.child_widget_ids_per_widget_id[&button][0];
+
+        // The button owns its text, so it can not be moved into another widget.
+        let result = widget_manager.handle_commands(vec![Command::AddChild {
+            parent_widget_id: column,
+            widget_placement: None,
+            child_widget_id: text,
+        }]);
+
+        match result {
+            Err(WidgetError::CommandFailed {
+                command_index,
+                error,
+            }) => {
+                assert_eq!(command_index, 0);
+                assert!(
+                    matches!(*error, WidgetError::NotHandled { widget_id, .. } if widget_id == button)
+                );
+            }
+            _ => panic!("The batch should fail"),
+        }
+
+        // The text is still the button's child.
+        assert_eq!(
+            widget_manager.parent_widget_id_per_widget_id.get(&text),
+            Some(&button)
+        );
+        assert!(!widget_manager
+            .child_widget_ids_per_widget_id
+            .contains_key(&column));
+    }
+
+    #[test]
+    fn test_widget_commands() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+
+        widget_manager
+            .handle_command(Command::CreateWidget(
+                0,
+                WidgetType::Placeholder {
+                    maximum_size: Size::new(10.0, 10.0),
+                },
+            ))
+            .unwrap();
+
+        // A widget command that the widget does not support fails the whole bundle.
+        let result = widget_manager.handle_command(Command::ToWidget(
+            0,
+            vec![
+                WidgetCommand::SetIsHidden(true),
+                WidgetCommand::SetValue(WidgetValue::Bool(true)),
+            ],
+        ));
+
+        match result {
+            Err(WidgetError::CommandFailed {
+                command_index,
+                error,
+            }) => {
+                assert_eq!(command_index, 0);
+                assert!(matches!(
+                    *error,
+                    WidgetError::NotHandled { widget_id: 0, .. }
+                ));
+            }
+            _ => panic!("The bundle should fail"),
+        }
+
+        // The bundle's previous widget commands were undone.
+        assert!(!widget_manager.widgets[&0].borrow().is_hidden());
+    }
+
+    #[test]
+    fn test_style_commands() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(
+                    0,
+                    WidgetType::Placeholder {
+                        maximum_size: Size::new(10.0, 10.0),
+                    },
+                ),
+                Command::CreateWidget(1, WidgetType::Text("Text".to_string())),
+            ])
+            .unwrap();
+
+        // Widgets accept the style commands that apply to them.
+        widget_manager
+            .handle_commands(vec![
+                Command::ToWidget(
+                    0,
+                    vec![
+                        WidgetCommand::SetFill(Some(Color::WHITE)),
+                        WidgetCommand::SetStroke(None),
+                    ],
+                ),
+                Command::ToWidget(1, vec![WidgetCommand::SetFont(Font::default())]),
+            ])
+            .unwrap();
+
+        // A placeholder has no text, so it does not support fonts.
+        let result = widget_manager.handle_command(Command::ToWidget(
+            0,
+            vec![WidgetCommand::SetFont(Font::default())],
+        ));
+
+        match result {
+            Err(WidgetError::CommandFailed { error, .. }) => {
+                assert!(matches!(
+                    *error,
+                    WidgetError::NotHandled { widget_id: 0, .. }
+                ));
+            }
+            _ => panic!("The command should fail"),
+        }
+    }
+
+    #[test]
+    fn test_widget_values() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let text_input = widget_manager.widget_id_provider().next_widget_id();
+
+        widget_manager
+            .handle_command(Command::CreateWidget(
+                text_input,
+                WidgetType::TextInput {
+                    text: "abc".to_string(),
+                    width: 100.0,
+                },
+            ))
+            .unwrap();
+
+        // A value of a kind that the widget does not accept fails the batch.
+        let result = widget_manager.handle_commands(vec![
+            Command::ToWidget(
+                text_input,
+                vec![WidgetCommand::SetValue(WidgetValue::from("def"))],
+            ),
+            Command::ToWidget(
+                text_input,
+                vec![WidgetCommand::SetValue(WidgetValue::Bool(true))],
+            ),
+        ]);
+
+        match result {
+            Err(WidgetError::CommandFailed {
+                command_index,
+                error,
+            }) => {
+                assert_eq!(command_index, 1);
+                assert!(matches!(
+                    *error,
+                    WidgetError::ValueKindNotAccepted {
+                        widget_id,
+                        value_kind: WidgetValueKind::Bool,
+                    } if widget_id == text_input
+                ));
+            }
+            _ => panic!("The batch should fail"),
+        }
+
+        // The batch's first value was undone.
+        assert_eq!(
+            widget_manager.value(text_input).unwrap().unwrap().as_text(),
+            Some("abc")
+        );
+    }
+
+    #[test]
+    fn test_widget_path() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let placeholder = |widget_id| {
+            Command::CreateWidget(
+                widget_id,
+                WidgetType::Placeholder {
+                    maximum_size: Size::new(10.0, 10.0),
+                },
+            )
+        };
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(0, WidgetType::LayoutColumn),
+                placeholder(1),
+                placeholder(2),
+                Command::AddChildren {
+                    parent_widget_id: 0,
+                    child_widgets: vec![(None, 1), (None, 2)],
+                },
+                Command::SetMainWidget(0),
+            ])
+            .unwrap();
+        widget_manager.resize(kurbo::Size::new(100.0, 100.0));
+
+        assert_eq!(widget_manager.widget_path(2), vec![0, 2]);
+
+        // The path at a position leads to the innermost widget there.
+        let rectangle = widget_manager.rectangle(2).unwrap();
+        let point = Point::new(rectangle.x0 + 1.0, rectangle.y0 + 1.0);
+        assert_eq!(widget_manager.widget_path_at(point), vec![0, 2]);
+        assert_eq!(widget_manager.hit_test(point), Some(2));
+        assert_eq!(widget_manager.hit_test(Point::new(-1.0, -1.0)), None);
+
+        // Hidden widgets are skipped.
+        widget_manager
+            .handle_command(Command::ToWidget(2, vec![WidgetCommand::SetIsHidden(true)]))
+            .unwrap();
+        assert_eq!(widget_manager.hit_test(point), Some(0));
+    }
+
+    #[test]
+    fn test_pointer_capture() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let column = widget_manager.widget_id_provider().next_widget_id();
+        let button = widget_manager.widget_id_provider().next_widget_id();
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(column, WidgetType::LayoutColumn),
+                Command::CreateWidget(button, WidgetType::TextButton("Button".to_string())),
+                Command::AddChild {
+                    parent_widget_id: column,
+                    widget_placement: None,
+                    child_widget_id: button,
+                },
+                Command::SetMainWidget(column),
+                Command::AddEventObservation(button, 1, WidgetEventType::Clicked, ()),
+            ])
+            .unwrap();
+        widget_manager.resize(kurbo::Size::new(200.0, 200.0));
+
+        let rectangle = widget_manager.rectangle(button).unwrap();
+        let inside = mouse_event(rectangle.x0 + 1.0, rectangle.y0 + 1.0);
+        let outside = mouse_event(rectangle.x1 + 1.0, rectangle.y1 + 1.0);
+
+        // Pressing the button captures the pointer.
+        widget_manager
+            .handle_event(&Event::MouseDown(inside.clone()), None)
+            .unwrap();
+        assert_eq!(widget_manager.pointer_capture_widget_id, Some(button));
+
+        // Releasing the pointer outside of the button releases it without a click.
+        let app_events = widget_manager
+            .handle_event(&Event::MouseUp(outside.clone()), None)
+            .unwrap();
+        assert!(app_events.is_empty());
+        assert_eq!(widget_manager.pointer_capture_widget_id, None);
+
+        // Releasing the pointer inside of the button clicks it.
+        widget_manager
+            .handle_event(&Event::MouseDown(inside.clone()), None)
+            .unwrap();
+        let app_events = widget_manager
+            .handle_event(&Event::MouseUp(inside), None)
+            .unwrap();
+        assert_eq!(app_events.len(), 1);
+
+        // Pressing the pointer outside of the focused button takes its focus away.
+        assert!(widget_manager.focused_widget.is_some());
+        widget_manager
+            .handle_event(&Event::MouseDown(outside), None)
+            .unwrap();
+        assert!(widget_manager.focused_widget.is_none());
+    }
+
+    #[test]
+    fn test_disabled_container() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let column = widget_manager.widget_id_provider().next_widget_id();
+        let row = widget_manager.widget_id_provider().next_widget_id();
+        let button = widget_manager.widget_id_provider().next_widget_id();
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(column, WidgetType::LayoutColumn),
+                Command::CreateWidget(row, WidgetType::LayoutRow),
+                Command::CreateWidget(button, WidgetType::TextButton("Button".to_string())),
+                Command::AddChild {
+                    parent_widget_id: column,
+                    widget_placement: None,
+                    child_widget_id: row,
+                },
+                Command::AddChild {
+                    parent_widget_id: row,
+                    widget_placement: None,
+                    child_widget_id: button,
+                },
+                Command::SetMainWidget(column),
+                Command::AddEventObservation(button, 1, WidgetEventType::Clicked, ()),
+            ])
+            .unwrap();
+        widget_manager.resize(kurbo::Size::new(200.0, 200.0));
+
+        let rectangle = widget_manager.rectangle(button).unwrap();
+        let mouse_event = mouse_event(rectangle.x0 + 1.0, rectangle.y0 + 1.0);
+        let tab = Event::KeyDown(KeyEvent {
+            key: KbKey::Tab,
+            ..Default::default()
+        });
+
+        // Returns the number of clicks on the button and whether Tab gives it focus afterwards.
+        let click_and_focus = |widget_manager: &mut PietWidgetManager<()>| {
+            widget_manager
+                .handle_event(&Event::MouseDown(mouse_event.clone()), None)
+                .unwrap();
+            let number_of_clicks = widget_manager
+                .handle_event(&Event::MouseUp(mouse_event.clone()), None)
+                .unwrap()
+                .len();
+
+            // Remove the focus that the click may have given.
+            widget_manager.focused_widget = None;
+            widget_manager.handle_event(&tab, None).unwrap();
+
+            (number_of_clicks, widget_manager.focused_widget.is_some())
+        };
+
+        // Disabling the container disables its descendants, so that they neither handle events nor
+        // receive focus.
+        widget_manager
+            .handle_command(Command::ToWidget(
+                column,
+                vec![WidgetCommand::SetIsDisabled(true)],
+            ))
+            .unwrap();
+        assert!(widget_manager.widgets[&row].borrow().is_disabled());
+        assert!(widget_manager.widgets[&button].borrow().is_disabled());
+        assert_eq!(click_and_focus(&mut widget_manager), (0, false));
+
+        // Enabling the container enables them again.
+        widget_manager
+            .handle_command(Command::ToWidget(
+                column,
+                vec![WidgetCommand::SetIsDisabled(false)],
+            ))
+            .unwrap();
+        assert!(!widget_manager.widgets[&row].borrow().is_disabled());
+        assert!(!widget_manager.widgets[&button].borrow().is_disabled());
+        assert_eq!(click_and_focus(&mut widget_manager), (1, true));
+    }
+
+    #[test]
+    fn test_event_propagation() {
+        // Without a stop, the event is captured down to the target, which alone handles it, and
+        // bubbles back up. The sibling widget does not get it.
+        assert_eq!(
+            recorded_pointer_events(Event::MouseDown, [None, None, None]),
+            vec![
+                (0, EventPhase::Capture),
+                (1, EventPhase::Capture),
+                (2, EventPhase::Target),
+                (1, EventPhase::Bubble),
+                (0, EventPhase::Bubble),
+            ]
+        );
+
+        // A stop in the capture phase keeps the event from the widgets further down.
+        assert_eq!(
+            recorded_pointer_events(Event::MouseDown, [Some(EventPhase::Capture), None, None]),
+            vec![(0, EventPhase::Capture)]
+        );
+
+        // A stop in the bubble phase keeps the event from the widgets further up.
+        assert_eq!(
+            recorded_pointer_events(Event::MouseDown, [None, Some(EventPhase::Bubble), None]),
+            vec![
+                (0, EventPhase::Capture),
+                (1, EventPhase::Capture),
+                (2, EventPhase::Target),
+                (1, EventPhase::Bubble),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wheel_event_propagation() {
+        // Wheel events take the path to the widget under the pointer, like other pointer events.
+        assert_eq!(
+            recorded_pointer_events(Event::MouseWheel, [None, None, None]),
+            vec![
+                (0, EventPhase::Capture),
+                (1, EventPhase::Capture),
+                (2, EventPhase::Target),
+                (1, EventPhase::Bubble),
+                (0, EventPhase::Bubble),
+            ]
+        );
+
+        // A container can intercept them in the capture phase, e.g. to scroll.
+        assert_eq!(
+            recorded_pointer_events(Event::MouseWheel, [None, Some(EventPhase::Capture), None]),
+            vec![(0, EventPhase::Capture), (1, EventPhase::Capture)]
+        );
+
+        // Or it can handle those that its descendants let bubble up.
+        assert_eq!(
+            recorded_pointer_events(Event::MouseWheel, [Some(EventPhase::Bubble), None, None]),
+            vec![
+                (0, EventPhase::Capture),
+                (1, EventPhase::Capture),
+                (2, EventPhase::Target),
+                (1, EventPhase::Bubble),
+                (0, EventPhase::Bubble),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shortcuts() {
+        let primary_modifiers = if cfg!(target_os = "macos") {
+            Modifiers::META
+        } else {
+            Modifiers::CONTROL
+        };
+
+        // The primary modifier key depends on the platform.
+        let shortcut = Shortcut::primary("S");
+        assert_eq!(shortcut.key, "s");
+        assert_eq!(shortcut.has_meta, cfg!(target_os = "macos"));
+        assert_eq!(shortcut.has_ctrl, !cfg!(target_os = "macos"));
+        assert_eq!("Primary+s".parse(), Ok(shortcut.clone()));
+
+        // Shortcuts are displayed the way they are parsed.
+        for string in ["Ctrl+Alt+Shift+S", "Ctrl++", "F5", "Shift+Meta+Enter"] {
+            let shortcut: Shortcut = string.parse().unwrap();
+            assert_eq!(shortcut.to_string().parse(), Ok(shortcut));
+        }
+        assert_eq!(
+            "ctrl+shift+a".parse::<Shortcut>().unwrap().to_string(),
+            "Ctrl+Shift+A"
+        );
+        assert!("Ctrl+".parse::<Shortcut>().is_err());
+        assert!("Hyper+A".parse::<Shortcut>().is_err());
+
+        let mut widget_manager: PietWidgetManager<usize> = PietWidgetManager::new();
+        let column = widget_manager.widget_id_provider().next_widget_id();
+        let button = widget_manager.widget_id_provider().next_widget_id();
+        let add_shortcut = |scope_widget_id, app_event| Command::AddShortcut {
+            scope_widget_id,
+            shortcut: Shortcut::primary("s"),
+            shortcut_action: ShortcutAction::AppEvent(app_event),
+        };
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(column, WidgetType::LayoutColumn),
+                Command::CreateWidget(button, WidgetType::TextButton("Button".to_string())),
+                Command::AddChild {
+                    parent_widget_id: column,
+                    widget_placement: None,
+                    child_widget_id: button,
+                },
+                Command::SetMainWidget(column),
+                add_shortcut(None, 1),
+                add_shortcut(Some(column), 2),
+            ])
+            .unwrap();
+
+        // A shortcut exists only once per scope.
+        match widget_manager.handle_command(add_shortcut(Some(column), 3)) {
+            Err(WidgetError::CommandFailed { error, .. }) => {
+                assert!(matches!(
+                    *error,
+                    WidgetError::ShortcutExistsAlready {
+                        scope_widget_id: Some(scope_widget_id),
+                        ..
+                    } if scope_widget_id == column
+                ));
+            }
+            _ => panic!("The shortcut should exist already"),
+        }
+
+        let press_shortcut = |widget_manager: &mut PietWidgetManager<usize>| {
+            widget_manager
+                .handle_event(
+                    &Event::KeyDown(KeyEvent {
+                        key: KbKey::Character("s".to_string()),
+                        mods: primary_modifiers,
+                        ..Default::default()
+                    }),
+                    None,
+                )
+                .unwrap()
+        };
+
+        // Without focus, the global shortcut applies.
+        assert_eq!(press_shortcut(&mut widget_manager), vec![1]);
+
+        // With focus on a descendant of the scope widget, the scoped shortcut overrides it.
+        widget_manager
+            .handle_event(
+                &Event::KeyDown(KeyEvent {
+                    key: KbKey::Tab,
+                    ..Default::default()
+                }),
+                None,
+            )
+            .unwrap();
+        assert_eq!(press_shortcut(&mut widget_manager), vec![2]);
+    }
+
+    #[test]
+    fn test_accessibility_tree() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let column = widget_manager.widget_id_provider().next_widget_id();
+        let button = widget_manager.widget_id_provider().next_widget_id();
+        let text_input = widget_manager.widget_id_provider().next_widget_id();
+        let placeholder = widget_manager.widget_id_provider().next_widget_id();
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(column, WidgetType::LayoutColumn),
+                Command::CreateWidget(button, WidgetType::TextButton("OK".to_string())),
+                Command::CreateWidget(
+                    text_input,
+                    WidgetType::TextInput {
+                        text: "Name".to_string(),
+                        width: 100.0,
+                    },
+                ),
+                Command::CreateWidget(
+                    placeholder,
+                    WidgetType::Placeholder {
+                        maximum_size: Size::new(10.0, 10.0),
+                    },
+                ),
+                Command::AddChildren {
+                    parent_widget_id: column,
+                    child_widgets: vec![(None, button), (None, text_input), (None, placeholder)],
+                },
+                Command::SetMainWidget(column),
+                Command::SetAccessibilityName(placeholder, Some("Logo".to_string())),
+                Command::SetHasFocus(text_input, true),
+                Command::ToWidget(button, vec![WidgetCommand::SetIsDisabled(true)]),
+                Command::ToWidget(placeholder, vec![WidgetCommand::SetIsHidden(true)]),
+            ])
+            .unwrap();
+        widget_manager.resize(kurbo::Size::new(200.0, 200.0));
+
+        // The button's text widget gets the next widget ID, 5. The button is named by its text.
+        let accessibility_tree = widget_manager.accessibility_tree().unwrap();
+        assert_eq!(
+            accessibility_tree.dump(),
+            "group #1\n\
+             \x20 button #2 \"OK\" disabled\n\
+             \x20   label #5 \"OK\" disabled\n\
+             \x20 text field #3 value=\"Name\" focused\n\
+             \x20 group #4 \"Logo\" hidden\n"
+        );
+        assert_eq!(
+            accessibility_tree.find(text_input).unwrap().bounds,
+            widget_manager.rectangle(text_input).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_drag_and_drop() {
+        let mut widget_manager: PietWidgetManager<Option<usize>> = PietWidgetManager::new();
+        let placeholder = |widget_id| {
+            Command::CreateWidget(
+                widget_id,
+                WidgetType::Placeholder {
+                    maximum_size: Size::new(10.0, 10.0),
+                },
+            )
+        };
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(0, WidgetType::LayoutColumn),
+                placeholder(1),
+                placeholder(2),
+                placeholder(3),
+                Command::AddChildren {
+                    parent_widget_id: 0,
+                    child_widgets: vec![(None, 1), (None, 2), (None, 3)],
+                },
+                Command::SetMainWidget(0),
+                Command::SetDragPayload(1, Some(DragPayload::new("row", "Row 1", 1_usize))),
+                Command::SetDropTarget(
+                    2,
+                    Some(DropTarget::new(vec!["row".to_string()], |drop_data| {
+                        drop_data.payload.data::<usize>().cloned()
+                    })),
+                ),
+                Command::SetDropTarget(
+                    3,
+                    Some(DropTarget::new(vec!["file".to_string()], |_| None)),
+                ),
+            ])
+            .unwrap();
+        widget_manager.resize(kurbo::Size::new(100.0, 100.0));
+
+        let on = |widget_id| {
+            let rectangle = widget_manager.rectangle(widget_id).unwrap();
+            mouse_event(rectangle.x0 + 5.0, rectangle.y0 + 5.0)
+        };
+        let on_1 = on(1);
+        let on_2 = on(2);
+        let on_3 = on(3);
+
+        // Dragging the draggable widget over the drop target highlights it.
+        widget_manager
+            .handle_event(&Event::MouseDown(on_1.clone()), None)
+            .unwrap();
+        widget_manager
+            .handle_event(&Event::MouseMove(on_2.clone()), None)
+            .unwrap();
+        assert_eq!(
+            widget_manager
+                .drag
+                .as_ref()
+                .and_then(|drag| drag.drop_target_widget_id()),
+            Some(2)
+        );
+
+        // A drop target that does not accept the payload is not highlighted.
+        widget_manager
+            .handle_event(&Event::MouseMove(on_3.clone()), None)
+            .unwrap();
+        assert_eq!(
+            widget_manager
+                .drag
+                .as_ref()
+                .and_then(|drag| drag.drop_target_widget_id()),
+            None
+        );
+
+        // Nothing is dropped there.
+        let app_events = widget_manager
+            .handle_event(&Event::MouseUp(on_3), None)
+            .unwrap();
+        assert!(app_events.is_empty());
+        assert!(widget_manager.drag.is_none());
+
+        // Dropping the payload on the drop target produces its application event.
+        widget_manager
+            .handle_event(&Event::MouseDown(on_1.clone()), None)
+            .unwrap();
+        widget_manager
+            .handle_event(&Event::MouseMove(on_2.clone()), None)
+            .unwrap();
+        let app_events = widget_manager
+            .handle_event(&Event::MouseUp(on_2.clone()), None)
+            .unwrap();
+        assert_eq!(app_events, vec![Some(1)]);
+
+        // A drop target that is unregistered while the payload is over it is not highlighted.
+        widget_manager
+            .handle_event(&Event::MouseDown(on_1.clone()), None)
+            .unwrap();
+        widget_manager
+            .handle_event(&Event::MouseMove(on_2.clone()), None)
+            .unwrap();
+        widget_manager
+            .handle_command(Command::SetDropTarget(2, None))
+            .unwrap();
+        assert_eq!(
+            widget_manager
+                .drag
+                .as_ref()
+                .and_then(|drag| drag.drop_target_widget_id()),
+            None
+        );
+        widget_manager
+            .handle_event(&Event::MouseUp(on_2.clone()), None)
+            .unwrap();
+
+        // Nothing is dropped on a drop target that was destroyed while the payload was over it.
+        widget_manager
+            .handle_command(Command::SetDropTarget(
+                2,
+                Some(DropTarget::new(vec!["row".to_string()], |drop_data| {
+                    drop_data.payload.data::<usize>().cloned()
+                })),
+            ))
+            .unwrap();
+        widget_manager
+            .handle_event(&Event::MouseDown(on_1), None)
+            .unwrap();
+        widget_manager
+            .handle_event(&Event::MouseMove(on_2.clone()), None)
+            .unwrap();
+
+        // A failing batch that destroys the drop target and the dragged widget leaves the drag as
+        // it was.
+        assert!(widget_manager
+            .handle_commands(vec![
+                Command::Destroy(2),
+                Command::Destroy(1),
+                Command::ToWidget(100, vec![WidgetCommand::SetIsHidden(true)]),
+            ])
+            .is_err());
+        assert_eq!(
+            widget_manager
+                .drag
+                .as_ref()
+                .map(|drag| (drag.source_widget_id(), drag.drop_target_widget_id())),
+            Some((1, Some(2)))
+        );
+
+        widget_manager.handle_command(Command::Destroy(2)).unwrap();
+        let app_events = widget_manager
+            .handle_event(&Event::MouseUp(on_2), None)
+            .unwrap();
+        assert!(app_events.is_empty());
+        assert!(widget_manager.drag.is_none());
+    }
+
+    #[test]
+    fn test_file_dialog() {
+        let mut widget_manager: PietWidgetManager<usize> = PietWidgetManager::new();
+
+        widget_manager
+            .handle_command(Command::ShowOpenDialog(
+                FileDialogOptions {
+                    has_multiple_selection: true,
+                    ..FileDialogOptions::default()
+                },
+                Rc::new(|paths| paths.len()),
+            ))
+            .unwrap();
+        assert!(matches!(
+            widget_manager.window_requests().as_slice(),
+            [WindowRequest::ShowOpenDialog(0, _)]
+        ));
+
+        // Closing the dialog produces the mapped application event, but only once.
+        let event = Event::FileChosen(0, vec!["a.txt".into(), "b.txt".into()]);
+        assert_eq!(widget_manager.handle_event(&event, None).unwrap(), vec![2]);
+        assert!(widget_manager
+            .handle_event(&event, None)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_close_window() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+
+        // The main window by default.
+        widget_manager.handle_command(Command::CloseWindow).unwrap();
+        assert!(matches!(
+            widget_manager.window_requests().as_slice(),
+            [WindowRequest::CloseWindow(MAIN_WINDOW_ID)]
+        ));
+
+        // Another window.
+        widget_manager.set_window_id(1);
+        widget_manager.handle_command(Command::CloseWindow).unwrap();
+        assert!(matches!(
+            widget_manager.window_requests().as_slice(),
+            [WindowRequest::CloseWindow(1)]
+        ));
+    }
+
+    #[test]
+    fn test_zoom() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(
+                    0,
+                    WidgetType::Placeholder {
+                        maximum_size: Size::new(1000.0, 1000.0),
+                    },
+                ),
+                Command::SetMainWidget(0),
+            ])
+            .unwrap();
+        widget_manager.resize(kurbo::Size::new(102.0, 102.0));
+        assert_eq!(
+            widget_manager.rectangle(0).unwrap().size(),
+            Size::new(100.0, 100.0)
+        );
+
+        // Zooming in lays out the widgets in a smaller, zoomed area.
+        widget_manager
+            .handle_command(Command::SetZoomFactor(2.0))
+            .unwrap();
+        assert_eq!(
+            widget_manager.rectangle(0).unwrap().size(),
+            Size::new(49.0, 49.0)
+        );
+
+        // The zoom factor is limited.
+        widget_manager
+            .handle_command(Command::SetZoomFactor(10.0))
+            .unwrap();
+        assert_eq!(widget_manager.shared_state.zoom_factor(), 4.0);
+    }
+
+    #[test]
+    fn test_localization() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let column = widget_manager.widget_id_provider().next_widget_id();
+        let text_widget = widget_manager.widget_id_provider().next_widget_id();
+        let button = widget_manager.widget_id_provider().next_widget_id();
+        let text = |widget_manager: &PietWidgetManager<()>, widget_id| {
+            widget_manager
+                .value(widget_id)
+                .unwrap()
+                .unwrap()
+                .as_text()
+                .unwrap()
+                .to_string()
+        };
+
+        widget_manager
+            .handle_commands(vec![
+                Command::AddMessageCatalog {
+                    locale: "en".to_string(),
+                    fluent_source: "title = Settings\nsave = Save".to_string(),
+                },
+                Command::AddMessageCatalog {
+                    locale: "de".to_string(),
+                    fluent_source: "title = Einstellungen\nsave = Speichern".to_string(),
+                },
+                Command::CreateWidget(column, WidgetType::LayoutColumn),
+                Command::CreateWidget(
+                    text_widget,
+                    WidgetType::LocalizedText(Message::new("title")),
+                ),
+                Command::CreateWidget(
+                    button,
+                    WidgetType::LocalizedTextButton(Message::new("save")),
+                ),
+                Command::AddChildren {
+                    parent_widget_id: column,
+                    child_widgets: vec![(None, text_widget), (None, button)],
+                },
+                Command::SetMainWidget(column),
+            ])
+            .unwrap();
+
+        // A text button's text is its text widget's value.
+        let button_text_widget_id = widget_manager.child_widget_ids_per_widget_id[&button][0];
+        assert_eq!(text(&widget_manager, text_widget), "Settings");
+        assert_eq!(text(&widget_manager, button_text_widget_id), "Save");
+
+        // Switching the locale translates all localized texts.
+        widget_manager
+            .handle_command(Command::SetLocale("de-DE".to_string()))
+            .unwrap();
+        assert_eq!(text(&widget_manager, text_widget), "Einstellungen");
+        assert_eq!(text(&widget_manager, button_text_widget_id), "Speichern");
+        assert_eq!(
+            widget_manager.locale_format().format_number(1234.5, 1),
+            "1.234,5"
+        );
+
+        // A failed batch, here due to a missing widget, restores the locale and the texts.
+        assert!(widget_manager
+            .handle_commands(vec![
+                Command::SetLocale("en".to_string()),
+                Command::SetMessage(100, Some(Message::new("title"))),
+            ])
+            .is_err());
+        assert_eq!(text(&widget_manager, text_widget), "Einstellungen");
+        assert_eq!(
+            widget_manager.message_catalogs.locale().to_string(),
+            "de-DE"
+        );
+
+        // A widget without message keeps its text when the locale changes.
+        widget_manager
+            .handle_commands(vec![
+                Command::SetMessage(text_widget, None),
+                Command::SetLocale("en".to_string()),
+            ])
+            .unwrap();
+        assert_eq!(text(&widget_manager, text_widget), "Einstellungen");
+        assert_eq!(text(&widget_manager, button_text_widget_id), "Save");
+    }
+
+    #[test]
+    fn test_layout_direction() {
+        let mut widget_manager: PietWidgetManager<()> = PietWidgetManager::new();
+        let placeholder = |widget_id| {
+            Command::CreateWidget(
+                widget_id,
+                WidgetType::Placeholder {
+                    maximum_size: Size::new(10.0, 10.0),
+                },
+            )
+        };
+
+        widget_manager
+            .handle_commands(vec![
+                Command::CreateWidget(0, WidgetType::LayoutRow),
+                placeholder(1),
+                placeholder(2),
+                Command::AddChildren {
+                    parent_widget_id: 0,
+                    child_widgets: vec![(None, 1), (None, 2)],
+                },
+                Command::SetMainWidget(0),
+            ])
+            .unwrap();
+        widget_manager.resize(kurbo::Size::new(102.0, 102.0));
+        assert_eq!(widget_manager.rectangle(1).unwrap().x0, 1.0);
+
+        // A right-to-left window mirrors the row, so that the first child widget is on the right.
+        widget_manager
+            .handle_command(Command::SetLayoutDirection(LayoutDirection::RightToLeft))
+            .unwrap();
+        assert_eq!(
+            widget_manager.rectangle(1).unwrap().x1,
+            widget_manager.rectangle(0).unwrap().x1
+        );
+        assert!(widget_manager.rectangle(2).unwrap().x1 <= widget_manager.rectangle(1).unwrap().x0);
+
+        // A widget's own layout direction overrides the window's.
+        widget_manager
+            .handle_command(Command::ToWidget(
+                0,
+                vec![WidgetCommand::SetLayoutDirection(Some(
+                    LayoutDirection::LeftToRight,
+                ))],
+            ))
+            .unwrap();
+        assert_eq!(widget_manager.rectangle(1).unwrap().x0, 1.0);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::localization::MessageCatalogs;
+use crate::widget_manager::context_menu::ContextMenu;
+use crate::widget_manager::drag_and_drop::Drag;
+use crate::widget_manager::widget_focus_order::WidgetFocusOrder;
+use crate::widget_manager::{PietWidgetManager, WidgetBox};
+use crate::{FileDialogId, PietWidget};
+use guiver::{
+    DragPayload, DropTarget, FileDialogMapper, GestureThresholds, LayoutDirection, MenuItem,
+    Message, Shortcut, ShortcutAction, WidgetId,
+};
+use std::collections::{HashMap, HashSet};
+
+/// The widget manager's state before a batch of commands. If a command of the batch fails, the
+/// snapshot restores that state, so that a batch is either applied completely or not at all.
+pub(crate) struct Snapshot<APP_EVENT: Clone> {
+    accessibility_name_per_widget_id: HashMap<WidgetId, String>,
+    child_widget_ids_per_widget_id: HashMap<WidgetId, Vec<WidgetId>>,
+    context_menu: Option<ContextMenu<APP_EVENT>>,
+    context_menu_items_per_widget_id: HashMap<WidgetId, Vec<MenuItem<APP_EVENT>>>,
+    drag: Option<Drag>,
+    drag_payload_per_widget_id: HashMap<WidgetId, DragPayload>,
+    drop_target_per_widget_id: HashMap<WidgetId, DropTarget<APP_EVENT>>,
+    file_dialog_mappers: HashMap<FileDialogId, FileDialogMapper<APP_EVENT>>,
+    focus_scope_widget_ids: HashSet<WidgetId>,
+    focused_widget: Option<WidgetBox<APP_EVENT>>,
+    gesture_thresholds: GestureThresholds,
+    layout_direction: LayoutDirection,
+    main_widget: Option<WidgetBox<APP_EVENT>>,
+    menu_bar_app_events: Vec<APP_EVENT>,
+    message_catalogs: MessageCatalogs,
+    message_per_widget_id: HashMap<WidgetId, Message>,
+    next_file_dialog_id: FileDialogId,
+    number_of_window_requests: usize,
+    parent_widget_id_per_widget_id: HashMap<WidgetId, WidgetId>,
+    pointer_capture_widget_id: Option<WidgetId>,
+    shortcut_actions_per_scope:
+        HashMap<Option<WidgetId>, HashMap<Shortcut, ShortcutAction<APP_EVENT>>>,
+    /// Copies of the widgets changed by the batch, made before their first change.
+    widget_copies: HashMap<WidgetId, Box<dyn PietWidget<APP_EVENT>>>,
+    widget_focus_order: WidgetFocusOrder,
+    widgets: HashMap<WidgetId, WidgetBox<APP_EVENT>>,
+    zoom_factor: f64,
+}
+
+impl<APP_EVENT: Clone + 'static> Snapshot<APP_EVENT> {
+    ///
+    pub(crate) fn new(widget_manager: &PietWidgetManager<APP_EVENT>) -> Self {
+        Snapshot {
+            accessibility_name_per_widget_id: widget_manager
+                .accessibility_name_per_widget_id
+                .clone(),
+            child_widget_ids_per_widget_id: widget_manager.child_widget_ids_per_widget_id.clone(),
+            context_menu: widget_manager.context_menu.clone(),
+            context_menu_items_per_widget_id: widget_manager
+                .context_menu_items_per_widget_id
+                .clone(),
+            drag: widget_manager.drag.clone(),
+            drag_payload_per_widget_id: widget_manager.drag_payload_per_widget_id.clone(),
+            drop_target_per_widget_id: widget_manager.drop_target_per_widget_id.clone(),
+            file_dialog_mappers: widget_manager.file_dialog_mappers.clone(),
+            focus_scope_widget_ids: widget_manager.focus_scope_widget_ids.clone(),
+            focused_widget: widget_manager.focused_widget.clone(),
+            gesture_thresholds: widget_manager
+                .gesture_recognizer
+                .gesture_thresholds()
+                .clone(),
+            layout_direction: widget_manager.layout_direction,
+            main_widget: widget_manager.main_widget.clone(),
+            menu_bar_app_events: widget_manager.menu_bar_app_events.clone(),
+            message_catalogs: widget_manager.message_catalogs.clone(),
+            message_per_widget_id: widget_manager.message_per_widget_id.clone(),
+            next_file_dialog_id: widget_manager.next_file_dialog_id,
+            number_of_window_requests: widget_manager.window_requests.len(),
+            parent_widget_id_per_widget_id: widget_manager.parent_widget_id_per_widget_id.clone(),
+            pointer_capture_widget_id: widget_manager.pointer_capture_widget_id,
+            shortcut_actions_per_scope: widget_manager.shortcut_actions_per_scope.clone(),
+            widget_copies: HashMap::new(),
+            widget_focus_order: widget_manager.widget_focus_order.clone(),
+            widgets: widget_manager.widgets.clone(),
+            zoom_factor: widget_manager.shared_state.zoom_factor(),
+        }
+    }
+
+    /// Copies the widget with the given ID and its descendants, unless they were copied already.
+    pub(crate) fn copy_widget_tree(
+        &mut self,
+        widget_manager: &PietWidgetManager<APP_EVENT>,
+        widget_id: WidgetId,
+    ) {
+        let mut widget_ids = vec![widget_id];
+
+        while let Some(widget_id) = widget_ids.pop() {
+            // The widget was copied already.
+            if self.widget_copies.contains_key(&widget_id) {
+                continue;
+            }
+
+            // There is a widget with the given ID.
+            if let Some(widget_box) = widget_manager.widgets.get(&widget_id) {
+                self.widget_copies
+                    .insert(widget_id, widget_box.borrow().boxed_clone());
+            }
+
+            // The widget has child widgets.
+            if let Some(child_widget_ids) = widget_manager
+                .child_widget_ids_per_widget_id
+                .get(&widget_id)
+            {
+                widget_ids.extend(child_widget_ids);
+            }
+        }
+    }
+
+    /// Restores the widget manager's state.
+    pub(crate) fn restore(self, widget_manager: &mut PietWidgetManager<APP_EVENT>) {
+        // Iterate over the widget copies.
+        for (widget_id, widget_copy) in self.widget_copies {
+            // The widget existed before the batch. Widgets created by the batch are just dropped.
+            if let Some(widget_box) = self.widgets.get(&widget_id) {
+                // Put the widget copy back into the widget box.
+                *widget_box.borrow_mut() = widget_copy;
+            }
+        }
+
+        widget_manager.accessibility_name_per_widget_id = self.accessibility_name_per_widget_id;
+        widget_manager.child_widget_ids_per_widget_id = self.child_widget_ids_per_widget_id;
+        widget_manager.context_menu = self.context_menu;
+        widget_manager.context_menu_items_per_widget_id = self.context_menu_items_per_widget_id;
+        widget_manager.drag = self.drag;
+        widget_manager.drag_payload_per_widget_id = self.drag_payload_per_widget_id;
+        widget_manager.drop_target_per_widget_id = self.drop_target_per_widget_id;
+        widget_manager.file_dialog_mappers = self.file_dialog_mappers;
+        widget_manager.focus_scope_widget_ids = self.focus_scope_widget_ids;
+        widget_manager.focused_widget = self.focused_widget;
+        widget_manager
+            .gesture_recognizer
+            .set_gesture_thresholds(self.gesture_thresholds);
+        widget_manager.layout_direction = self.layout_direction;
+        widget_manager.main_widget = self.main_widget;
+        widget_manager.menu_bar_app_events = self.menu_bar_app_events;
+        widget_manager.message_catalogs = self.message_catalogs;
+        widget_manager.message_per_widget_id = self.message_per_widget_id;
+        widget_manager.next_file_dialog_id = self.next_file_dialog_id;
+        widget_manager.parent_widget_id_per_widget_id = self.parent_widget_id_per_widget_id;
+        widget_manager.pointer_capture_widget_id = self.pointer_capture_widget_id;
+        widget_manager.shortcut_actions_per_scope = self.shortcut_actions_per_scope;
+        widget_manager.widget_focus_order = self.widget_focus_order;
+        widget_manager.widgets = self.widgets;
+        widget_manager
+            .window_requests
+            .truncate(self.number_of_window_requests);
+
+        // The batch changed the zoom factor.
+        if widget_manager.shared_state.zoom_factor() != self.zoom_factor {
+            // Restore the zoom factor and lay out the widgets again.
+            widget_manager.set_zoom_factor(self.zoom_factor);
+        }
+    }
+}
@@ -1,83 +1,268 @@
-use guiver::WidgetId;
+use guiver::{FocusOrder, Rectangle, WidgetId};
+use std::collections::HashMap;
 
 ///
+#[derive(Clone)]
 pub(crate) struct WidgetFocusOrder {
-    index_of_widget_with_focus: Option<usize>,
-    widget_focus_order: Vec<WidgetId>,
+    /// The kind of focus order.
+    focus_order: FocusOrder,
+    /// The tab indices per widget ID.
+    tab_index_per_widget_id: HashMap<WidgetId, usize>,
+    /// The IDs of the widgets that accept focus, in creation order.
+    widget_ids: Vec<WidgetId>,
 }
 
 impl WidgetFocusOrder {
     ///
     pub(crate) fn new() -> WidgetFocusOrder {
         Self {
-            index_of_widget_with_focus: None,
-            widget_focus_order: Vec::new(),
+            focus_order: FocusOrder::default(),
+            tab_index_per_widget_id: HashMap::new(),
+            widget_ids: Vec::new(),
         }
     }
 
     ///
     pub(crate) fn add_widget(&mut self, widget_id: WidgetId) {
-        self.widget_focus_order.push(widget_id);
-        self.widget_focus_order.dedup();
+        // The widget is not in the focus order yet.
+        if !self.widget_ids.contains(&widget_id) {
+            self.widget_ids.push(widget_id);
+        }
     }
 
-    ///
-    pub(crate) fn focus_next_widget(&mut self) -> Option<WidgetId> {
-        // A widget had focus.
-        if let Some(index_of_widget_with_focus) = self.index_of_widget_with_focus {
-            let mut next_index_of_widget_with_focus = index_of_widget_with_focus + 1;
+    /// Returns the ID of the widget that follows (or, if `forward` is `false`, precedes) the given
+    /// widget in the given focus order. Without a given widget the first (or last) widget is
+    /// returned.
+    pub(crate) fn next_widget_id(
+        widget_ids: &[WidgetId],
+        widget_id: Option<WidgetId>,
+        forward: bool,
+    ) -> Option<WidgetId> {
+        let number_of_widgets = widget_ids.len();
 
-            if next_index_of_widget_with_focus >= self.widget_focus_order.len() {
-                next_index_of_widget_with_focus = 0;
-            }
+        // There are no widgets in the focus order.
+        if number_of_widgets == 0 {
+            return None;
+        }
+
+        let index =
+            match widget_id.and_then(|widget_id| widget_ids.iter().position(|&i| i == widget_id)) {
+                Some(index) if forward => (index + 1) % number_of_widgets,
+                Some(index) => (index + number_of_widgets - 1) % number_of_widgets,
+                None if forward => 0,
+                None => number_of_widgets - 1,
+            };
+
+        widget_ids.get(index).cloned()
+    }
 
-            self.index_of_widget_with_focus = Some(next_index_of_widget_with_focus);
+    /// Returns the IDs of the widgets that accept focus in focus order. `rectangle` returns a
+    /// widget's rectangle, `widget_tree_order` the IDs of the main widget's widget tree in
+    /// depth-first order.
+    pub(crate) fn ordered_widget_ids(
+        &self,
+        rectangle: impl Fn(WidgetId) -> Rectangle,
+        widget_tree_order: impl FnOnce() -> Vec<WidgetId>,
+    ) -> Vec<WidgetId> {
+        let mut widget_ids: Vec<WidgetId> = match &self.focus_order {
+            FocusOrder::Creation => self.widget_ids.clone(),
+            FocusOrder::Explicit(widget_ids) => widget_ids
+                .iter()
+                .filter(|widget_id| self.widget_ids.contains(widget_id))
+                .cloned()
+                .collect(),
+            FocusOrder::Geometry => geometric_order(
+                self.widget_ids
+                    .iter()
+                    .map(|widget_id| (*widget_id, rectangle(*widget_id)))
+                    .collect(),
+            ),
+            FocusOrder::WidgetTree => widget_tree_order()
+                .into_iter()
+                .filter(|widget_id| self.widget_ids.contains(widget_id))
+                .collect(),
+        };
 
-            self.widget_focus_order
-                .get(next_index_of_widget_with_focus)
+        // Widgets with a tab index come first, in ascending order. The sort is stable, so the other
+        // widgets keep their order.
+        widget_ids.sort_by_key(|widget_id| {
+            self.tab_index_per_widget_id
+                .get(widget_id)
                 .cloned()
-        }
-        // No widget had focus.
-        else {
-            None
+                .unwrap_or(usize::MAX)
+        });
+
+        widget_ids
+    }
+
+    ///
+    pub(crate) fn remove_widget(&mut self, widget_id: WidgetId) {
+        self.widget_ids.retain(|&i| i != widget_id);
+        self.tab_index_per_widget_id.remove(&widget_id);
+
+        // The focus order lists the widget explicitly.
+        if let FocusOrder::Explicit(widget_ids) = &mut self.focus_order {
+            widget_ids.retain(|&i| i != widget_id);
         }
     }
 
     ///
-    pub(crate) fn focus_widget(&mut self, widget_id: WidgetId) {
-        // A widget with that ID is in the focus order.
-        if let Some(index_of_widget_with_focus) =
-            self.widget_focus_order.iter().position(|&i| i == widget_id)
-        {
-            // Set the index of this widget.
-            self.index_of_widget_with_focus = Some(index_of_widget_with_focus);
+    pub(crate) fn set_focus_order(&mut self, focus_order: FocusOrder) {
+        self.focus_order = focus_order;
+    }
+
+    ///
+    pub(crate) fn set_tab_index(&mut self, widget_id: WidgetId, tab_index: Option<usize>) {
+        // Set the tab index.
+        if let Some(tab_index) = tab_index {
+            self.tab_index_per_widget_id.insert(widget_id, tab_index);
         }
-        // No widget with that ID is in the focus order.
+        // Remove the tab index.
         else {
-            // Focus no widget.
-            self.index_of_widget_with_focus = None;
+            self.tab_index_per_widget_id.remove(&widget_id);
         }
     }
+}
 
-    ///
-    pub(crate) fn remove_widget(&mut self, widget_id: WidgetId) {
-        // A widget with that ID is in the focus order.
-        if let Some(index_of_widget_with_remove) =
-            self.widget_focus_order.iter().position(|&i| i == widget_id)
-        {
-            // Remove the widget.
-            self.widget_focus_order.remove(index_of_widget_with_remove);
-
-            // Adjust the focus index.
-            if let Some(index_of_widget_with_focus) = self.index_of_widget_with_focus {
-                if index_of_widget_with_focus >= self.widget_focus_order.len() {
-                    if self.widget_focus_order.is_empty() {
-                        self.index_of_widget_with_focus = None;
-                    } else {
-                        self.index_of_widget_with_focus = Some(0);
-                    }
-                }
+/// Returns the given widgets' IDs in visual order: line by line from top to bottom, each line from
+/// left to right. A widget belongs to a line if it starts above the bottom of the line's topmost
+/// widget.
+pub(crate) fn geometric_order(widget_rectangles: Vec<(WidgetId, Rectangle)>) -> Vec<WidgetId> {
+    let mut widget_rectangles = widget_rectangles;
+
+    // Sort the widgets from top to bottom.
+    widget_rectangles
+        .sort_by(|(_, rectangle1), (_, rectangle2)| rectangle1.y0.total_cmp(&rectangle2.y0));
+
+    // Group the widgets into lines.
+    let mut lines: Vec<(f64, Vec<(WidgetId, Rectangle)>)> = vec![];
+
+    for (widget_id, rectangle) in widget_rectangles {
+        match lines.last_mut() {
+            // The widget starts above the bottom of the current line.
+            Some((line_bottom, line)) if rectangle.y0 < *line_bottom => {
+                line.push((widget_id, rectangle));
             }
+            // The widget starts a new line.
+            _ => lines.push((rectangle.y1, vec![(widget_id, rectangle)])),
         }
     }
+
+    // Sort each line from left to right.
+    lines
+        .into_iter()
+        .flat_map(|(_, mut line)| {
+            line.sort_by(|(_, rectangle1), (_, rectangle2)| {
+                rectangle1.x0.total_cmp(&rectangle2.x0)
+            });
+            line.into_iter().map(|(widget_id, _)| widget_id)
+        })
+        .collect()
+}
+
+// =================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::widget_manager::widget_focus_order::{geometric_order, WidgetFocusOrder};
+    use guiver::{FocusOrder, Rectangle};
+
+    #[test]
+    fn test_geometric_order() {
+        // Two lines with slightly different vertical offsets, given in arbitrary order.
+        let widget_rectangles = vec![
+            (1, Rectangle::new(100.0, 2.0, 150.0, 20.0)),
+            (2, Rectangle::new(0.0, 30.0, 50.0, 50.0)),
+            (3, Rectangle::new(0.0, 0.0, 50.0, 20.0)),
+            (4, Rectangle::new(100.0, 31.0, 150.0, 50.0)),
+        ];
+
+        assert_eq!(geometric_order(widget_rectangles), vec![3, 1, 2, 4]);
+    }
+
+    #[test]
+    fn test_next_widget_id() {
+        let widget_ids = [1, 2, 3];
+
+        // Forward.
+        assert_eq!(
+            WidgetFocusOrder::next_widget_id(&widget_ids, None, true),
+            Some(1)
+        );
+        assert_eq!(
+            WidgetFocusOrder::next_widget_id(&widget_ids, Some(2), true),
+            Some(3)
+        );
+        assert_eq!(
+            WidgetFocusOrder::next_widget_id(&widget_ids, Some(3), true),
+            Some(1)
+        );
+
+        // Backward.
+        assert_eq!(
+            WidgetFocusOrder::next_widget_id(&widget_ids, None, false),
+            Some(3)
+        );
+        assert_eq!(
+            WidgetFocusOrder::next_widget_id(&widget_ids, Some(1), false),
+            Some(3)
+        );
+
+        // No widgets.
+        assert_eq!(WidgetFocusOrder::next_widget_id(&[], Some(1), true), None);
+    }
+
+    #[test]
+    fn test_ordered_widget_ids() {
+        let mut widget_focus_order = WidgetFocusOrder::new();
+        widget_focus_order.add_widget(1);
+        widget_focus_order.add_widget(2);
+        widget_focus_order.add_widget(3);
+        widget_focus_order.add_widget(2);
+
+        // The widgets are placed from right to left.
+        let rectangle = |widget_id| {
+            let x = 100.0 - widget_id as f64 * 10.0;
+            Rectangle::new(x, 0.0, x + 10.0, 10.0)
+        };
+        let widget_tree_order = || vec![0, 3, 1, 2];
+
+        assert_eq!(
+            widget_focus_order.ordered_widget_ids(rectangle, widget_tree_order),
+            vec![1, 2, 3]
+        );
+
+        widget_focus_order.set_focus_order(FocusOrder::Geometry);
+        assert_eq!(
+            widget_focus_order.ordered_widget_ids(rectangle, widget_tree_order),
+            vec![3, 2, 1]
+        );
+
+        widget_focus_order.set_focus_order(FocusOrder::WidgetTree);
+        assert_eq!(
+            widget_focus_order.ordered_widget_ids(rectangle, widget_tree_order),
+            vec![3, 1, 2]
+        );
+
+        // Unknown widgets are ignored.
+        widget_focus_order.set_focus_order(FocusOrder::Explicit(vec![2, 4, 1]));
+        assert_eq!(
+            widget_focus_order.ordered_widget_ids(rectangle, widget_tree_order),
+            vec![2, 1]
+        );
+
+        // Widgets with a tab index come first.
+        widget_focus_order.set_focus_order(FocusOrder::Creation);
+        widget_focus_order.set_tab_index(3, Some(1));
+        assert_eq!(
+            widget_focus_order.ordered_widget_ids(rectangle, widget_tree_order),
+            vec![3, 1, 2]
+        );
+
+        widget_focus_order.remove_widget(3);
+        assert_eq!(
+            widget_focus_order.ordered_widget_ids(rectangle, widget_tree_order),
+            vec![1, 2]
+        );
+    }
 }
@@ -1,29 +1,201 @@
-use crate::{Event, PietApplication};
+use crate::application::{close_window, open_window, request_close, window_closed, Windows};
+use crate::text_field::TextFieldInputHandler;
+use crate::{
+    CloseResponse, Event, FileDialogId, PietApplication, TextFieldState, WindowId, WindowRequest,
+};
 use druid_shell::kurbo::Size;
 use druid_shell::piet::Piet;
+use druid_shell::text::InputHandler;
 use druid_shell::{
-    FileDialogToken, FileInfo, IdleToken, KeyEvent, MouseEvent, Region, Scale, TimerToken,
-    WinHandler, WindowHandle,
+    text, FileDialogToken, FileInfo, IdleToken, KbKey, KeyEvent, MouseEvent, Region, Scale,
+    TextFieldToken, TimerToken, WinHandler, WindowHandle,
 };
 use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
 
 ///
 pub struct WindowEventHandler {
-    /// The application.
-    application: Box<dyn PietApplication>,
+    /// The application, shared by all windows.
+    application: Rc<RefCell<Box<dyn PietApplication>>>,
+    /// The IDs of the open file dialogs.
+    file_dialog_ids: HashMap<FileDialogToken, FileDialogId>,
+    /// The size that the window is not resized beyond.
+    maximum_size: Option<Size>,
+    /// The focused text widget's token and state, which the platform's text input edits.
+    text_field: Option<(TextFieldToken, Rc<RefCell<TextFieldState>>)>,
+    /// Whether the platform's text input edited the text field state while it held the input lock.
+    text_field_was_edited: Rc<Cell<bool>>,
     /// The window handle.
     window_handle: WindowHandle,
+    ///
+    window_id: WindowId,
+    /// The state shared by all windows.
+    windows: Rc<RefCell<Windows>>,
     /// The window size. Needed for repainting.
     window_size: Size,
 }
 
 impl WindowEventHandler {
     ///
-    pub fn new(application: Box<dyn PietApplication>) -> Self {
+    pub(crate) fn new(
+        windows: Rc<RefCell<Windows>>,
+        window_id: WindowId,
+        maximum_size: Option<Size>,
+    ) -> Self {
+        let application = windows.borrow().application.clone();
+
         WindowEventHandler {
             application,
+            file_dialog_ids: HashMap::new(),
+            maximum_size,
+            text_field: None,
+            text_field_was_edited: Rc::new(Cell::new(false)),
             window_handle: WindowHandle::default(),
+            window_id,
             window_size: Size::default(),
+            windows,
+        }
+    }
+
+    /// Lets the application handle the given event and repaints the window.
+    fn handle_event(&mut self, event: Event) {
+        self.application
+            .borrow_mut()
+            .handle_event(self.window_id, &event);
+
+        // Handle the requests the application made while handling the event.
+        self.handle_window_requests();
+
+        self.window_handle
+            .invalidate_rect(self.window_size.to_rect());
+    }
+
+    /// Lets the application handle the files chosen in the file dialog with the given token.
+    fn handle_file_dialog_result(&mut self, token: FileDialogToken, files: Vec<FileInfo>) {
+        // The file dialog was requested by the application.
+        if let Some(file_dialog_id) = self.file_dialog_ids.remove(&token) {
+            self.handle_event(Event::FileChosen(
+                file_dialog_id,
+                files.into_iter().map(|file| file.path).collect(),
+            ));
+        }
+    }
+
+    /// Registers the focused text widget's state with the platform's text input or, if it is
+    /// `None`, unregisters the text field.
+    fn set_text_field(&mut self, text_field_state: Option<TextFieldState>) {
+        match (&self.text_field, text_field_state) {
+            // A text widget got focus.
+            (None, Some(text_field_state)) => {
+                let token = self.window_handle.add_text_field();
+                self.window_handle.set_focused_text_field(Some(token));
+                self.text_field = Some((token, Rc::new(RefCell::new(text_field_state))));
+            }
+            // The focused text widget's state changed.
+            (Some((token, current_text_field_state)), Some(text_field_state)) => {
+                let token = *token;
+
+                // The state is the one that the platform's text input produced.
+                if *current_text_field_state.borrow() == text_field_state {
+                    return;
+                }
+
+                // Tell the platform's text input what changed.
+                let update = {
+                    let current_text_field_state = current_text_field_state.borrow();
+
+                    // Only the position changed, e.g. after a resize.
+                    if current_text_field_state.text == text_field_state.text
+                        && current_text_field_state.selection == text_field_state.selection
+                        && current_text_field_state.composition_range
+                            == text_field_state.composition_range
+                    {
+                        text::Event::LayoutChanged
+                    }
+                    // Only the selection changed.
+                    else if current_text_field_state.text == text_field_state.text
+                        && current_text_field_state.composition_range
+                            == text_field_state.composition_range
+                    {
+                        text::Event::SelectionChanged
+                    }
+                    // The text changed, e.g. by undo.
+                    else {
+                        text::Event::Reset
+                    }
+                };
+
+                *current_text_field_state.borrow_mut() = text_field_state;
+                self.window_handle.update_text_field(token, update);
+            }
+            // The text widget lost focus.
+            (Some((token, _)), None) => {
+                let token = *token;
+
+                self.window_handle.set_focused_text_field(None);
+                self.window_handle.remove_text_field(token);
+                self.text_field = None;
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Applies the application's requests to the window.
+    fn handle_window_requests(&mut self) {
+        let window_requests = self
+            .application
+            .borrow_mut()
+            .window_requests(self.window_id);
+
+        // Iterate over the application's window requests.
+        for window_request in window_requests {
+            match window_request {
+                WindowRequest::CloseWindow(window_id) => {
+                    // The request is to close this window, which may not be registered yet while
+                    // it is being created.
+                    if window_id == self.window_id {
+                        self.window_handle.close();
+                    }
+                    // The request is to close another window.
+                    else {
+                        close_window(&self.windows, window_id);
+                    }
+                }
+                WindowRequest::OpenWindow {
+                    window_id,
+                    window_options,
+                } => {
+                    open_window(&self.windows, window_id, window_options);
+                }
+                WindowRequest::RequestTimer(duration) => {
+                    self.window_handle.request_timer(duration);
+                }
+                WindowRequest::SetMenu(menu) => self.window_handle.set_menu(menu),
+                WindowRequest::SetSize(size) => self.window_handle.set_size(size),
+                WindowRequest::SetTextField(text_field_state) => {
+                    self.set_text_field(text_field_state);
+                }
+                WindowRequest::SetTitle(title) => self.window_handle.set_title(&title),
+                WindowRequest::ShowOpenDialog(file_dialog_id, file_dialog_options) => {
+                    // The dialog was shown.
+                    if let Some(file_dialog_token) =
+                        self.window_handle.open_file(file_dialog_options)
+                    {
+                        self.file_dialog_ids
+                            .insert(file_dialog_token, file_dialog_id);
+                    }
+                }
+                WindowRequest::ShowSaveDialog(file_dialog_id, file_dialog_options) => {
+                    // The dialog was shown.
+                    if let Some(file_dialog_token) = self.window_handle.save_as(file_dialog_options)
+                    {
+                        self.file_dialog_ids
+                            .insert(file_dialog_token, file_dialog_id);
+                    }
+                }
+            }
         }
     }
 }
@@ -31,90 +203,146 @@ impl WindowEventHandler {
 impl WinHandler for WindowEventHandler {
     fn connect(&mut self, handle: &WindowHandle) {
         self.window_handle = handle.clone();
+
+        // Handle the requests the application made before the window was created, e.g. to set the
+        // menu bar.
+        self.handle_window_requests();
     }
 
     fn size(&mut self, size: Size) {
-        self.application.resize(size);
+        // The window was resized beyond its maximum size.
+        if let Some(maximum_size) = self.maximum_size {
+            if size.width > maximum_size.width || size.height > maximum_size.height {
+                // Shrink the window, which leads to another call with the allowed size.
+                self.window_handle
+                    .set_size(size.clamp(Size::ZERO, maximum_size));
+                return;
+            }
+        }
+
+        self.application.borrow_mut().resize(self.window_id, size);
         self.window_size = size;
     }
 
-    fn scale(&mut self, _scale: Scale) {
-        // TODO: Handle the event.
-
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+    fn scale(&mut self, scale: Scale) {
+        // Handle the scale changed event.
+        self.handle_event(Event::ScaleChanged(scale));
     }
 
     fn prepare_paint(&mut self) {}
 
     fn paint(&mut self, piet: &mut Piet, region: &Region) {
-        self.application.paint(piet, region);
+        self.application
+            .borrow_mut()
+            .paint(self.window_id, piet, region);
     }
 
     fn rebuild_resources(&mut self) {}
 
-    fn command(&mut self, _id: u32) {}
+    fn command(&mut self, id: u32) {
+        // Handle the menu command event.
+        self.handle_event(Event::MenuCommand(id));
+    }
 
-    fn save_as(&mut self, _token: FileDialogToken, _file: Option<FileInfo>) {}
+    fn save_as(&mut self, token: FileDialogToken, file: Option<FileInfo>) {
+        self.handle_file_dialog_result(token, file.into_iter().collect());
+    }
 
-    fn open_file(&mut self, _token: FileDialogToken, _file: Option<FileInfo>) {}
+    fn open_file(&mut self, token: FileDialogToken, file: Option<FileInfo>) {
+        self.handle_file_dialog_result(token, file.into_iter().collect());
+    }
+
+    fn open_files(&mut self, token: FileDialogToken, files: Vec<FileInfo>) {
+        self.handle_file_dialog_result(token, files);
+    }
 
     fn key_down(&mut self, event: KeyEvent) -> bool {
-        // Handle the key down event.
-        self.application.handle_event(&Event::KeyDown(event));
+        // A text widget has focus.
+        if let Some((_, text_field_state)) = &self.text_field {
+            let is_composing = text_field_state.borrow().composition_range.is_some();
+            let is_text_input = match &event.key {
+                KbKey::Character(_) => !event.mods.ctrl() && !event.mods.meta(),
+                KbKey::Dead | KbKey::Process => true,
+                _ => false,
+            };
+
+            // Let the platform's text input handle typed text and, while the IME is composing, any
+            // key. It edits the text field via `acquire_input_lock()`.
+            if is_composing || is_text_input {
+                return false;
+            }
+        }
 
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+        // Handle the key down event.
+        self.handle_event(Event::KeyDown(event));
         true
     }
 
     fn key_up(&mut self, event: KeyEvent) {
         // Handle the key up event.
-        self.application.handle_event(&Event::KeyUp(event));
-
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+        self.handle_event(Event::KeyUp(event));
     }
 
-    fn wheel(&mut self, _event: &MouseEvent) {
-        // TODO: Handle the event.
+    fn acquire_input_lock(
+        &mut self,
+        token: TextFieldToken,
+        _mutable: bool,
+    ) -> Box<dyn InputHandler> {
+        let text_field_state = match &self.text_field {
+            // The token is the focused text widget's.
+            Some((text_field_token, text_field_state)) if *text_field_token == token => {
+                text_field_state.clone()
+            }
+            // The token is outdated, so the edits are dropped.
+            _ => Rc::new(RefCell::new(TextFieldState::default())),
+        };
+
+        self.text_field_was_edited.set(false);
+
+        Box::new(TextFieldInputHandler::new(
+            text_field_state,
+            self.text_field_was_edited.clone(),
+        ))
+    }
 
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+    fn release_input_lock(&mut self, token: TextFieldToken) {
+        // The platform's text input edited the focused text widget's state.
+        if self.text_field_was_edited.replace(false) {
+            let text_field_state = match &self.text_field {
+                Some((text_field_token, text_field_state)) if *text_field_token == token => {
+                    text_field_state.borrow().clone()
+                }
+                _ => return,
+            };
+
+            // Let the application pass the edited state on to the text widget.
+            self.handle_event(Event::TextFieldEdited(text_field_state));
+        }
     }
 
-    fn zoom(&mut self, _delta: f64) {
-        // TODO: Handle the event.
+    fn wheel(&mut self, event: &MouseEvent) {
+        // Handle the mouse wheel event.
+        self.handle_event(Event::MouseWheel(event.clone()));
+    }
 
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+    fn zoom(&mut self, delta: f64) {
+        // Handle the zoom event.
+        self.handle_event(Event::Zoom(delta));
     }
 
     fn mouse_move(&mut self, event: &MouseEvent) {
         // Handle the mouse move event.
-        self.application
-            .handle_event(&Event::MouseMove(event.clone()));
-
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+        self.handle_event(Event::MouseMove(event.clone()));
     }
 
     fn mouse_down(&mut self, event: &MouseEvent) {
         // Handle the mouse down event.
-        self.application
-            .handle_event(&Event::MouseDown(event.clone()));
-
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+        self.handle_event(Event::MouseDown(event.clone()));
     }
 
     fn mouse_up(&mut self, event: &MouseEvent) {
         // Handle the mouse up event.
-        self.application
-            .handle_event(&Event::MouseUp(event.clone()));
-
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+        self.handle_event(Event::MouseUp(event.clone()));
     }
 
     fn mouse_leave(&mut self) {
@@ -124,31 +352,40 @@ impl WinHandler for WindowEventHandler {
             .invalidate_rect(self.window_size.to_rect());
     }
 
-    fn timer(&mut self, _token: TimerToken) {}
+    fn timer(&mut self, _token: TimerToken) {
+        // Handle the timer event.
+        self.handle_event(Event::Timer);
+    }
 
     fn got_focus(&mut self) {
-        // TODO: Handle the event.
-
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+        // Handle the window focused event.
+        self.handle_event(Event::WindowFocused);
     }
 
     fn lost_focus(&mut self) {
-        // TODO: Handle the event.
-
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+        // Handle the window unfocused event.
+        self.handle_event(Event::WindowUnfocused);
     }
 
     fn request_close(&mut self) {
-        // Handle the close request event.
-        self.application.handle_event(&Event::RequestClose);
-
-        self.window_handle
-            .invalidate_rect(self.window_size.to_rect());
+        let windows = self.windows.clone();
+        let window_id = self.window_id;
+
+        // The application denies closing the window, but may have asked for a confirmation.
+        if request_close(&windows, window_id, || self.handle_window_requests())
+            == CloseResponse::Deny
+        {
+            self.window_handle
+                .invalidate_rect(self.window_size.to_rect());
+        }
     }
 
-    fn destroy(&mut self) {}
+    fn destroy(&mut self) {
+        // The last window was closed and the application agrees to quit.
+        if window_closed(&self.windows, self.window_id) {
+            druid_shell::Application::global().quit();
+        }
+    }
 
     fn idle(&mut self, _token: IdleToken) {}
 
@@ -0,0 +1,40 @@
+use druid_shell::kurbo::{Point, Size};
+
+/// The options with which a window is created.
+///
+/// There is no window icon option yet: druid-shell cannot set a window's icon, so the icon is the
+/// one that the platform gives the application, e.g. from its bundle on macOS.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowOptions {
+    /// Whether the window stays on top of other windows.
+    pub is_always_on_top: bool,
+    /// Whether the user can resize the window.
+    pub is_resizable: bool,
+    /// Whether the window's background is transparent where nothing is painted.
+    pub is_transparent: bool,
+    /// The size that the window is not resized beyond.
+    pub maximum_size: Option<Size>,
+    /// The size that the window can not be resized below.
+    pub minimum_size: Option<Size>,
+    /// The initial position of the window's top left corner on the screen. `None` lets the operating
+    /// system choose.
+    pub position: Option<Point>,
+    /// The initial size.
+    pub size: Size,
+    pub title: String,
+}
+
+impl Default for WindowOptions {
+    fn default() -> Self {
+        WindowOptions {
+            is_always_on_top: false,
+            is_resizable: true,
+            is_transparent: false,
+            maximum_size: None,
+            minimum_size: None,
+            position: None,
+            size: Size::new(400.0, 200.0),
+            title: String::new(),
+        }
+    }
+}
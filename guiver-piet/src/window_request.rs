@@ -0,0 +1,40 @@
+use crate::{TextFieldState, WindowId, WindowOptions};
+use druid_shell::kurbo::Size;
+use druid_shell::{FileDialogOptions, Menu};
+use std::time::Duration;
+
+/// Identifies a file dialog shown with `WindowRequest::ShowOpenDialog` or `ShowSaveDialog`, so that
+/// the `Event::FileChosen` can be related to it.
+pub type FileDialogId = usize;
+
+/// A request from the application to its window. The window event handler asks the application
+/// for requests via `PietApplication::window_requests()` after each event.
+pub enum WindowRequest {
+    /// Closes the window with the given ID, which need not be the window that the request was made
+    /// for. The application gets an `Event::WindowClosed` for it. If no window with that ID is open,
+    /// nothing happens.
+    CloseWindow(WindowId),
+    /// Opens another window with the given ID and options. The new window's events are given to the
+    /// application with that ID. If a window with that ID is open already, nothing happens.
+    OpenWindow {
+        window_id: WindowId,
+        window_options: WindowOptions,
+    },
+    /// Requests an `Event::Timer` after the given duration.
+    RequestTimer(Duration),
+    /// Sets the window's menu bar.
+    SetMenu(Menu),
+    /// Resizes the window. A maximum size given in the window's options still applies.
+    SetSize(Size),
+    /// Tells the platform's text input, e.g. an IME, about the focused text widget's state. `None`
+    /// means that no text widget has focus.
+    SetTextField(Option<TextFieldState>),
+    ///
+    SetTitle(String),
+    /// Shows a native dialog to open files. Once it is closed, an `Event::FileChosen` with the given
+    /// ID follows.
+    ShowOpenDialog(FileDialogId, FileDialogOptions),
+    /// Shows a native dialog to choose a file to save to. Once it is closed, an `Event::FileChosen`
+    /// with the given ID follows.
+    ShowSaveDialog(FileDialogId, FileDialogOptions),
+}
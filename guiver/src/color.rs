@@ -0,0 +1,31 @@
+/// A backend-neutral RGBA color.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Color {
+    pub alpha: u8,
+    pub blue: u8,
+    pub green: u8,
+    pub red: u8,
+}
+
+impl Color {
+    ///
+    pub const BLACK: Color = Color::rgb8(0, 0, 0);
+
+    ///
+    pub const WHITE: Color = Color::rgb8(255, 255, 255);
+
+    /// Creates an opaque color.
+    pub const fn rgb8(red: u8, green: u8, blue: u8) -> Self {
+        Color::rgba8(red, green, blue, 255)
+    }
+
+    ///
+    pub const fn rgba8(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Color {
+            alpha,
+            blue,
+            green,
+            red,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::Color;
+
+/// A backend-neutral font description.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Font {
+    pub font_color: Color,
+    pub font_family: FontFamily,
+    pub font_size: f64,
+    /// The font weight from 1 to 1000. 400 is regular, 700 is bold.
+    pub font_weight: u16,
+    pub has_strikethrough: bool,
+    pub has_underline: bool,
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Font {
+            font_color: Color::WHITE,
+            font_family: FontFamily::SystemUi,
+            font_size: 14.0,
+            font_weight: 400,
+            has_strikethrough: false,
+            has_underline: false,
+        }
+    }
+}
+
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FontFamily {
+    Monospace,
+    /// A font family given by its name, e.g. "Helvetica".
+    Named(String),
+    SansSerif,
+    Serif,
+    #[default]
+    SystemUi,
+}
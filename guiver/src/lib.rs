@@ -1,19 +1,39 @@
+mod color;
+mod font;
 mod point;
 mod rectangle;
 mod size;
+mod stroke;
 pub mod widget;
 pub mod widget_manager;
 
+pub use color::Color;
+pub use font::{Font, FontFamily};
 pub use point::Point;
 pub use rectangle::Rectangle;
 pub use size::Size;
+pub use stroke::Stroke;
+pub use widget::accessibility::{AccessibilityNode, AccessibilityRole};
 pub use widget::alignment::{HorizontalAlignment, VerticalAlignment};
+pub use widget::command::WidgetCommand;
 pub use widget::error::WidgetError;
 pub use widget::event::WidgetEvent;
+pub use widget::event_observation::{
+    EventMapper, EventObservationHandle, Modifiers, WidgetEventData, WidgetEventObservation,
+};
 pub use widget::event_type::WidgetEventType;
 pub use widget::grid::{GridColumnProperties, GridRowProperties};
+pub use widget::layout_direction::LayoutDirection;
 pub use widget::placement::WidgetPlacement;
 pub use widget::r#type::WidgetType;
 pub use widget::size_constraints::SizeConstraints;
+pub use widget::value::{WidgetValue, WidgetValueKind};
 pub use widget::{Widget, WidgetId};
+pub use widget_manager::drag_and_drop::{DragPayload, DropData, DropMapper, DropTarget};
+pub use widget_manager::file_dialog::{FileDialogMapper, FileDialogOptions, FileType};
+pub use widget_manager::focus_order::FocusOrder;
+pub use widget_manager::gesture_thresholds::GestureThresholds;
 pub use widget_manager::id_provider::WidgetIdProvider;
+pub use widget_manager::menu_item::MenuItem;
+pub use widget_manager::message::{Message, MessageArgument};
+pub use widget_manager::shortcut::{ParseShortcutError, Shortcut, ShortcutAction};
@@ -1,7 +1,7 @@
 use crate::{Point, Size};
 
 ///
-#[derive(Clone, Copy, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Rectangle {
     pub x0: f64,
     pub y0: f64,
@@ -0,0 +1,20 @@
+use crate::Color;
+
+/// A backend-neutral stroke description.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stroke {
+    pub stroke_color: Color,
+    /// Alternating lengths of dashes and gaps. An empty pattern draws a solid line.
+    pub stroke_dash_pattern: Vec<f64>,
+    pub stroke_width: f64,
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Stroke {
+            stroke_color: Color::BLACK,
+            stroke_dash_pattern: vec![],
+            stroke_width: 1.0,
+        }
+    }
+}
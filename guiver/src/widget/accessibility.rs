@@ -0,0 +1,112 @@
+use crate::{Rectangle, WidgetId};
+use std::fmt::{Display, Formatter};
+
+/// A node of a window's accessibility tree, which describes a widget to assistive technologies like
+/// screen readers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    /// The widget's rectangle in window coordinates.
+    pub bounds: Rectangle,
+    /// The nodes of the widget's child widgets, in the order they were added.
+    pub children: Vec<AccessibilityNode>,
+    /// Whether the widget itself or one of its ancestor widgets is disabled.
+    pub is_disabled: bool,
+    ///
+    pub is_focused: bool,
+    ///
+    pub is_hidden: bool,
+    /// The name that a screen reader announces, e.g. a button's text.
+    pub name: Option<String>,
+    ///
+    pub role: AccessibilityRole,
+    /// The widget's value, e.g. the text of a text field.
+    pub value: Option<String>,
+    ///
+    pub widget_id: WidgetId,
+}
+
+impl AccessibilityNode {
+    /// Returns the node and its descendants as text, one node per line and indented by depth, e.g.
+    /// `button #1 "OK" focused`. Tests can assert on this instead of using a screen reader.
+    pub fn dump(&self) -> String {
+        let mut text = String::new();
+        self.dump_with_depth(0, &mut text);
+        text
+    }
+
+    ///
+    fn dump_with_depth(&self, depth: usize, text: &mut String) {
+        text.push_str(&"  ".repeat(depth));
+        text.push_str(&format!("{} #{}", self.role, self.widget_id));
+
+        // The node has a name.
+        if let Some(name) = &self.name {
+            text.push_str(&format!(" {:?}", name));
+        }
+
+        // The node has a value.
+        if let Some(value) = &self.value {
+            text.push_str(&format!(" value={:?}", value));
+        }
+
+        // Append the states.
+        for (state, has_state) in [
+            ("disabled", self.is_disabled),
+            ("focused", self.is_focused),
+            ("hidden", self.is_hidden),
+        ] {
+            // The node has the state.
+            if has_state {
+                text.push(' ');
+                text.push_str(state);
+            }
+        }
+
+        text.push('\n');
+
+        // Iterate over the child nodes.
+        for child in &self.children {
+            child.dump_with_depth(depth + 1, text);
+        }
+    }
+
+    /// Returns the node with the given widget ID, which is either this node or one of its
+    /// descendants.
+    pub fn find(&self, widget_id: WidgetId) -> Option<&AccessibilityNode> {
+        // This is the node with the given widget ID.
+        if self.widget_id == widget_id {
+            Some(self)
+        }
+        // Search the descendants.
+        else {
+            self.children.iter().find_map(|child| child.find(widget_id))
+        }
+    }
+}
+
+/// The role of a widget for assistive technologies like screen readers.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AccessibilityRole {
+    Button,
+    /// A widget that groups other widgets, like a layout widget.
+    Group,
+    /// A text that is only read, like a `Text` widget.
+    Label,
+    Link,
+    /// A text that can be edited, like a `TextInput` widget.
+    TextField,
+}
+
+impl Display for AccessibilityRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AccessibilityRole::Button => "button",
+            AccessibilityRole::Group => "group",
+            AccessibilityRole::Label => "label",
+            AccessibilityRole::Link => "link",
+            AccessibilityRole::TextField => "text field",
+        };
+
+        write!(f, "{}", name)
+    }
+}
@@ -1,10 +1,34 @@
+use crate::LayoutDirection;
+
 ///
 #[derive(Clone, Copy, Debug, Default)]
 pub enum HorizontalAlignment {
     #[default]
     Center,
+    /// Right in a right-to-left layout or paragraph, otherwise left.
+    End,
     Left,
     Right,
+    /// Left in a left-to-right layout or paragraph, otherwise right.
+    Start,
+}
+
+impl HorizontalAlignment {
+    /// Returns `Left` or `Right` for `Start` and `End`, depending on the given direction. The other
+    /// alignments are returned as they are.
+    pub fn resolved(self, layout_direction: LayoutDirection) -> HorizontalAlignment {
+        match (self, layout_direction) {
+            (HorizontalAlignment::End, LayoutDirection::LeftToRight)
+            | (HorizontalAlignment::Start, LayoutDirection::RightToLeft) => {
+                HorizontalAlignment::Right
+            }
+            (HorizontalAlignment::End, LayoutDirection::RightToLeft)
+            | (HorizontalAlignment::Start, LayoutDirection::LeftToRight) => {
+                HorizontalAlignment::Left
+            }
+            (horizontal_alignment, _) => horizontal_alignment,
+        }
+    }
 }
 
 // =================================================================================================
@@ -0,0 +1,31 @@
+use crate::{
+    Color, Font, HorizontalAlignment, LayoutDirection, Stroke, VerticalAlignment, WidgetValue,
+};
+
+/// A command to a single widget. The widget manager passes it on to the widget via
+/// `Command::ToWidget`. A widget handles the commands it supports and returns
+/// `WidgetError::NotHandled` for all others.
+#[derive(Debug)]
+pub enum WidgetCommand {
+    /// Sets the widget's fill, e.g. a button's background. `None` removes the fill.
+    SetFill(Option<Color>),
+    /// Sets the widget's font.
+    SetFont(Font),
+    /// Sets the widget's horizontal alignment. This could refer to child widgets, text etc..
+    SetHorizontalAlignment(HorizontalAlignment),
+    /// Disables/enables the widget. Disabled widgets ignore mouse and keyboard input. Container
+    /// widgets disable/enable their descendants as well.
+    SetIsDisabled(bool),
+    /// Hides/shows the widget.
+    SetIsHidden(bool),
+    /// Sets the direction in which the widget and its descendants lay out their child widgets and
+    /// text. `None` takes the direction of the parent widget or the window again.
+    SetLayoutDirection(Option<LayoutDirection>),
+    /// Sets the widget's stroke, e.g. a button's border. `None` removes the stroke.
+    SetStroke(Option<Stroke>),
+    /// Sets the given value to the widget. Values of a kind the widget does not accept result in
+    /// `WidgetError::ValueKindNotAccepted`.
+    SetValue(WidgetValue),
+    /// Sets the widget's vertical alignment. This could refer to child widgets, text etc..
+    SetVerticalAlignment(VerticalAlignment),
+}
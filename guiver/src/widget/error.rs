@@ -1,8 +1,22 @@
-use crate::WidgetId;
+use crate::{Shortcut, WidgetId, WidgetValueKind};
 
 ///
 #[derive(Debug)]
 pub enum WidgetError {
+    /// The command with the given index in a batch of commands failed with the given error. None of
+    /// the batch's commands was applied. Commands that result from handling the batch are counted
+    /// after the batch's commands.
+    CommandFailed {
+        command_index: usize,
+        error: Box<WidgetError>,
+    },
+    /// The locale is no valid language identifier like "en-US".
+    InvalidLocale(String),
+    /// The message catalog for the given locale has syntax errors.
+    InvalidMessageCatalog {
+        locale: String,
+        description: String,
+    },
     NoSuchChildWidget {
         parent_widget_id: WidgetId,
         child_widget_id: WidgetId,
@@ -12,5 +26,15 @@ pub enum WidgetError {
         widget_id: WidgetId,
         description: String,
     },
+    ShortcutExistsAlready {
+        scope_widget_id: Option<WidgetId>,
+        shortcut: Shortcut,
+    },
+    /// The widget does not accept values of the given kind. `Widget::accepted_value_kinds()`
+    /// returns the kinds it accepts.
+    ValueKindNotAccepted {
+        widget_id: WidgetId,
+        value_kind: WidgetValueKind,
+    },
     WidgetExistsAlready(WidgetId),
 }
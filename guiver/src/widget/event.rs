@@ -4,6 +4,11 @@ use crate::WidgetId;
 #[derive(Clone, Debug)]
 pub enum WidgetEvent<APP_EVENT: Clone> {
     AppEvent(APP_EVENT),
+    /// The widget with the given ID captured the pointer: it gets the pointer events until it
+    /// releases the pointer, even while the pointer is outside of it.
+    CapturedPointer(WidgetId),
     GainedFocus(WidgetId),
     LostFocus(WidgetId),
+    /// The widget with the given ID released the pointer.
+    ReleasedPointer(WidgetId),
 }
@@ -0,0 +1,63 @@
+use crate::{WidgetEvent, WidgetId, WidgetValue};
+use std::rc::Rc;
+
+/// Maps the data of an observed widget event to an application event.
+pub type EventMapper<APP_EVENT> = Rc<dyn Fn(&WidgetEventData) -> APP_EVENT>;
+
+/// Identifies a widget event observation, so that it can be removed again.
+pub type EventObservationHandle = usize;
+
+/// What a widget produces when an observed widget event occurs.
+#[derive(Clone)]
+pub enum WidgetEventObservation<APP_EVENT: Clone> {
+    /// A copy of the given application event.
+    AppEvent(APP_EVENT),
+    /// The application event that the given mapper returns for the event's data.
+    Mapper(EventMapper<APP_EVENT>),
+}
+
+impl<APP_EVENT: Clone> WidgetEventObservation<APP_EVENT> {
+    /// Returns the widget event for an occurrence of the observed widget event with the given data.
+    pub fn widget_event(&self, widget_event_data: &WidgetEventData) -> WidgetEvent<APP_EVENT> {
+        match self {
+            WidgetEventObservation::AppEvent(app_event) => WidgetEvent::AppEvent(app_event.clone()),
+            WidgetEventObservation::Mapper(event_mapper) => {
+                WidgetEvent::AppEvent(event_mapper(widget_event_data))
+            }
+        }
+    }
+}
+
+/// The data of an occurrence of an observed widget event.
+#[derive(Debug)]
+pub struct WidgetEventData {
+    /// The number of successive clicks for `WidgetEventType::Clicked`, e.g. 2 for a double click.
+    pub click_count: u8,
+    /// The modifier keys that were held down.
+    pub modifiers: Modifiers,
+    /// The widget's value, e.g. the new text for `WidgetEventType::ValueChanged` on a text input.
+    pub value: Option<WidgetValue>,
+    /// The ID of the widget in which the event occurred.
+    pub widget_id: WidgetId,
+}
+
+impl WidgetEventData {
+    /// Creates event data without clicks, modifier keys and value.
+    pub fn new(widget_id: WidgetId) -> Self {
+        WidgetEventData {
+            click_count: 0,
+            modifiers: Modifiers::default(),
+            value: None,
+            widget_id,
+        }
+    }
+}
+
+/// The modifier keys that are held down.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Modifiers {
+    pub has_alt: bool,
+    pub has_ctrl: bool,
+    pub has_meta: bool,
+    pub has_shift: bool,
+}
@@ -3,8 +3,18 @@
 pub enum WidgetEventType {
     /// The widget was clicked.
     Clicked,
+    /// The widget was double-clicked.
+    DoubleClicked,
+    /// A drag that started in the widget ended.
+    DragEnded,
+    /// A drag started in the widget: the pointer moved far enough while it was pressed.
+    DragStarted,
+    /// The pointer moved during a drag that started in the widget.
+    Dragged,
     /// The widget gained focus.
     GainedFocus,
+    /// The pointer was pressed on the widget long enough without dragging.
+    LongPressed,
     /// The widget lost focus.
     LostFocus,
     /// The widget was submitted, e.g. by pressing Enter on a text input.
@@ -0,0 +1,9 @@
+/// The direction in which widgets lay out their child widgets and text. Right-to-left is meant for
+/// languages like Arabic and Hebrew: it mirrors `Row`, `Padding` and `Grid` widgets and the `Start`
+/// and `End` alignments.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum LayoutDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
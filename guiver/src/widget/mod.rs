@@ -1,18 +1,25 @@
-use crate::{HorizontalAlignment, Point, Rectangle, Size, SizeConstraints, VerticalAlignment};
+use crate::{Point, Rectangle, Size, SizeConstraints};
+use accessibility::AccessibilityRole;
 use error::WidgetError;
-use event::WidgetEvent;
+use event_observation::{EventObservationHandle, WidgetEventObservation};
 use event_type::WidgetEventType;
-use std::any::Any;
+use layout_direction::LayoutDirection;
+use value::{WidgetValue, WidgetValueKind};
 
+pub mod accessibility;
 pub mod alignment;
+pub mod command;
 pub mod error;
 pub mod event;
+pub mod event_observation;
 pub mod event_type;
 pub mod grid;
+pub mod layout_direction;
 mod location;
 pub mod placement;
 pub mod size_constraints;
 pub mod r#type;
+pub mod value;
 
 ///
 pub type WidgetId = usize;
@@ -20,33 +27,65 @@ pub type WidgetId = usize;
 /// A trait for widgets.
 /// The methods are typically called by a `WidgetManager` and parental composite widgets.
 pub trait Widget<APP_EVENT: Clone> {
+    /// Returns the kinds of values that the widget accepts via `WidgetCommand::SetValue`. Values of
+    /// other kinds result in `WidgetError::ValueKindNotAccepted`.
+    fn accepted_value_kinds(&self) -> &'static [WidgetValueKind] {
+        &[]
+    }
+
+    /// Returns the name that assistive technologies like screen readers announce for the widget,
+    /// e.g. the text of a `Text` widget.
+    fn accessibility_name(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the widget's role for assistive technologies like screen readers.
+    fn accessibility_role(&self) -> AccessibilityRole {
+        AccessibilityRole::Group
+    }
+
+    /// Returns the widget's value for assistive technologies like screen readers, e.g. the text of
+    /// a `TextInput` widget.
+    fn accessibility_value(&self) -> Option<String> {
+        None
+    }
+
     /// Returns `true` if the widget generally accepts focus, like e.g. a `Button` or `TextInput`
     /// widget. A `WidgetManager` uses this to build a tab/focus order.
     fn accepts_focus(&self) -> bool {
         false
     }
 
-    ///
+    /// Adds an observation of the given widget event type. An observation with the same handle is
+    /// replaced.
     fn add_event_observation(
         &mut self,
+        event_observation_handle: EventObservationHandle,
         widget_event_type: WidgetEventType,
-        widget_event: WidgetEvent<APP_EVENT>,
+        widget_event_observation: WidgetEventObservation<APP_EVENT>,
     );
 
     /// Applies the given size constraints to the widget and returns its size.
     fn apply_size_constraints(&mut self, size_constraints: SizeConstraints) -> Size;
 
-    ///
-    fn event_observation(
+    /// Returns the widget's observations of the given widget event type, in the order they were
+    /// added.
+    fn event_observations(
         &mut self,
         widget_event_type: &WidgetEventType,
-    ) -> Option<&WidgetEvent<APP_EVENT>>;
+    ) -> Vec<&WidgetEventObservation<APP_EVENT>>;
 
     /// Returns the widget's flex factor. This is used in layout widgets like `Column` and `Row`.
     fn flex_factor(&self) -> u16 {
         0
     }
 
+    /// Returns `true` if the widget itself or one of its ancestor widgets is disabled.
+    fn is_disabled(&self) -> bool;
+
+    /// Returns `true` if the widget is hidden.
+    fn is_hidden(&self) -> bool;
+
     /// Returns the widget's rectangle.
     fn rectangle(&self) -> &Rectangle;
 
@@ -67,16 +106,27 @@ pub trait Widget<APP_EVENT: Clone> {
     }
 
     ///
-    fn remove_event_observation(&mut self, widget_event_type: &WidgetEventType);
+    fn remove_event_observation(&mut self, event_observation_handle: EventObservationHandle);
 
     /// Returns the widget's selected value. This can be e.g. selected text in a `TextInput` widget.
-    fn selected_value(&self) -> Option<Box<dyn Any>> {
+    fn selected_value(&self) -> Option<WidgetValue> {
         self.value()
     }
 
+    /// Tells the widget the layout direction of its nearest ancestor widget with a layout direction,
+    /// or the window's. Container widgets pass this on to their child widgets. The widget's own
+    /// setting from `WidgetCommand::SetLayoutDirection` is kept. Widgets without child widgets or
+    /// text can ignore it.
+    fn set_ancestor_layout_direction(&mut self, _layout_direction: LayoutDirection) {}
+
     /// Enables or disables the widget's debug rendering.
     fn set_debug_rendering(&mut self, debug_rendering: bool);
 
+    /// Tells the widget whether one of its ancestor widgets is disabled. Container widgets pass this
+    /// on to their child widgets. The widget's own setting from `WidgetCommand::SetIsDisabled` is
+    /// kept.
+    fn set_has_disabled_ancestor(&mut self, has_disabled_ancestor: bool);
+
     /// Gives focus to or removes focus from the widget.
     fn set_has_focus(&mut self, _has_focus: bool) -> Result<(), WidgetError> {
         Err(WidgetError::NotHandled {
@@ -85,39 +135,11 @@ pub trait Widget<APP_EVENT: Clone> {
         })
     }
 
-    /// Sets a widget's horizontal alignment. This could refer to child widgets, text etc..
-    fn set_horizontal_alignment(
-        &mut self,
-        _horizontal_alignment: HorizontalAlignment,
-    ) -> Result<(), WidgetError> {
-        Err(WidgetError::NotHandled {
-            widget_id: *self.widget_id(),
-            description: "`set_horizontal_alignment()`".to_string(),
-        })
-    }
-
-    /// Disables or enables the widget.
-    fn set_is_disabled(&mut self, is_disabled: bool);
-
-    /// Hides or shows the widget.
-    fn set_is_hidden(&mut self, is_hidden: bool);
-
     /// Sets the widget's origin.
     fn set_origin(&mut self, origin: Point);
 
-    /// Sets a widget's vertical alignment. This could refer to child widgets, text etc..
-    fn set_vertical_alignment(
-        &mut self,
-        _vertical_alignment: VerticalAlignment,
-    ) -> Result<(), WidgetError> {
-        Err(WidgetError::NotHandled {
-            widget_id: *self.widget_id(),
-            description: "`set_vertical_alignment()`".to_string(),
-        })
-    }
-
     /// Returns the widget's value.
-    fn value(&self) -> Option<Box<dyn Any>> {
+    fn value(&self) -> Option<WidgetValue> {
         None
     }
 
@@ -1,4 +1,4 @@
-use crate::{GridColumnProperties, GridRowProperties, Size};
+use crate::{GridColumnProperties, GridRowProperties, Message, Size};
 
 /// The type of a widget to construct.
 #[derive(Clone, Debug)]
@@ -21,6 +21,12 @@ pub enum WidgetType {
     LayoutSizedBox {
         desired_size: Size,
     },
+    /// A hyperlink whose text is the given message in the current locale's language.
+    LocalizedHyperlink(Message),
+    /// A text whose text is the given message in the current locale's language.
+    LocalizedText(Message),
+    /// A text button whose text is the given message in the current locale's language.
+    LocalizedTextButton(Message),
     Text(String),
     TextButton(String),
     TextInput {
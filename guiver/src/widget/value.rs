@@ -0,0 +1,148 @@
+use std::any::Any;
+
+/// A widget's value, e.g. the text of a `Text` widget.
+#[derive(Debug)]
+pub enum WidgetValue {
+    Bool(bool),
+    /// A value of a type that only the application and a custom widget know.
+    Custom(Box<dyn Any>),
+    /// The index of the selected item, e.g. in a list.
+    Index(usize),
+    /// The indices of the selected items, e.g. in a list with multiple selection.
+    Indices(Vec<usize>),
+    Number(f64),
+    Text(String),
+}
+
+impl WidgetValue {
+    ///
+    pub fn as_bool(&self) -> Option<bool> {
+        if let WidgetValue::Bool(bool) = self {
+            Some(*bool)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the custom value if it is of type `T`.
+    pub fn as_custom<T: 'static>(&self) -> Option<&T> {
+        if let WidgetValue::Custom(value) = self {
+            value.downcast_ref::<T>()
+        } else {
+            None
+        }
+    }
+
+    ///
+    pub fn as_index(&self) -> Option<usize> {
+        if let WidgetValue::Index(index) = self {
+            Some(*index)
+        } else {
+            None
+        }
+    }
+
+    ///
+    pub fn as_indices(&self) -> Option<&[usize]> {
+        if let WidgetValue::Indices(indices) = self {
+            Some(indices)
+        } else {
+            None
+        }
+    }
+
+    ///
+    pub fn as_number(&self) -> Option<f64> {
+        if let WidgetValue::Number(number) = self {
+            Some(*number)
+        } else {
+            None
+        }
+    }
+
+    ///
+    pub fn as_text(&self) -> Option<&str> {
+        if let WidgetValue::Text(text) = self {
+            Some(text)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the kind of the value.
+    pub fn kind(&self) -> WidgetValueKind {
+        match self {
+            WidgetValue::Bool(_) => WidgetValueKind::Bool,
+            WidgetValue::Custom(_) => WidgetValueKind::Custom,
+            WidgetValue::Index(_) => WidgetValueKind::Index,
+            WidgetValue::Indices(_) => WidgetValueKind::Indices,
+            WidgetValue::Number(_) => WidgetValueKind::Number,
+            WidgetValue::Text(_) => WidgetValueKind::Text,
+        }
+    }
+
+    /// Returns a text representation of the value, e.g. for the clipboard. Custom values have none.
+    pub fn to_text(&self) -> Option<String> {
+        match self {
+            WidgetValue::Bool(bool) => Some(bool.to_string()),
+            WidgetValue::Custom(_) => None,
+            WidgetValue::Index(index) => Some(index.to_string()),
+            WidgetValue::Indices(indices) => Some(
+                indices
+                    .iter()
+                    .map(|index| index.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            WidgetValue::Number(number) => Some(number.to_string()),
+            WidgetValue::Text(text) => Some(text.clone()),
+        }
+    }
+}
+
+impl From<bool> for WidgetValue {
+    fn from(bool: bool) -> Self {
+        WidgetValue::Bool(bool)
+    }
+}
+
+impl From<f64> for WidgetValue {
+    fn from(number: f64) -> Self {
+        WidgetValue::Number(number)
+    }
+}
+
+impl From<usize> for WidgetValue {
+    fn from(index: usize) -> Self {
+        WidgetValue::Index(index)
+    }
+}
+
+impl From<Vec<usize>> for WidgetValue {
+    fn from(indices: Vec<usize>) -> Self {
+        WidgetValue::Indices(indices)
+    }
+}
+
+impl From<&str> for WidgetValue {
+    fn from(text: &str) -> Self {
+        WidgetValue::Text(text.to_string())
+    }
+}
+
+impl From<String> for WidgetValue {
+    fn from(text: String) -> Self {
+        WidgetValue::Text(text)
+    }
+}
+
+/// The kind of a `WidgetValue`. Widgets declare which kinds of values they accept.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WidgetValueKind {
+    Bool,
+    Custom,
+    Index,
+    Indices,
+    Number,
+    Text,
+}
@@ -1,7 +1,8 @@
 use crate::{
-    HorizontalAlignment, VerticalAlignment, WidgetEventType, WidgetId, WidgetPlacement, WidgetType,
+    DragPayload, DropTarget, EventMapper, EventObservationHandle, FileDialogMapper,
+    FileDialogOptions, FocusOrder, GestureThresholds, LayoutDirection, MenuItem, Message, Shortcut,
+    ShortcutAction, Size, WidgetCommand, WidgetEventType, WidgetId, WidgetPlacement, WidgetType,
 };
-use std::any::Any;
 
 /// A command to the widget manager.
 pub enum Command<APP_EVENT> {
@@ -13,13 +14,41 @@ pub enum Command<APP_EVENT> {
     },
     /// Adds widget event observation: if a widget event of type `WidgetEventType` occurs in the
     /// widget with the given ID, it produces a value of type `WidgetEvent::Custom(APP_EVENT)` in
-    /// `handle_event()`.
-    AddEventObservation(WidgetId, WidgetEventType, APP_EVENT),
+    /// `handle_event()`. A widget event type can have several observations, each with its own
+    /// handle. Adding an observation with a handle that the widget has already replaces it.
+    AddEventObservation(WidgetId, EventObservationHandle, WidgetEventType, APP_EVENT),
+    /// Adds widget event observation with a mapper: if a widget event of type `WidgetEventType`
+    /// occurs in the widget with the given ID, the mapper turns the event's data, like the widget's
+    /// new value or the click count, into an application event.
+    AddMappedEventObservation(
+        WidgetId,
+        EventObservationHandle,
+        WidgetEventType,
+        EventMapper<APP_EVENT>,
+    ),
+    /// Adds a message catalog in Fluent syntax for the given locale, e.g. "de" or "de-AT". A locale
+    /// uses the catalogs of its language as well, so that "de-AT" only needs the messages that
+    /// differ from "de". Messages of later catalogs replace those of earlier ones.
+    AddMessageCatalog {
+        locale: String,
+        fluent_source: String,
+    },
     /// Adds the child widgets to the parent widget.
     AddChildren {
         parent_widget_id: WidgetId,
         child_widgets: Vec<(Option<WidgetPlacement>, WidgetId)>,
     },
+    /// Adds a keyboard shortcut. If a scope widget is given, the shortcut only applies while the
+    /// widget or one of its descendants has focus. Such a scoped shortcut takes precedence over a
+    /// shortcut without scope or with an outer scope. Adding a shortcut that exists already in the
+    /// given scope is an error.
+    AddShortcut {
+        scope_widget_id: Option<WidgetId>,
+        shortcut: Shortcut,
+        shortcut_action: ShortcutAction<APP_EVENT>,
+    },
+    /// Closes the window of the widget manager.
+    CloseWindow,
     /// Creates and adds a widget with the given ID and type.
     CreateWidget(WidgetId, WidgetType),
     /// Destroys the widget with the given ID.
@@ -35,22 +64,68 @@ pub enum Command<APP_EVENT> {
         parent_widget_id: WidgetId,
         destroy_child_widgets: bool,
     },
-    /// Removes observation of the given widget's event.
-    RemoveEventObservation(WidgetId, WidgetEventType),
+    /// Removes the given widget's event observation with the given handle.
+    RemoveEventObservation(WidgetId, EventObservationHandle),
+    /// Removes the keyboard shortcut from the given scope.
+    RemoveShortcut {
+        scope_widget_id: Option<WidgetId>,
+        shortcut: Shortcut,
+    },
+    /// Sets the name that assistive technologies like screen readers announce for the widget,
+    /// overriding the widget's own name. This is useful e.g. for widgets without text. `None` removes
+    /// the name again.
+    SetAccessibilityName(WidgetId, Option<String>),
+    /// Sets the widget's context menu, which is opened by a right click on the widget. An empty menu
+    /// removes the context menu.
+    SetContextMenu(WidgetId, Vec<MenuItem<APP_EVENT>>),
     /// Enables/disables debug rendering mode for the widget.
     SetDebugRendering(WidgetId, bool),
+    /// Makes the widget draggable with the given payload. `None` makes it undraggable again.
+    SetDragPayload(WidgetId, Option<DragPayload>),
+    /// Makes the widget a drop target for payloads of certain kinds. While a payload that it accepts
+    /// is dragged over it, it is highlighted. `None` makes it no drop target anymore.
+    SetDropTarget(WidgetId, Option<DropTarget<APP_EVENT>>),
+    /// Sets the order in which Tab and Shift+Tab move the focus. Hidden and disabled widgets are
+    /// skipped.
+    SetFocusOrder(FocusOrder),
+    /// Sets the thresholds with which double clicks, long presses and drags are recognized.
+    SetGestureThresholds(GestureThresholds),
     /// Gives/removes focus to the widget.
     SetHasFocus(WidgetId, bool),
-    /// Sets the widget's horizontal alignment.
-    SetHorizontalAlignment(WidgetId, HorizontalAlignment),
-    /// Enables/disables the widget.
-    SetIsDisabled(WidgetId, bool),
-    /// Hides/shows the widget.
-    SetIsHidden(WidgetId, bool),
+    /// Makes/unmakes the widget a focus scope: while the widget or one of its descendants has
+    /// focus, Tab and Shift+Tab cycle within the widget's widget tree, e.g. in a dialog.
+    SetIsFocusScope(WidgetId, bool),
+    /// Sets the window's layout direction, e.g. right-to-left for Arabic or Hebrew. Widget trees with
+    /// their own layout direction from `WidgetCommand::SetLayoutDirection` keep it.
+    SetLayoutDirection(LayoutDirection),
+    /// Sets the locale, e.g. "ar-EG", and looks up the messages of all localized widgets again.
+    SetLocale(String),
     /// Makes the widget with the given ID the main widget.
     SetMainWidget(WidgetId),
-    /// Sets the given value to the widget.
-    SetValue(WidgetId, Box<dyn Any>),
-    /// Sets the widget's vertical alignment.
-    SetVerticalAlignment(WidgetId, VerticalAlignment),
+    /// Sets the window's menu bar. Its top-level items are usually submenus. The selected items'
+    /// application events are returned by `handle_event()`.
+    SetMenuBar(Vec<MenuItem<APP_EVENT>>),
+    /// Sets the message whose translation is the text of the widget, e.g. of a `Text` or a text
+    /// button. It is looked up again whenever the locale changes. `None` keeps the current text.
+    SetMessage(WidgetId, Option<Message>),
+    /// Sets the widget's tab index. Widgets with a tab index receive focus before all other widgets,
+    /// in ascending order of their tab indices. `None` removes the tab index.
+    SetTabIndex(WidgetId, Option<usize>),
+    /// Resizes the window of the widget manager.
+    SetWindowSize(Size),
+    /// Sets the title of the window of the widget manager.
+    SetWindowTitle(String),
+    /// Sets the factor by which the window's content is zoomed. The factor is limited to the range
+    /// from 0.25 to 4.
+    SetZoomFactor(f64),
+    /// Shows a native dialog to choose one or, if the options allow it, several existing files. The
+    /// mapper turns the chosen files' paths into an application event, which `handle_event()` returns
+    /// once the dialog is closed.
+    ShowOpenDialog(FileDialogOptions, FileDialogMapper<APP_EVENT>),
+    /// Shows a native dialog to choose a file to save to. The mapper turns the chosen file's path
+    /// into an application event, which `handle_event()` returns once the dialog is closed.
+    ShowSaveDialog(FileDialogOptions, FileDialogMapper<APP_EVENT>),
+    /// Passes the given commands on to the widget with the given ID, e.g. to set its value or to
+    /// hide it. The commands are handled in the given order.
+    ToWidget(WidgetId, Vec<WidgetCommand>),
 }
@@ -0,0 +1,91 @@
+use crate::{Modifiers, Point, WidgetId};
+use std::any::Any;
+use std::rc::Rc;
+
+/// What a draggable widget carries to a drop target.
+#[derive(Clone)]
+pub struct DragPayload {
+    /// A value that only the application knows, e.g. the index of a dragged list row.
+    data: Rc<dyn Any>,
+    /// The kind of the payload, e.g. "list-row". Drop targets accept payloads by their kind.
+    kind: String,
+    /// The text that the drag preview shows.
+    label: String,
+}
+
+impl DragPayload {
+    ///
+    pub fn new(kind: impl Into<String>, label: impl Into<String>, data: impl Any) -> Self {
+        DragPayload {
+            data: Rc::new(data),
+            kind: kind.into(),
+            label: label.into(),
+        }
+    }
+
+    /// Returns a reference to the payload's data if it is of type `T`.
+    pub fn data<T: 'static>(&self) -> Option<&T> {
+        self.data.downcast_ref::<T>()
+    }
+
+    ///
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    ///
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+/// The data of a drop of a payload on a drop target.
+pub struct DropData {
+    /// The modifier keys that were held down.
+    pub modifiers: Modifiers,
+    ///
+    pub payload: DragPayload,
+    /// The position of the pointer.
+    pub position: Point,
+    /// The ID of the widget that was dragged.
+    pub source_widget_id: WidgetId,
+    /// The ID of the drop target.
+    pub target_widget_id: WidgetId,
+}
+
+/// Maps the data of a drop to an application event.
+pub type DropMapper<APP_EVENT> = Rc<dyn Fn(&DropData) -> APP_EVENT>;
+
+/// A widget on which payloads of certain kinds can be dropped.
+#[derive(Clone)]
+pub struct DropTarget<APP_EVENT> {
+    /// The kinds of the payloads that can be dropped.
+    accepted_kinds: Vec<String>,
+    drop_mapper: DropMapper<APP_EVENT>,
+}
+
+impl<APP_EVENT> DropTarget<APP_EVENT> {
+    /// Creates a drop target that accepts payloads of the given kinds. A drop produces the
+    /// application event that the given mapper returns for the drop's data.
+    pub fn new(
+        accepted_kinds: Vec<String>,
+        drop_mapper: impl Fn(&DropData) -> APP_EVENT + 'static,
+    ) -> Self {
+        DropTarget {
+            accepted_kinds,
+            drop_mapper: Rc::new(drop_mapper),
+        }
+    }
+
+    /// Returns whether payloads of the given kind can be dropped.
+    pub fn accepts(&self, kind: &str) -> bool {
+        self.accepted_kinds
+            .iter()
+            .any(|accepted_kind| accepted_kind == kind)
+    }
+
+    /// Returns the application event for the given drop.
+    pub fn app_event(&self, drop_data: &DropData) -> APP_EVENT {
+        (self.drop_mapper)(drop_data)
+    }
+}
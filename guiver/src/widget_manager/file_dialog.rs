@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Maps the paths of the files chosen in a file dialog to an application event. If the dialog was
+/// cancelled, there are no paths.
+pub type FileDialogMapper<APP_EVENT> = Rc<dyn Fn(&[PathBuf]) -> APP_EVENT>;
+
+/// The options of a file dialog.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileDialogOptions {
+    /// The file name that a save dialog proposes.
+    pub default_file_name: Option<String>,
+    /// The types of the files that can be chosen. If there are none, all files can be chosen.
+    pub file_types: Vec<FileType>,
+    /// Whether several files can be chosen in an open dialog.
+    pub has_multiple_selection: bool,
+    /// The directory that the dialog shows first.
+    pub starting_directory: Option<PathBuf>,
+    pub title: Option<String>,
+}
+
+/// A type of files, e.g. "Text" with the extensions "txt" and "md".
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileType {
+    /// The extensions without a leading dot.
+    pub extensions: Vec<String>,
+    pub name: String,
+}
+
+impl FileType {
+    ///
+    pub fn new(name: impl Into<String>, extensions: &[&str]) -> Self {
+        FileType {
+            extensions: extensions
+                .iter()
+                .map(|extension| extension.to_string())
+                .collect(),
+            name: name.into(),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::WidgetId;
+
+/// The order in which Tab and Shift+Tab move the focus between the widgets that accept focus.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FocusOrder {
+    /// The order in which the widgets were created.
+    #[default]
+    Creation,
+    /// The given order. Widgets that are not listed do not receive focus via Tab.
+    Explicit(Vec<WidgetId>),
+    /// The visual order: line by line from top to bottom, each line from left to right.
+    Geometry,
+    /// Depth-first through the main widget's widget tree, siblings in visual order. Unlike
+    /// `Geometry`, a container's widgets are traversed before those of the next container.
+    WidgetTree,
+}
@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+/// The thresholds with which a widget manager recognizes gestures from pointer events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GestureThresholds {
+    /// The maximum distance between the two clicks of a double click.
+    pub double_click_distance: f64,
+    /// The maximum time between the two clicks of a double click.
+    pub double_click_interval: Duration,
+    /// The distance that the pointer has to move while it is pressed to start a drag.
+    pub drag_distance: f64,
+    /// The time that the pointer has to stay pressed without dragging for a long press.
+    pub long_press_duration: Duration,
+}
+
+impl Default for GestureThresholds {
+    fn default() -> Self {
+        GestureThresholds {
+            double_click_distance: 4.0,
+            double_click_interval: Duration::from_millis(500),
+            drag_distance: 4.0,
+            long_press_duration: Duration::from_millis(600),
+        }
+    }
+}
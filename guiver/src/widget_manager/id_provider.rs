@@ -1,7 +1,9 @@
-use crate::WidgetId;
+use crate::{EventObservationHandle, WidgetId};
 
 ///
 pub struct WidgetIdProvider {
+    /// The counter for the next event observation handle.
+    next_event_observation_handle_counter: EventObservationHandle,
     /// The counter for the next widget ID.
     next_widget_id_counter: WidgetId,
 }
@@ -10,10 +12,17 @@ impl WidgetIdProvider {
     ///
     pub fn new() -> Self {
         WidgetIdProvider {
+            next_event_observation_handle_counter: 0,
             next_widget_id_counter: 0,
         }
     }
 
+    ///
+    pub fn next_event_observation_handle(&mut self) -> EventObservationHandle {
+        self.next_event_observation_handle_counter += 1;
+        self.next_event_observation_handle_counter
+    }
+
     ///
     pub fn next_widget_id(&mut self) -> WidgetId {
         self.next_widget_id_counter += 1;
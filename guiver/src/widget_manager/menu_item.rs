@@ -0,0 +1,36 @@
+use crate::Shortcut;
+
+/// An item of a menu, e.g. a context menu or a menu bar.
+#[derive(Clone, Debug)]
+pub enum MenuItem<APP_EVENT> {
+    /// An item that produces the given application event in `handle_event()` when it is selected.
+    Action {
+        app_event: APP_EVENT,
+        /// Whether the item shows a check mark.
+        is_checked: bool,
+        is_enabled: bool,
+        label: String,
+        /// The item's optional keyboard shortcut. Native menus use it as accelerator, other menus
+        /// only display it.
+        shortcut: Option<Shortcut>,
+    },
+    /// A line separating groups of items.
+    Separator,
+    /// An item that opens a submenu.
+    Submenu {
+        is_enabled: bool,
+        label: String,
+        menu_items: Vec<MenuItem<APP_EVENT>>,
+    },
+}
+
+impl<APP_EVENT> MenuItem<APP_EVENT> {
+    /// Returns whether the item can be highlighted and selected.
+    pub fn is_selectable(&self) -> bool {
+        match self {
+            MenuItem::Action { is_enabled, .. } => *is_enabled,
+            MenuItem::Separator => false,
+            MenuItem::Submenu { is_enabled, .. } => *is_enabled,
+        }
+    }
+}
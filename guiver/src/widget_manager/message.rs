@@ -0,0 +1,62 @@
+/// A text that is looked up by its key in the message catalogs of the current locale, e.g. the
+/// Fluent message `greeting = Hello, { $name }!`. The widget manager looks it up again whenever the
+/// locale changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    /// The named arguments that the translations refer to, e.g. `name` for `{ $name }`.
+    pub arguments: Vec<(String, MessageArgument)>,
+    /// The message's key in the message catalogs. It is shown if no catalog has the message.
+    pub key: String,
+}
+
+impl Message {
+    /// Creates a message without arguments.
+    pub fn new(key: impl Into<String>) -> Self {
+        Message {
+            arguments: vec![],
+            key: key.into(),
+        }
+    }
+
+    /// Returns the message with the given named argument added.
+    pub fn with_argument(
+        mut self,
+        name: impl Into<String>,
+        argument: impl Into<MessageArgument>,
+    ) -> Self {
+        self.arguments.push((name.into(), argument.into()));
+        self
+    }
+}
+
+/// An argument of a message. Translations can select a variant by a number's plural category,
+/// e.g. "1 file" vs. "2 files".
+#[derive(Clone, Debug, PartialEq)]
+pub enum MessageArgument {
+    Number(f64),
+    Text(String),
+}
+
+impl From<f64> for MessageArgument {
+    fn from(number: f64) -> Self {
+        MessageArgument::Number(number)
+    }
+}
+
+impl From<usize> for MessageArgument {
+    fn from(number: usize) -> Self {
+        MessageArgument::Number(number as f64)
+    }
+}
+
+impl From<&str> for MessageArgument {
+    fn from(text: &str) -> Self {
+        MessageArgument::Text(text.to_string())
+    }
+}
+
+impl From<String> for MessageArgument {
+    fn from(text: String) -> Self {
+        MessageArgument::Text(text)
+    }
+}
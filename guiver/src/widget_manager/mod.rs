@@ -2,7 +2,14 @@ use crate::widget_manager::command::Command;
 use crate::WidgetError;
 
 pub mod command;
+pub mod drag_and_drop;
+pub mod file_dialog;
+pub mod focus_order;
+pub mod gesture_thresholds;
 pub mod id_provider;
+pub mod menu_item;
+pub mod message;
+pub mod shortcut;
 
 pub trait WidgetManager<APP_EVENT> {
     ///
@@ -10,6 +17,7 @@ pub trait WidgetManager<APP_EVENT> {
         self.handle_commands(vec![command])
     }
 
-    ///
+    /// Handles the given commands as a transaction: if a command fails, the widget manager is left
+    /// as it was before and a `WidgetError::CommandFailed` is returned.
     fn handle_commands(&mut self, commands: Vec<Command<APP_EVENT>>) -> Result<(), WidgetError>;
 }
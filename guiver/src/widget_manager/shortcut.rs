@@ -0,0 +1,153 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A keyboard shortcut, i.e. a key together with modifier keys, like "Ctrl+C".
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Shortcut {
+    pub has_alt: bool,
+    pub has_ctrl: bool,
+    pub has_meta: bool,
+    pub has_shift: bool,
+    /// Either a lowercase character like "c" or a key name like "F1" or "Enter", as used by the
+    /// `key` attribute of the W3C UI Events.
+    pub key: String,
+}
+
+impl Shortcut {
+    /// Creates a shortcut without modifier keys.
+    pub fn new(key: impl Into<String>) -> Self {
+        let key = key.into();
+
+        Shortcut {
+            has_alt: false,
+            has_ctrl: false,
+            has_meta: false,
+            has_shift: false,
+            // Characters are lowercase.
+            key: if key.chars().count() == 1 {
+                key.to_lowercase()
+            } else {
+                key
+            },
+        }
+    }
+
+    /// Creates a shortcut with the platform's primary modifier key: "Meta" (the Command key) on
+    /// macOS and "Ctrl" otherwise.
+    pub fn primary(key: impl Into<String>) -> Self {
+        let mut shortcut = Shortcut::new(key);
+
+        if cfg!(target_os = "macos") {
+            shortcut.has_meta = true;
+        } else {
+            shortcut.has_ctrl = true;
+        }
+
+        shortcut
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.has_ctrl {
+            write!(f, "Ctrl+")?;
+        }
+
+        if self.has_alt {
+            write!(f, "Alt+")?;
+        }
+
+        if self.has_shift {
+            write!(f, "Shift+")?;
+        }
+
+        if self.has_meta {
+            if cfg!(target_os = "macos") {
+                write!(f, "Cmd+")?;
+            } else {
+                write!(f, "Meta+")?;
+            }
+        }
+
+        // The key is a character.
+        if self.key.chars().count() == 1 {
+            write!(f, "{}", self.key.to_uppercase())
+        }
+        // The key is a key name.
+        else {
+            write!(f, "{}", self.key)
+        }
+    }
+}
+
+/// The error returned when parsing a `Shortcut` fails.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ParseShortcutError(pub String);
+
+impl fmt::Display for ParseShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid shortcut: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseShortcutError {}
+
+impl FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    /// Parses a shortcut like "Ctrl+C", "Primary+Shift+S" or "F5". "Primary" is the platform's
+    /// primary modifier key, see `Shortcut::primary()`.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        // Split the string into the modifier keys and the key. "Ctrl++" has the key "+".
+        let (modifiers, key) = if let Some(modifiers) = string.strip_suffix("++") {
+            (modifiers, "+")
+        } else {
+            string.rsplit_once('+').unwrap_or(("", string))
+        };
+
+        // There is no key.
+        if key.is_empty() {
+            return Err(ParseShortcutError(string.to_string()));
+        }
+
+        let mut shortcut = Shortcut::new(key);
+
+        // Iterate over the modifier keys.
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "alt" | "option" => shortcut.has_alt = true,
+                "control" | "ctrl" => shortcut.has_ctrl = true,
+                "cmd" | "command" | "meta" | "super" => shortcut.has_meta = true,
+                "primary" if cfg!(target_os = "macos") => shortcut.has_meta = true,
+                "primary" => shortcut.has_ctrl = true,
+                "shift" => shortcut.has_shift = true,
+                _ => return Err(ParseShortcutError(string.to_string())),
+            }
+        }
+
+        Ok(shortcut)
+    }
+}
+
+/// What a keyboard shortcut does.
+#[derive(Clone, Debug)]
+pub enum ShortcutAction<APP_EVENT> {
+    /// Produces the given application event in `handle_event()`.
+    AppEvent(APP_EVENT),
+    /// Copies the focused widget's selected value to the clipboard.
+    Copy,
+    /// Copies the focused widget's selected value to the clipboard and removes it.
+    Cut,
+    /// Pastes the clipboard content into the focused widget.
+    Paste,
+    /// Resets the zoom factor of the window's content to 1.
+    ResetZoom,
+    /// Selects the focused widget's whole value.
+    SelectAll,
+    /// Undoes the focused widget's last change.
+    Undo,
+    /// Zooms into the window's content.
+    ZoomIn,
+    /// Zooms out of the window's content.
+    ZoomOut,
+}